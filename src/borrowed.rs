@@ -0,0 +1,275 @@
+//! A borrowed variant of [`Value`](crate::Value), parsed without copying
+//! strings out of the input where possible.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::borrow::Cow;
+//!
+//! let v = twic::borrowed::from_str(r#"msg:hello!,quoted:"a\tb";"#).unwrap();
+//! assert!(matches!(v["msg"].as_str(), Some("hello!")));
+//! assert!(matches!(v["msg"], twic::borrowed::Value::String(Cow::Borrowed(_))));
+//! assert!(matches!(v["quoted"], twic::borrowed::Value::String(Cow::Owned(_))));
+//! let owned: twic::Value = r#"msg:hello!,quoted:"a\tb";"#.parse().unwrap();
+//! assert_eq!(v.into_owned(), owned);
+//! ```
+
+use alloc::{borrow::Cow, collections::BTreeMap, vec::Vec};
+use core::ops::Range;
+
+use crate::{
+    de::{ParseError, Reader, Scalar, Tree, build},
+    value::{self, Number},
+};
+
+/// A map value borrowing from the parsed input, mapping string keys to
+/// [`Value`]s.
+pub type Map<'a> = BTreeMap<Cow<'a, str>, Value<'a>>;
+
+/// Represents a Twic value whose strings and keys are borrowed from the parsed
+/// input where possible.
+///
+/// Strings are borrowed unless they are quoted strings containing escape
+/// sequences, which are decoded into owned strings.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Value<'a> {
+    /// Represents a Twic null value.
+    #[default]
+    Null,
+    /// Represents a Twic boolean value.
+    Boolean(bool),
+    /// Represents a Twic number value.
+    Number(Number),
+    /// Represents a Twic string value.
+    String(Cow<'a, str>),
+    /// Represents a Twic string value that is not valid UTF-8.
+    Bytes(Vec<u8>),
+    /// Represents a Twic vector value.
+    Vector(Vec<Value<'a>>),
+    /// Represents a Twic map value.
+    Map(Map<'a>),
+}
+
+/// Predicates and accessors for [`Value`].
+impl<'a> Value<'a> {
+    /// Checks if the value is null.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    /// Returns the boolean value if the value is a boolean, `None` otherwise.
+    pub fn as_boolean(&self) -> Option<bool> {
+        if let Value::Boolean(b) = self {
+            Some(*b)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number value if the value is a number, `None` otherwise.
+    pub fn as_number(&self) -> Option<Number> {
+        if let Value::Number(n) = self {
+            Some(n.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the string slice if the value is a string, `None` otherwise.
+    pub fn as_str(&self) -> Option<&str> {
+        if let Value::String(s) = self {
+            Some(s)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the vector if the value is a vector, `None` otherwise.
+    pub fn as_vector(&self) -> Option<&Vec<Value<'a>>> {
+        if let Value::Vector(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the map if the value is a map, `None` otherwise.
+    pub fn as_map(&self) -> Option<&Map<'a>> {
+        if let Value::Map(m) = self {
+            Some(m)
+        } else {
+            None
+        }
+    }
+
+    /// Converts the value into an owned [`Value`](crate::Value), copying all
+    /// borrowed strings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let v = twic::borrowed::from_str("users::alice,bob;;").unwrap();
+    /// assert_eq!(
+    ///     v.into_owned(),
+    ///     twic::Value::map_from([("users", twic::Value::vector_from(["alice", "bob"]))]),
+    /// );
+    /// ```
+    pub fn into_owned(self) -> value::Value {
+        match self {
+            Value::Null => value::Value::Null,
+            Value::Boolean(b) => value::Value::Boolean(b),
+            Value::Number(n) => value::Value::Number(n),
+            Value::String(s) => value::Value::String(s.into_owned()),
+            Value::Bytes(b) => value::Value::Bytes(b),
+            Value::Vector(v) => {
+                value::Value::Vector(v.into_iter().map(Value::into_owned).collect())
+            }
+            Value::Map(m) => value::Value::Map(
+                m.into_iter()
+                    .map(|(k, v)| (k.into_owned(), v.into_owned()))
+                    .collect::<value::Map>(),
+            ),
+        }
+    }
+}
+
+impl<'a> From<Value<'a>> for value::Value {
+    fn from(value: Value<'a>) -> Self {
+        value.into_owned()
+    }
+}
+
+/// The null value returned when indexing a missing key or index.
+static NULL: Value<'static> = Value::Null;
+
+impl<'a> core::ops::Index<&str> for Value<'a> {
+    type Output = Value<'a>;
+
+    /// Returns the value of the given key if the value is a map, or
+    /// [`Value::Null`] if the key does not exist.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is not a map.
+    fn index(&self, key: &str) -> &Value<'a> {
+        match self {
+            Value::Map(m) => m.get(key).unwrap_or(&NULL),
+            _ => panic!("twic value is not a map"),
+        }
+    }
+}
+
+impl<'a> core::ops::Index<usize> for Value<'a> {
+    type Output = Value<'a>;
+
+    /// Returns the element at the given index if the value is a vector, or
+    /// [`Value::Null`] if the index is out of bounds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is not a vector.
+    fn index(&self, index: usize) -> &Value<'a> {
+        match self {
+            Value::Vector(v) => v.get(index).unwrap_or(&NULL),
+            _ => panic!("twic value is not a vector"),
+        }
+    }
+}
+
+impl<'a> Tree<'a> for Value<'a> {
+    type Map = Map<'a>;
+
+    fn scalar(scalar: Scalar<'a>, _: Range<usize>) -> Self {
+        match scalar {
+            Scalar::Null => Value::Null,
+            Scalar::Boolean(b) => Value::Boolean(b),
+            Scalar::Number(n) => Value::Number(n),
+            Scalar::String(s) => Value::String(s),
+            Scalar::Bytes(b) => Value::Bytes(b),
+        }
+    }
+
+    fn vector(elements: Vec<Self>, _: Range<usize>) -> Self {
+        Value::Vector(elements)
+    }
+
+    fn map(map: Map<'a>, _: Range<usize>) -> Self {
+        Value::Map(map)
+    }
+
+    fn contains_key(map: &Map<'a>, key: &str) -> bool {
+        map.contains_key(key)
+    }
+
+    fn insert(map: &mut Map<'a>, key: Cow<'a, str>, _: Range<usize>, value: Self) {
+        map.insert(key, value);
+    }
+}
+
+/// Parses Twic text into a borrowed [`Value`].
+///
+/// Strings and keys are borrowed from the input unless they are quoted strings
+/// containing escape sequences. Otherwise, it behaves exactly like
+/// [`twic::from_str`](crate::from_str) parsing into a
+/// [`Value`](crate::Value).
+///
+/// # Examples
+///
+/// ```
+/// let v = twic::borrowed::from_str("profile:name:twic,version:0.1;;").unwrap();
+/// assert_eq!(v["profile"]["name"].as_str(), Some("twic"));
+/// assert!(twic::borrowed::from_str("a:1,a:2;").is_err());
+/// ```
+pub fn from_str(s: &str) -> Result<Value<'_>, ParseError> {
+    build(Reader::new(s))
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::borrow::Cow;
+
+    use super::{Value, from_str};
+    use crate::de::{ParseErrorKind, parse_value};
+
+    #[test]
+    fn test_borrowing() {
+        let s = r#"plain:a, "quoted key":"b", "esc\u0041":"c\n", v::x,"y";;"#;
+        let v = from_str(s).unwrap();
+
+        let Value::Map(m) = &v else {
+            panic!("not a map")
+        };
+        let borrowed: alloc::vec::Vec<_> = m
+            .keys()
+            .map(|k| (k.as_ref(), matches!(k, Cow::Borrowed(_))))
+            .collect();
+        assert_eq!(
+            borrowed,
+            [
+                ("escA", false),
+                ("plain", true),
+                ("quoted key", true),
+                ("v", true)
+            ]
+        );
+        assert!(matches!(&v["plain"], Value::String(Cow::Borrowed("a"))));
+        assert!(matches!(
+            &v["quoted key"],
+            Value::String(Cow::Borrowed("b"))
+        ));
+        assert!(matches!(&v["escA"], Value::String(Cow::Owned(s)) if s == "c\n"));
+        assert!(matches!(&v["v"][1], Value::String(Cow::Borrowed("y"))));
+        assert!(v["missing"].is_null());
+        assert!(v["v"][2].is_null());
+
+        assert_eq!(v.into_owned(), parse_value(s).unwrap());
+    }
+
+    #[test]
+    fn test_errors() {
+        let e = from_str("a:1, a:2;").unwrap_err();
+        assert_eq!(e.kind(), &ParseErrorKind::DuplicateKey("a".into()));
+        assert_eq!(e.offset(), 5);
+        assert!(from_str(":1,").is_err());
+    }
+}
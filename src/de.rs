@@ -0,0 +1,581 @@
+//! Parsing Twic text into [`Value`]s.
+//!
+//! Float literals are converted with `core`'s `f64::from_str`, which rounds
+//! correctly to the nearest float, ties to even, for mantissas of any length
+//! and exponents of any size. Floats are written back with `core`'s `Debug`
+//! formatting, which prints the shortest text that parses back to the same
+//! bits. Both are part of `core`, so the crate needs no float conversion code
+//! of its own to stay `no_std`.
+
+use alloc::{borrow::Cow, vec::Vec};
+use core::{ops::Range, str::FromStr};
+
+#[cfg(feature = "decimal")]
+use crate::value::Decimal;
+use crate::value::{Map, Number, NumberReprs, Path, Value};
+
+#[cfg(feature = "serde")]
+pub(crate) mod deserializer;
+mod error;
+mod lexer;
+mod number;
+mod parser;
+mod reader;
+mod spanned;
+mod tree;
+
+#[cfg(feature = "serde")]
+#[doc(inline)]
+pub use deserializer::Deserializer;
+#[cfg(feature = "serde")]
+pub(crate) use deserializer::{KeyDeserializer, visit_f32, visit_f64, visit_number};
+#[doc(inline)]
+pub use error::{ExpectedToken, ExpectedTokens, ParseError, ParseErrorKind};
+pub(crate) use number::number_repr;
+#[cfg(all(feature = "serde", any(feature = "bigint", feature = "decimal")))]
+pub(crate) use number::parse_number_exact;
+#[doc(inline)]
+pub use parser::{Event, Scalar};
+#[doc(inline)]
+pub use reader::Reader;
+#[doc(inline)]
+pub use spanned::{Spanned, SpannedEntry, SpannedMap, SpannedValue, parse_spanned};
+
+pub(crate) use lexer::{is_keyword, is_structural};
+pub(crate) use parser::Parser;
+pub(crate) use tree::{Tree, build};
+
+/// Parses Twic text into a [`Value`].
+pub(crate) fn parse_value(s: &str) -> Result<Value, ParseError> {
+    read_value(Reader::new(s))
+}
+
+/// Reads a whole [`Value`] from a reader.
+fn read_value(reader: Reader<'_>) -> Result<Value, ParseError> {
+    tree::build(reader)
+}
+
+impl<'a> Tree<'a> for Value {
+    type Map = Map;
+
+    fn scalar(scalar: Scalar<'a>, _: Range<usize>) -> Self {
+        match scalar {
+            Scalar::Null => Value::Null,
+            Scalar::Boolean(b) => Value::Boolean(b),
+            Scalar::Number(n) => Value::Number(n),
+            Scalar::String(s) => Value::String(s.into_owned()),
+            Scalar::Bytes(b) => Value::Bytes(b),
+        }
+    }
+
+    fn vector(elements: Vec<Self>, _: Range<usize>) -> Self {
+        Value::Vector(elements)
+    }
+
+    fn map(map: Map, _: Range<usize>) -> Self {
+        Value::Map(map)
+    }
+
+    fn contains_key(map: &Map, key: &str) -> bool {
+        map.contains_key(key)
+    }
+
+    fn insert(map: &mut Map, key: Cow<'a, str>, _: Range<usize>, value: Self) {
+        map.insert(key.into_owned(), value);
+    }
+}
+
+/// Records the spellings of the numbers of a spanned value at `path` that are
+/// not written the default way.
+fn record_reprs(s: &str, value: &Spanned<SpannedValue>, path: &mut Path, reprs: &mut NumberReprs) {
+    match &value.value {
+        SpannedValue::Number(n) => {
+            if let Some(repr) = number_repr(&s[value.span.clone()], n) {
+                reprs.insert(path.clone(), n.clone(), repr);
+            }
+        }
+        SpannedValue::Vector(v) => {
+            for (i, item) in v.iter().enumerate() {
+                path.push(i);
+                record_reprs(s, item, path, reprs);
+                path.pop();
+            }
+        }
+        SpannedValue::Map(m) => {
+            for (key, entry) in m {
+                path.push(key.as_str());
+                record_reprs(s, &entry.value, path, reprs);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Parses Twic text into a [`Value`].
+///
+/// Whitespaces around the value are ignored. Integer literals outside the
+/// range of integer [`Number`]s are parsed as the nearest float. Duplicate keys
+/// in a map are rejected.
+///
+/// # Examples
+///
+/// ```
+/// use twic::Value;
+///
+/// let v = twic::from_str("profile:name:twic,version:0.1;,users::alice,bob;;").unwrap();
+/// assert_eq!(v["profile"]["name"], "twic");
+/// assert_eq!(v["profile"]["version"], 0.1);
+/// assert_eq!(v["users"], Value::vector_from(["alice", "bob"]));
+/// ```
+///
+/// ```
+/// use twic::{Number, Value};
+///
+/// assert_eq!(twic::from_str("null").unwrap(), Value::Null);
+/// assert_eq!(twic::from_str("-0x10").unwrap(), -16);
+/// assert_eq!(twic::from_str("1e400").unwrap(), Value::number(Number::Inf { negative: false }));
+/// assert_eq!(twic::from_str(r#""\u{1F600}\x41""#).unwrap(), "😀A");
+/// assert_eq!(twic::from_str(";").unwrap(), Value::map_empty());
+/// assert_eq!(twic::from_str(":;").unwrap(), Value::vector_empty());
+/// assert!(twic::from_str("a:1,a:2;").is_err());
+/// ```
+pub fn from_str(s: &str) -> Result<Value, ParseError> {
+    parse_value(s)
+}
+
+/// Parses Twic text into a [`Value`] like [`from_str`], except that number
+/// literals with a fraction or an exponent are parsed as exact [`Decimal`]s
+/// when their significant digits fit in a `u128` and their exponent in an
+/// `i16`, and as the nearest floats otherwise.
+///
+/// Available with the `decimal` feature.
+///
+/// # Examples
+///
+/// ```
+/// use twic::value::{Decimal, Number};
+///
+/// let v = twic::de::from_str_decimal("price:19.90,rate:1e-3,count:3;").unwrap();
+/// assert_eq!(v["price"].as_number(), Some(Number::Decimal(Decimal::new(199, -1).unwrap())));
+/// assert_eq!(v["rate"].as_number(), Some(Number::Decimal(Decimal::new(1, -3).unwrap())));
+/// assert_eq!(v["count"], 3);
+/// assert_eq!(twic::from_str("price:19.90;").unwrap()["price"], 19.9);
+/// ```
+#[cfg(feature = "decimal")]
+pub fn from_str_decimal(s: &str) -> Result<Value, ParseError> {
+    read_value(Reader::new(s).decimals(true))
+}
+
+/// Parses Twic text into a [`Value`] like [`from_str`], also returning the
+/// spellings of its number literals.
+///
+/// Each number literal that is not written the way the number is written by
+/// default, such as `0xFF00`, `007`, `+1` or `1.5E3`, gets an entry in the
+/// returned [`NumberReprs`] at its path in the value. Give the table to
+/// [`PrettyFormatter::number_reprs`](crate::ser::PrettyFormatter::number_reprs)
+/// or [`CompactFormatter::number_reprs`](crate::ser::CompactFormatter::number_reprs)
+/// to write the numbers back with their spelling.
+///
+/// # Examples
+///
+/// ```
+/// use twic::{Number, Path, ser::PrettyFormatter, value::Radix};
+///
+/// let (v, reprs) = twic::de::from_str_with_reprs("mask:0xFF00,ids::7,007;;").unwrap();
+/// assert_eq!(v["mask"], 65280);
+/// assert_eq!(reprs.len(), 2);
+/// let mask = reprs.get(&"mask".parse::<Path>().unwrap(), &Number::from(65280));
+/// assert_eq!(mask.unwrap().radix, Radix::Hexadecimal);
+/// let id = reprs.get(&"ids[1]".parse::<Path>().unwrap(), &Number::from(7));
+/// assert_eq!(id.unwrap().min_digits, 3);
+///
+/// let f = PrettyFormatter::new().number_reprs(reprs);
+/// assert_eq!(f.to_string(&v).unwrap(), "ids: :7, 007;, mask: 0xFF00;");
+/// ```
+pub fn from_str_with_reprs(s: &str) -> Result<(Value, NumberReprs), ParseError> {
+    let spanned = parse_spanned(s)?;
+    let mut reprs = NumberReprs::new();
+    record_reprs(s, &spanned, &mut Path::new(), &mut reprs);
+    Ok((spanned.value.into_value(), reprs))
+}
+
+impl FromStr for Value {
+    type Err = ParseError;
+
+    /// Parses Twic text into a [`Value`]. See [`from_str`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::Value;
+    ///
+    /// let v: Value = "msg:hello!,from:twic;".parse().unwrap();
+    /// assert_eq!(v["msg"], "hello!");
+    /// assert_eq!(v["from"], "twic");
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_value(s)
+    }
+}
+
+impl FromStr for Number {
+    type Err = ParseError;
+
+    /// Parses a Twic number literal, i.e. a decimal or hexadecimal number,
+    /// `nan`, or `inf` with an optional sign. Surrounding whitespaces are not
+    /// allowed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ParseErrorKind::InvalidNumber`] if `s` is not
+    /// a valid number literal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::Number;
+    ///
+    /// assert_eq!("-0x10".parse::<Number>().unwrap(), Number::from(-16));
+    /// assert_eq!("2.5e-1".parse::<Number>().unwrap(), Number::Float(0.25));
+    /// assert_eq!("-inf".parse::<Number>().unwrap(), Number::Inf { negative: true });
+    /// assert!("1.".parse::<Number>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        number::parse_number(s)
+            .ok_or_else(|| ParseError::new(ParseErrorKind::InvalidNumber, 0).locate(s))
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl FromStr for Decimal {
+    type Err = ParseError;
+
+    /// Parses a decimal number literal exactly, e.g. `-1.50` or `2e-3`.
+    /// Surrounding whitespaces are not allowed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ParseErrorKind::InvalidNumber`] if `s` is not
+    /// a valid decimal literal, or if its significant digits do not fit in a
+    /// `u128` or its exponent is out of the range of `i16`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Decimal;
+    ///
+    /// assert_eq!("-1.50".parse::<Decimal>().unwrap(), Decimal::new(-15, -1).unwrap());
+    /// assert_eq!("2e-3".parse::<Decimal>().unwrap().to_string(), "0.002");
+    /// assert!("0x10".parse::<Decimal>().is_err());
+    /// assert!("1e40000".parse::<Decimal>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        number::parse_decimal(s)
+            .ok_or_else(|| ParseError::new(ParseErrorKind::InvalidNumber, 0).locate(s))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::{
+        string::{String, ToString},
+        vec::Vec,
+    };
+
+    use super::{ExpectedToken, ParseErrorKind, from_str_with_reprs, parse_value};
+    #[cfg(feature = "decimal")]
+    use crate::value::Decimal;
+    use crate::value::{Number, NumberRepr, Path, Radix, Value};
+
+    fn err(s: &str) -> (ParseErrorKind, usize) {
+        let e = parse_value(s).unwrap_err();
+        (e.kind().clone(), e.offset())
+    }
+
+    #[test]
+    fn test_scalars() {
+        assert_eq!(parse_value("null").unwrap(), Value::Null);
+        assert_eq!(parse_value(" true ").unwrap(), true);
+        assert_eq!(parse_value("false").unwrap(), false);
+        assert_eq!(parse_value("nan").unwrap(), Value::number(Number::NaN));
+        assert_eq!(
+            parse_value("-inf").unwrap(),
+            Value::number(Number::Inf { negative: true })
+        );
+        assert_eq!(parse_value("hello!").unwrap(), "hello!");
+        assert_eq!(parse_value("nanny").unwrap(), "nanny");
+        assert_eq!(parse_value(".5").unwrap(), ".5");
+        assert_eq!(parse_value("a\"b").unwrap(), "a\"b");
+        assert_eq!(parse_value("\u{3000}x\u{3000}").unwrap(), "x");
+        assert_eq!(parse_value(r#""null""#).unwrap(), "null");
+        assert_eq!(parse_value(r#""""#).unwrap(), "");
+    }
+
+    #[test]
+    fn test_numbers() {
+        let n = |s: &str| parse_value(s).unwrap().as_number().unwrap();
+
+        assert!(matches!(n("42"), Number::PosInt(42)));
+        assert!(matches!(n("+007"), Number::PosInt(7)));
+        assert!(matches!(n("-0"), Number::PosInt(0)));
+        assert!(matches!(n("-1"), Number::NegInt(u64::MAX)));
+        assert!(matches!(n("0x0fF"), Number::PosInt(255)));
+        assert!(matches!(
+            n("18446744073709551615"),
+            Number::PosInt(u64::MAX)
+        ));
+        assert!(matches!(n("-18446744073709551616"), Number::NegInt(0)));
+        assert!(matches!(n("-0x10000000000000000"), Number::NegInt(0)));
+        #[cfg(not(feature = "bigint"))]
+        {
+            assert!(
+                matches!(n("18446744073709551616"), Number::Float(f) if f == 18446744073709551616.0)
+            );
+            assert!(
+                matches!(n("0x1000000000000000000000000000000000"), Number::Float(f) if f == 2f64.powi(132))
+            );
+        }
+        #[cfg(feature = "bigint")]
+        {
+            assert!(matches!(n("18446744073709551616"), Number::BigInt(_)));
+            assert_eq!(
+                n("+018446744073709551616").to_string(),
+                "18446744073709551616"
+            );
+            assert_eq!(
+                n("-0x10000000000000001").to_string(),
+                "-18446744073709551617"
+            );
+            assert_eq!(
+                n("0x1000000000000000000000000000000000").as_f64(),
+                2f64.powi(132)
+            );
+            assert!(matches!(n("18446744073709551616.0"), Number::Float(_)));
+        }
+        assert!(matches!(n("1.0"), Number::Float(1.0)));
+        assert!(matches!(n("-0.0"), Number::Float(f) if f.is_sign_negative()));
+        assert!(matches!(n("1e3"), Number::Float(1000.0)));
+        assert!(matches!(n("2.5E-1"), Number::Float(0.25)));
+        assert!(matches!(n("1e400"), Number::Inf { negative: false }));
+
+        // decimals are opt-in
+        #[cfg(feature = "decimal")]
+        {
+            let n = |s: &str| super::from_str_decimal(s).unwrap().as_number().unwrap();
+            let d = |s: &str| match n(s) {
+                Number::Decimal(d) => (d.is_negative(), d.mantissa(), d.exponent()),
+                n => panic!("{} parsed as {:?}", s, n),
+            };
+            assert_eq!(d("1.0"), (false, 1, 0));
+            assert_eq!(d("-2.50e-1"), (true, 25, -2));
+            assert_eq!(d("1e3"), (false, 1, 3));
+            assert_eq!(d("0.000e99999999999999999999"), (false, 0, 0));
+            assert_eq!(d("-100.0100"), (true, 10001, -2));
+            assert_eq!(d("1e32767"), (false, 1, 32767));
+            assert_eq!(
+                d("340282366920938463463374607431768211455000.0e-3"),
+                (false, u128::MAX, 0)
+            );
+            assert_eq!(n("-0.0").to_string(), "-0.0");
+            assert!(matches!(n("1e32768"), Number::Inf { negative: false }));
+            assert!(matches!(
+                n("340282366920938463463374607431768211456.0"),
+                Number::Float(_)
+            ));
+            assert!(matches!(n("1e-99999"), Number::Float(0.0)));
+            assert!(matches!(n("0x1E"), Number::PosInt(30)));
+            assert!(matches!(n("-12"), Number::NegInt(_)));
+            assert_eq!("1.50".parse::<Decimal>().unwrap().to_string(), "1.5");
+            assert_eq!("-7".parse::<Decimal>().unwrap().to_string(), "-7.0");
+            for s in ["inf", "nan", "0x1E", "1.", "1e32768", " 1.0"] {
+                assert!(s.parse::<Decimal>().is_err(), "{}", s);
+            }
+        }
+
+        for s in [
+            "1.", "1e", "0x", "0xg", "+nan", "1_000", "--1", "+", "1.5.2", "0X1",
+        ] {
+            assert_eq!(err(s), (ParseErrorKind::InvalidNumber, 0), "{}", s);
+            assert!(s.parse::<Number>().is_err(), "{}", s);
+        }
+
+        for s in [
+            "0",
+            "-0x1F",
+            "+1.5e3",
+            "nan",
+            "+inf",
+            "-18446744073709551616",
+        ] {
+            assert_eq!(s.parse::<Number>().unwrap(), n(s), "{}", s);
+        }
+        for s in ["", " 1", "1 ", "x", "null"] {
+            let e = s.parse::<Number>().unwrap_err();
+            assert_eq!(e.kind(), &ParseErrorKind::InvalidNumber, "{}", s);
+        }
+    }
+
+    #[test]
+    fn test_escapes() {
+        assert_eq!(
+            parse_value(r#""\"\\\/\b\f\n\r\t""#).unwrap(),
+            "\"\\/\u{8}\u{c}\n\r\t"
+        );
+        assert_eq!(parse_value(r#""é\u{e9}\xc3\xa9""#).unwrap(), "ééé");
+        assert_eq!(parse_value(r#""\ud83d\ude00""#).unwrap(), "😀");
+        assert_eq!(parse_value("\"a\nb\"").unwrap(), "a\nb");
+        assert_eq!(
+            parse_value(r#"x:"a\xffé\xc3";"#).unwrap()["x"],
+            Value::Bytes(b"a\xff\xc3\xa9\xc3".into())
+        );
+
+        assert_eq!(err(r#""\q""#), (ParseErrorKind::InvalidEscape, 1));
+        assert_eq!(err(r#""\u12""#), (ParseErrorKind::InvalidHexDigit, 5));
+        assert_eq!(err(r#""\u{1g}""#), (ParseErrorKind::InvalidHexDigit, 5));
+        assert_eq!(err(r#""\u{}""#), (ParseErrorKind::InvalidEscape, 1));
+        assert_eq!(
+            err(r#""\u{123456789}""#),
+            (ParseErrorKind::InvalidEscape, 1)
+        );
+        assert_eq!(err(r#""a\xg0""#), (ParseErrorKind::InvalidHexDigit, 4));
+        assert_eq!(err(r#""a\x0"#), (ParseErrorKind::InvalidHexDigit, 5));
+        assert_eq!(
+            err(r#""\ud800""#),
+            (ParseErrorKind::InvalidUnicodeEscape, 1)
+        );
+        assert_eq!(
+            err(r#""\u{110000}""#),
+            (ParseErrorKind::InvalidUnicodeEscape, 1)
+        );
+        assert_eq!(err(r#"x:"\xff":1;"#), (ParseErrorKind::InvalidUtf8, 2));
+        assert_eq!(err(r#""\xc3":1;"#), (ParseErrorKind::InvalidUtf8, 0));
+        assert_eq!(err(r#""abc"#), (ParseErrorKind::UnterminatedString, 0));
+    }
+
+    #[test]
+    fn test_containers() {
+        let v = parse_value(" : 1 , :; , ;, a:b;, :x;  ; ").unwrap();
+        assert_eq!(
+            v,
+            Value::vector([
+                1.into(),
+                Value::vector_empty(),
+                Value::map_empty(),
+                Value::map_from([("a", "b")]),
+                Value::vector_from(["x"]),
+            ])
+        );
+
+        let v = parse_value(r#"a:b:c;, "key with spaces" : "v", x:;;"#).unwrap();
+        assert_eq!(v["a"]["b"], "c");
+        assert_eq!(v["key with spaces"], "v");
+        assert_eq!(v["x"], Value::map_empty());
+    }
+
+    #[test]
+    fn test_number_reprs() {
+        let (v, reprs) = from_str_with_reprs(":0x1,2,a:+3,b::4,005;;,6;").unwrap();
+        assert_eq!(v, parse_value(":1,2,a:3,b::4,5;;,6;").unwrap());
+        let paths: Vec<String> = reprs.iter().map(|(p, _, _)| p.to_string()).collect();
+        assert_eq!(paths, ["[0]", "[2].a", "[2].b[1]"]);
+        assert!(
+            reprs
+                .iter()
+                .all(|(_, _, repr)| *repr != NumberRepr::default())
+        );
+        assert!(
+            reprs
+                .iter()
+                .all(|(p, n, _)| v[p] == Value::Number(n.clone()))
+        );
+
+        let (_, reprs) = from_str_with_reprs("0xff").unwrap();
+        let repr = reprs.get(&Path::new(), &Number::from(255)).unwrap();
+        assert_eq!(repr.radix, Radix::Hexadecimal);
+        assert_eq!(reprs.get(&Path::new(), &Number::from(255.0)), None);
+        assert!(
+            from_str_with_reprs("a:1,b:0.5,c:-inf;")
+                .unwrap()
+                .1
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(err(""), (ParseErrorKind::UnexpectedEof, 0));
+        assert_eq!(err("  "), (ParseErrorKind::UnexpectedEof, 2));
+        assert_eq!(err(","), (ParseErrorKind::UnexpectedChar(','), 0));
+        assert_eq!(err("a b"), (ParseErrorKind::UnexpectedChar('b'), 2));
+        assert_eq!(err("a:1"), (ParseErrorKind::MissingSemicolon, 3));
+        assert_eq!(err(":1,2"), (ParseErrorKind::MissingSemicolon, 4));
+        assert_eq!(err(":1,;"), (ParseErrorKind::MissingSemicolon, 4));
+        assert_eq!(err(":1,"), (ParseErrorKind::UnexpectedEof, 3));
+        assert_eq!(err("a:1,2:3;"), (ParseErrorKind::UnexpectedChar('2'), 4));
+        assert_eq!(err("a:1,b;"), (ParseErrorKind::UnexpectedChar(';'), 5));
+        assert_eq!(err("a:1;;"), (ParseErrorKind::UnexpectedChar(';'), 4));
+        assert_eq!(err("\"a\"b"), (ParseErrorKind::UnexpectedChar('b'), 3));
+        assert_eq!(
+            err("a:1, a:2;"),
+            (ParseErrorKind::DuplicateKey("a".into()), 5)
+        );
+    }
+
+    #[test]
+    fn test_error_locations() {
+        let expected = |s: &str| {
+            let e = parse_value(s).unwrap_err();
+            e.expected().iter().collect::<Vec<_>>()
+        };
+
+        assert_eq!(expected(""), [ExpectedToken::Value]);
+        assert_eq!(
+            expected(":"),
+            [ExpectedToken::Value, ExpectedToken::Semicolon]
+        );
+        assert_eq!(expected("a:1,"), [ExpectedToken::Key]);
+        assert_eq!(expected("a:1,b;"), [ExpectedToken::Colon]);
+        assert_eq!(
+            expected("a:1"),
+            [ExpectedToken::Comma, ExpectedToken::Semicolon]
+        );
+        assert_eq!(expected("a b"), [ExpectedToken::Eof]);
+        assert_eq!(expected(r#""\q""#), []);
+
+        let e = parse_value("a: 1,\r\n\tb: :x, y\n;").unwrap_err();
+        assert_eq!(e.kind(), &ParseErrorKind::MissingSemicolon);
+        assert_eq!((e.offset(), e.line(), e.column()), (18, 3, 2));
+        assert_eq!(
+            e.to_string(),
+            "\
+missing `;` at end of input, expected `,` or `;` at line 3, column 2
+  |
+3 | ;
+  |  ^"
+        );
+
+        let e = parse_value("a: 1,\r\n\tb: \"x\\q\"\n;").unwrap_err();
+        assert_eq!((e.line(), e.column()), (2, 7));
+        assert_eq!(
+            e.to_string(),
+            "\
+invalid escape sequence at line 2, column 7
+  |
+2 | \tb: \"x\\q\"
+  | \t     ^"
+        );
+
+        let long = alloc::format!("{}:{};", "k".repeat(100), "v".repeat(100));
+        let e = parse_value(&long[..long.len() - 1]).unwrap_err();
+        assert_eq!((e.line(), e.column()), (1, 202));
+        let rendered = e.to_string();
+        let lines: Vec<_> = rendered.lines().collect();
+        assert_eq!(lines[2], alloc::format!("1 | ...{}", "v".repeat(40)));
+        assert_eq!(lines[3], alloc::format!("  | {}^", " ".repeat(43)));
+
+        let e = parse_value(&"\n".repeat(12)).unwrap_err();
+        assert_eq!((e.line(), e.column()), (13, 1));
+        assert!(e.to_string().ends_with("\n   |\n13 | \n   | ^"));
+    }
+}
@@ -0,0 +1,640 @@
+use alloc::borrow::Cow;
+#[cfg(any(feature = "bigint", feature = "decimal"))]
+use alloc::string::ToString;
+#[cfg(any(feature = "bigint", feature = "decimal"))]
+use core::iter;
+
+#[cfg(any(feature = "bigint", feature = "decimal"))]
+use serde::de::value::MapDeserializer;
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor, value::CowStrDeserializer};
+
+use super::{
+    error::{ParseError, ParseErrorKind},
+    number::parse_number,
+    parser::{Event, Parser, Scalar},
+};
+use crate::value::Number;
+
+/// Deserializes a Rust value from Twic text.
+///
+/// Maps, structs and struct variants are read from Twic maps, and sequences,
+/// tuples and tuple variants from Twic vectors. `null` is read as `None`, `()`
+/// and unit structs. Map keys are always strings in Twic, but are parsed as
+/// integers, floats or booleans when the key type requires it.
+///
+/// Enums use the externally tagged representation: a unit variant is the
+/// variant name as a string, and any other variant is a map with the variant
+/// name as its single key. See the [crate documentation](crate#serde-support)
+/// for details.
+///
+/// # Errors
+///
+/// Returns an error if the text is not valid Twic, or if it does not match the
+/// structure expected by `T`.
+///
+/// # Examples
+///
+/// ```
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize, Debug, PartialEq)]
+/// struct Config {
+///     name: String,
+///     port: u16,
+///     users: Vec<String>,
+///     proxy: Option<String>,
+/// }
+///
+/// let cfg: Config = twic::serde::from_str("name:twic,port:8080,users::alice,bob;,proxy:null;").unwrap();
+/// assert_eq!(
+///     cfg,
+///     Config {
+///         name: "twic".into(),
+///         port: 8080,
+///         users: vec!["alice".into(), "bob".into()],
+///         proxy: None,
+///     },
+/// );
+///
+/// assert!(twic::serde::from_str::<Config>("name:twic,port:-1,users:;,proxy:null;").is_err());
+/// ```
+pub fn from_str<'de, T: de::Deserialize<'de>>(s: &'de str) -> Result<T, ParseError> {
+    let mut deserializer = Deserializer::new(s);
+    let value = T::deserialize(&mut deserializer).map_err(|e| {
+        e.or_offset(deserializer.parser.event_span().start)
+            .locate(s)
+    })?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+/// A serde [`Deserializer`](de::Deserializer) reading Twic text.
+///
+/// See [`from_str`] for how Twic values are mapped to Rust values.
+pub struct Deserializer<'de> {
+    parser: Parser<'de>,
+    peeked: Option<Event<'de>>,
+}
+
+impl<'de> Deserializer<'de> {
+    /// Creates a deserializer reading from the given Twic text.
+    pub fn new(input: &'de str) -> Self {
+        Deserializer {
+            parser: Parser::new(input),
+            peeked: None,
+        }
+    }
+
+    /// Checks that the input has been fully consumed, with only whitespaces
+    /// after the deserialized value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use twic::de::Deserializer;
+    ///
+    /// let mut de = Deserializer::new(":1,2; ");
+    /// let v = Vec::<u8>::deserialize(&mut de).unwrap();
+    /// assert_eq!(v, [1, 2]);
+    /// de.end().unwrap();
+    ///
+    /// let mut de = Deserializer::new(":1,2; 3");
+    /// Vec::<u8>::deserialize(&mut de).unwrap();
+    /// assert!(de.end().is_err());
+    /// ```
+    pub fn end(&mut self) -> Result<(), ParseError> {
+        match self.peeked.take() {
+            Some(_) => Err(self.custom_error("unexpected trailing value")),
+            None => match self.parser.next_event()? {
+                Some(_) => Err(self.custom_error("unexpected trailing value")),
+                None => Ok(()),
+            },
+        }
+    }
+
+    /// Reads the next event.
+    fn next(&mut self) -> Result<Event<'de>, ParseError> {
+        match self.peeked.take() {
+            Some(event) => Ok(event),
+            None => self
+                .parser
+                .next_event()?
+                .ok_or_else(|| self.custom_error("unexpected end of value")),
+        }
+    }
+
+    /// Peeks the next event without consuming it.
+    fn peek(&mut self) -> Result<&Event<'de>, ParseError> {
+        if self.peeked.is_none() {
+            self.peeked = Some(self.next()?);
+        }
+        Ok(self.peeked.as_ref().unwrap())
+    }
+
+    /// Creates a custom error at the offset of the last event.
+    fn custom_error(&self, msg: &str) -> ParseError {
+        ParseError::new(
+            ParseErrorKind::Custom(msg.into()),
+            self.parser.event_span().start,
+        )
+    }
+
+    /// Consumes the `End` event closing a container whose visitor has
+    /// returned.
+    fn end_container(&mut self, what: &str) -> Result<(), ParseError> {
+        match self.next()? {
+            Event::End => Ok(()),
+            _ => Err(self.custom_error(what)),
+        }
+    }
+}
+
+/// Visits a number with the narrowest visitor method holding it exactly.
+pub(crate) fn visit_number<'de, V: Visitor<'de>>(
+    n: Number,
+    visitor: V,
+) -> Result<V::Value, ParseError> {
+    match n {
+        Number::PosInt(n) => visitor.visit_u64(n),
+        Number::NegInt(n) if n >= 1 << 63 => visitor.visit_i64(n as i64),
+        Number::NegInt(n) => visitor.visit_i128(n as i128 - (1 << 64)),
+        Number::Float(f) => visitor.visit_f64(f),
+        Number::NaN => visitor.visit_f64(f64::NAN),
+        Number::Inf { negative: false } => visitor.visit_f64(f64::INFINITY),
+        Number::Inf { negative: true } => visitor.visit_f64(f64::NEG_INFINITY),
+        #[cfg(feature = "bigint")]
+        Number::BigInt(n) => match (n.to_i128(), n.to_u128()) {
+            (Some(v), _) => visitor.visit_i128(v),
+            (_, Some(v)) => visitor.visit_u128(v),
+            // see `Number::SERDE_TOKEN`
+            _ => visitor.visit_map(MapDeserializer::new(iter::once((
+                Number::SERDE_TOKEN,
+                n.to_string(),
+            )))),
+        },
+        #[cfg(feature = "decimal")]
+        Number::Decimal(d) => visitor.visit_map(MapDeserializer::new(iter::once((
+            Number::SERDE_TOKEN,
+            d.to_string(),
+        )))),
+    }
+}
+
+/// Visits a number requested as an `f32`, converting decimals to the nearest
+/// `f32` instead of passing them exactly.
+pub(crate) fn visit_f32<'de, V: Visitor<'de>>(
+    n: Number,
+    visitor: V,
+) -> Result<V::Value, ParseError> {
+    #[cfg(feature = "decimal")]
+    if let Number::Decimal(d) = n {
+        return visitor.visit_f32(d.to_f32());
+    }
+    visit_number(n, visitor)
+}
+
+/// Visits a number requested as an `f64`, converting decimals to the nearest
+/// `f64` instead of passing them exactly.
+pub(crate) fn visit_f64<'de, V: Visitor<'de>>(
+    n: Number,
+    visitor: V,
+) -> Result<V::Value, ParseError> {
+    #[cfg(feature = "decimal")]
+    if let Number::Decimal(d) = n {
+        return visitor.visit_f64(d.to_f64());
+    }
+    visit_number(n, visitor)
+}
+
+/// Visits a string, borrowing from the input when possible.
+fn visit_cow_str<'de, V: Visitor<'de>>(
+    s: Cow<'de, str>,
+    visitor: V,
+) -> Result<V::Value, ParseError> {
+    match s {
+        Cow::Borrowed(s) => visitor.visit_borrowed_str(s),
+        Cow::Owned(s) => visitor.visit_string(s),
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = ParseError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ParseError> {
+        match self.next()? {
+            Event::Scalar(Scalar::Null) => visitor.visit_unit(),
+            Event::Scalar(Scalar::Boolean(b)) => visitor.visit_bool(b),
+            Event::Scalar(Scalar::Number(n)) => visit_number(n, visitor),
+            Event::Scalar(Scalar::String(s)) => visit_cow_str(s, visitor),
+            Event::Scalar(Scalar::Bytes(b)) => visitor.visit_byte_buf(b),
+            Event::StartVector => {
+                let value = visitor.visit_seq(SeqAccess { de: self })?;
+                self.end_container("too many elements in vector")?;
+                Ok(value)
+            }
+            Event::StartMap => {
+                let value = visitor.visit_map(MapAccess { de: self })?;
+                self.end_container("too many entries in map")?;
+                Ok(value)
+            }
+            Event::Key(_) | Event::End => {
+                unreachable!("the parser only emits keys and ends after a value")
+            }
+        }
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ParseError> {
+        match self.next()? {
+            Event::Scalar(Scalar::Number(n)) => visit_f32(n, visitor),
+            event => {
+                self.peeked = Some(event);
+                self.deserialize_any(visitor)
+            }
+        }
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ParseError> {
+        match self.next()? {
+            Event::Scalar(Scalar::Number(n)) => visit_f64(n, visitor),
+            event => {
+                self.peeked = Some(event);
+                self.deserialize_any(visitor)
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ParseError> {
+        if let Event::Scalar(Scalar::Null) = self.peek()? {
+            self.peeked = None;
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, ParseError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ParseError> {
+        match self.next()? {
+            Event::Scalar(Scalar::String(s)) => visitor.visit_enum(CowStrDeserializer::new(s)),
+            Event::StartMap => {
+                let Event::Key(variant) = self.next()? else {
+                    return Err(self.custom_error("expected a map with a single key for an enum"));
+                };
+                let value = visitor.visit_enum(EnumAccess { de: self, variant })?;
+                self.end_container("expected a map with a single key for an enum")?;
+                Ok(value)
+            }
+            _ => Err(self.custom_error("expected a string or a map for an enum")),
+        }
+    }
+
+    fn is_human_readable(&self) -> bool {
+        true
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+/// Accesses the elements of a vector.
+struct SeqAccess<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess<'_, 'de> {
+    type Error = ParseError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, ParseError> {
+        if let Event::End = self.de.peek()? {
+            return Ok(None);
+        }
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+}
+
+/// Accesses the entries of a map.
+struct MapAccess<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess<'_, 'de> {
+    type Error = ParseError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, ParseError> {
+        match self.de.next()? {
+            Event::Key(key) => seed.deserialize(KeyDeserializer::new(key)).map(Some),
+            event => {
+                // leave the `End` event for the caller
+                self.de.peeked = Some(event);
+                Ok(None)
+            }
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, ParseError> {
+        seed.deserialize(&mut *self.de)
+    }
+}
+
+/// Accesses the variant of an enum written as a single-key map.
+struct EnumAccess<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+    variant: Cow<'de, str>,
+}
+
+impl<'a, 'de> de::EnumAccess<'de> for EnumAccess<'a, 'de> {
+    type Error = ParseError;
+    type Variant = &'a mut Deserializer<'de>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), ParseError> {
+        let variant = seed.deserialize(self.variant.into_deserializer())?;
+        Ok((variant, self.de))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for &mut Deserializer<'de> {
+    type Error = ParseError;
+
+    fn unit_variant(self) -> Result<(), ParseError> {
+        de::Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, ParseError> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, ParseError> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ParseError> {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
+
+/// Deserializes a map key, parsing it as a number or a boolean when the key
+/// type requires it.
+pub(crate) struct KeyDeserializer<'de> {
+    key: Cow<'de, str>,
+}
+
+impl<'de> KeyDeserializer<'de> {
+    /// Creates a deserializer for the given map key.
+    pub(crate) fn new(key: Cow<'de, str>) -> Self {
+        KeyDeserializer { key }
+    }
+
+    /// Creates an error for a key that cannot be read as the expected type.
+    fn invalid(&self, expected: &str) -> ParseError {
+        de::Error::custom(alloc::format!(
+            "invalid map key {:?}, expected {}",
+            self.key,
+            expected
+        ))
+    }
+}
+
+macro_rules! deserialize_numeric_key {
+    ($visit:ident: $($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ParseError> {
+                match parse_number(&self.key) {
+                    Some(n) => $visit(n, visitor),
+                    None => Err(self.invalid("a number")),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for KeyDeserializer<'de> {
+    type Error = ParseError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ParseError> {
+        visit_cow_str(self.key, visitor)
+    }
+
+    deserialize_numeric_key! {
+        visit_number:
+        deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64 deserialize_i128
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64 deserialize_u128
+    }
+    deserialize_numeric_key! { visit_f32: deserialize_f32 }
+    deserialize_numeric_key! { visit_f64: deserialize_f64 }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ParseError> {
+        match &*self.key {
+            "true" => visitor.visit_bool(true),
+            "false" => visitor.visit_bool(false),
+            _ => Err(self.invalid("a boolean")),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, ParseError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ParseError> {
+        visitor.visit_enum(CowStrDeserializer::new(self.key))
+    }
+
+    serde::forward_to_deserialize_any! {
+        char str string bytes byte_buf option unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::{
+        collections::BTreeMap,
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+
+    use serde::Deserialize;
+
+    use super::from_str;
+    use crate::de::ParseErrorKind;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Rect(u32, u32),
+        Polygon { sides: u8, name: Option<String> },
+    }
+
+    #[derive(Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum Color {
+        Red,
+        Green,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Unit;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Meters(f32);
+
+    #[test]
+    fn test_scalars() {
+        assert_eq!(from_str::<u8>("255").unwrap(), 255);
+        assert!(from_str::<u8>("256").is_err());
+        assert_eq!(from_str::<i64>("-0x10").unwrap(), -16);
+        assert_eq!(
+            from_str::<i128>("-18446744073709551616").unwrap(),
+            -(1 << 64)
+        );
+        assert_eq!(
+            from_str::<u128>("18446744073709551615").unwrap(),
+            u64::MAX as u128
+        );
+        assert_eq!(from_str::<f64>("2.5e-1").unwrap(), 0.25);
+        assert_eq!(from_str::<f64>("3").unwrap(), 3.0);
+        assert!(from_str::<f64>("nan").unwrap().is_nan());
+        assert_eq!(from_str::<f32>("-inf").unwrap(), f32::NEG_INFINITY);
+        assert!(from_str::<bool>(" true ").unwrap());
+        assert_eq!(from_str::<char>("é").unwrap(), 'é');
+        assert_eq!(from_str::<String>(r#""a\nb""#).unwrap(), "a\nb");
+        assert_eq!(from_str::<&str>("hello!").unwrap(), "hello!");
+        assert!(from_str::<&str>(r#""a\nb""#).is_err());
+        assert_eq!(from_str::<()>("null").unwrap(), ());
+        assert_eq!(from_str::<Unit>("null").unwrap(), Unit);
+        assert_eq!(from_str::<Meters>("1.5").unwrap(), Meters(1.5));
+        assert_eq!(from_str::<Option<u8>>("null").unwrap(), None);
+        assert_eq!(from_str::<Option<u8>>("1").unwrap(), Some(1));
+        assert!(from_str::<String>("1").is_err());
+    }
+
+    #[test]
+    fn test_containers() {
+        assert_eq!(from_str::<Vec<u8>>(":1, 2 ,3;").unwrap(), [1, 2, 3]);
+        assert!(from_str::<Vec<u8>>(":;").unwrap().is_empty());
+        assert_eq!(from_str::<(u8, String)>(":1,a;").unwrap(), (1, "a".into()));
+        assert!(from_str::<(u8, u8)>(":1;").is_err());
+        assert!(from_str::<(u8, u8)>(":1,2,3;").is_err());
+        assert_eq!(
+            from_str::<Vec<Vec<u8>>>("::1;,:;;").unwrap(),
+            vec![vec![1], vec![]]
+        );
+
+        let m = from_str::<BTreeMap<u32, bool>>(r#""1":true,"02":false;"#).unwrap();
+        assert_eq!(m, BTreeMap::from([(1, true), (2, false)]));
+        assert!(from_str::<BTreeMap<u32, bool>>("a:true;").is_err());
+        let m = from_str::<BTreeMap<bool, ()>>(";").unwrap();
+        assert!(m.is_empty());
+        let m = from_str::<BTreeMap<bool, ()>>(r#""true":null;"#).unwrap();
+        assert_eq!(m, BTreeMap::from([(true, ())]));
+    }
+
+    #[test]
+    fn test_enums() {
+        assert_eq!(from_str::<Shape>("Empty").unwrap(), Shape::Empty);
+        assert_eq!(from_str::<Shape>("Empty:null;").unwrap(), Shape::Empty);
+        assert_eq!(
+            from_str::<Shape>("Circle:1.5;").unwrap(),
+            Shape::Circle(1.5)
+        );
+        assert_eq!(from_str::<Shape>("Rect::2,3;;").unwrap(), Shape::Rect(2, 3));
+        assert_eq!(
+            from_str::<Shape>("Polygon:sides:6,name:hex;;").unwrap(),
+            Shape::Polygon {
+                sides: 6,
+                name: Some("hex".into())
+            }
+        );
+        assert_eq!(
+            from_str::<BTreeMap<Color, u8>>("Red:1,Green:2;").unwrap(),
+            BTreeMap::from([(Color::Red, 1), (Color::Green, 2)])
+        );
+
+        for s in [
+            "Circle",
+            "Square",
+            "Circle:1,Empty:null;",
+            ";",
+            ":Empty;",
+            "1",
+        ] {
+            assert!(from_str::<Shape>(s).is_err(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        #[derive(Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Config {
+            name: String,
+            port: u16,
+        }
+
+        let e = from_str::<Config>("name:twic,port:x;").unwrap_err();
+        assert!(matches!(e.kind(), ParseErrorKind::Custom(_)));
+        assert_eq!(e.offset(), 15);
+        assert_eq!((e.line(), e.column()), (1, 16));
+
+        let e = from_str::<Config>("name:twic;").unwrap_err();
+        assert_eq!(
+            e.kind(),
+            &ParseErrorKind::Custom("missing field `port`".to_string())
+        );
+
+        let e = from_str::<Config>("name:twic,port:1").unwrap_err();
+        assert_eq!(e.kind(), &ParseErrorKind::MissingSemicolon);
+        assert_eq!(e.offset(), 16);
+
+        assert!(from_str::<u8>("1 2").is_err());
+        assert!(from_str::<u8>("").is_err());
+    }
+}
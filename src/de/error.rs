@@ -0,0 +1,391 @@
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+/// The kind of error that occurred while parsing Twic text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The input ended while more tokens were expected.
+    UnexpectedEof,
+    /// The input ended before the `;` closing a map or vector.
+    MissingSemicolon,
+    /// A character appeared where it is not allowed.
+    UnexpectedChar(char),
+    /// A quoted string is not terminated by a closing `"`.
+    UnterminatedString,
+    /// An escape sequence in a quoted string is invalid.
+    InvalidEscape,
+    /// A character in a `\xXX` or unicode escape sequence is not a
+    /// hexadecimal digit.
+    InvalidHexDigit,
+    /// A unicode escape sequence does not denote a valid unicode scalar value.
+    InvalidUnicodeEscape,
+    /// The input, or the bytes of a quoted string after processing `\xXX`
+    /// escapes, are not valid UTF-8.
+    InvalidUtf8,
+    /// A token starting with a digit, `+` or `-` is not a valid number.
+    InvalidNumber,
+    /// A key appears more than once in the same map.
+    DuplicateKey(String),
+    /// A custom error, such as a type mismatch reported while deserializing a
+    /// Rust value.
+    Custom(String),
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::UnexpectedEof => write!(f, "unexpected end of input"),
+            ParseErrorKind::MissingSemicolon => write!(f, "missing `;` at end of input"),
+            ParseErrorKind::UnexpectedChar(c) => write!(f, "unexpected character {:?}", c),
+            ParseErrorKind::UnterminatedString => write!(f, "unterminated quoted string"),
+            ParseErrorKind::InvalidEscape => write!(f, "invalid escape sequence"),
+            ParseErrorKind::InvalidHexDigit => write!(f, "invalid hexadecimal digit"),
+            ParseErrorKind::InvalidUnicodeEscape => {
+                write!(f, "unicode escape is not a valid unicode scalar value")
+            }
+            ParseErrorKind::InvalidUtf8 => write!(f, "invalid UTF-8"),
+            ParseErrorKind::InvalidNumber => write!(f, "invalid number"),
+            ParseErrorKind::DuplicateKey(key) => write!(f, "duplicate map key {:?}", key),
+            ParseErrorKind::Custom(msg) => f.write_str(msg),
+        }
+    }
+}
+
+/// A kind of token the parser may expect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExpectedToken {
+    /// The start of any value.
+    Value,
+    /// A map key, which is an unquoted or quoted string.
+    Key,
+    /// `:`
+    Colon,
+    /// `,`
+    Comma,
+    /// `;`
+    Semicolon,
+    /// The end of input.
+    Eof,
+}
+
+impl ExpectedToken {
+    const ALL: [ExpectedToken; 6] = [
+        ExpectedToken::Value,
+        ExpectedToken::Key,
+        ExpectedToken::Colon,
+        ExpectedToken::Comma,
+        ExpectedToken::Semicolon,
+        ExpectedToken::Eof,
+    ];
+}
+
+impl fmt::Display for ExpectedToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExpectedToken::Value => write!(f, "a value"),
+            ExpectedToken::Key => write!(f, "a key"),
+            ExpectedToken::Colon => write!(f, "`:`"),
+            ExpectedToken::Comma => write!(f, "`,`"),
+            ExpectedToken::Semicolon => write!(f, "`;`"),
+            ExpectedToken::Eof => write!(f, "end of input"),
+        }
+    }
+}
+
+/// A set of [`ExpectedToken`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ExpectedTokens(u8);
+
+impl ExpectedTokens {
+    /// The empty set.
+    pub const NONE: ExpectedTokens = ExpectedTokens(0);
+
+    /// Returns the set with the given token added.
+    pub(crate) const fn with(self, token: ExpectedToken) -> Self {
+        ExpectedTokens(self.0 | 1 << token as u8)
+    }
+
+    /// Checks if the set contains the given token.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::{Value, de::ExpectedToken};
+    ///
+    /// let err = ":1,2".parse::<Value>().unwrap_err();
+    /// assert!(err.expected().contains(ExpectedToken::Semicolon));
+    /// assert!(!err.expected().contains(ExpectedToken::Eof));
+    /// ```
+    pub fn contains(&self, token: ExpectedToken) -> bool {
+        self.0 & 1 << token as u8 != 0
+    }
+
+    /// Checks if the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns an iterator over the tokens in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::{Value, de::ExpectedToken};
+    ///
+    /// let err = ":1 2;".parse::<Value>().unwrap_err();
+    /// let expected: Vec<_> = err.expected().iter().collect();
+    /// assert_eq!(expected, [ExpectedToken::Comma, ExpectedToken::Semicolon]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = ExpectedToken> + '_ {
+        ExpectedToken::ALL
+            .into_iter()
+            .filter(|&token| self.contains(token))
+    }
+}
+
+impl fmt::Display for ExpectedTokens {
+    /// Formats the set as an English list, such as "`,` or `;`".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count = self.iter().count();
+        for (i, token) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(if i + 1 == count { " or " } else { ", " })?;
+            }
+            token.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// The part of the input around an error, kept to render it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Snippet {
+    /// 1-based line number.
+    line: usize,
+    /// 1-based column number, in characters.
+    column: usize,
+    /// The offending line, shortened around the error if it is too long.
+    text: String,
+    /// The character index in `text` at which the error occurred.
+    caret: usize,
+}
+
+/// The maximum number of characters of the offending line shown on each side
+/// of the error.
+const SNIPPET_RADIUS: usize = 40;
+
+impl Snippet {
+    /// Extracts the snippet around the given byte offset of the input.
+    fn new(input: &str, offset: usize) -> Self {
+        let offset = offset.min(input.len());
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        let line = input[..line_start].matches('\n').count() + 1;
+
+        let before = &input[line_start..offset];
+        let after = input[offset..line_end].trim_end_matches('\r');
+        let column = before.chars().count() + 1;
+
+        let mut text = String::new();
+        let mut caret = column - 1;
+        let skip = caret.saturating_sub(SNIPPET_RADIUS);
+        if skip > 0 {
+            text.push_str("...");
+            caret = caret - skip + 3;
+        }
+        text.extend(before.chars().skip(skip));
+
+        let mut after_chars = after.chars();
+        text.extend(after_chars.by_ref().take(SNIPPET_RADIUS));
+        if after_chars.next().is_some() {
+            text.push_str("...");
+        }
+
+        Snippet {
+            line,
+            column,
+            text,
+            caret,
+        }
+    }
+}
+
+/// An error that occurred while parsing Twic text.
+///
+/// Besides the [kind](ParseError::kind) of the error, it carries the
+/// [byte offset](ParseError::offset), [line](ParseError::line) and
+/// [column](ParseError::column) at which it occurred, and the set of tokens
+/// that were [expected](ParseError::expected) there. Its `Display` output
+/// renders the offending line with a caret under the error.
+///
+/// # Examples
+///
+/// ```
+/// use twic::Value;
+///
+/// let err = "name: twic,\nport 8080;".parse::<Value>().unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     "\
+/// unexpected character '8', expected `:` at line 2, column 6
+///   |
+/// 2 | port 8080;
+///   |      ^",
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    expected: ExpectedTokens,
+    /// `None` for errors raised by `Deserialize` implementations, until the
+    /// deserializer fills in where they occurred.
+    offset: Option<usize>,
+    /// `None` until the error is located in the input.
+    snippet: Option<Snippet>,
+}
+
+impl ParseError {
+    /// Creates a new parse error of the given kind at the given byte offset.
+    pub(crate) fn new(kind: ParseErrorKind, offset: usize) -> Self {
+        ParseError {
+            kind,
+            expected: ExpectedTokens::NONE,
+            offset: Some(offset),
+            snippet: None,
+        }
+    }
+
+    /// Sets the tokens that were expected where the error occurred.
+    pub(crate) fn expecting(mut self, expected: ExpectedTokens) -> Self {
+        self.expected = expected;
+        self
+    }
+
+    /// Sets the byte offset of the error if it is not known yet.
+    #[cfg(feature = "serde")]
+    pub(crate) fn or_offset(mut self, offset: usize) -> Self {
+        self.offset.get_or_insert(offset);
+        self
+    }
+
+    /// Computes the line and column of the error in the given input, if not
+    /// done yet.
+    pub(crate) fn locate(mut self, input: &str) -> Self {
+        if let (Some(offset), None) = (self.offset, &self.snippet) {
+            self.snippet = Some(Snippet::new(input, offset));
+        }
+        self
+    }
+
+    /// Returns the kind of the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::{Value, de::ParseErrorKind};
+    ///
+    /// let err = "\"abc".parse::<Value>().unwrap_err();
+    /// assert_eq!(err.kind(), &ParseErrorKind::UnterminatedString);
+    /// ```
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+
+    /// Returns the byte offset in the input at which the error occurred, or 0
+    /// if the error did not occur while reading text, such as one returned by
+    /// [`from_value`](crate::from_value).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::Value;
+    ///
+    /// let err = "a:1,b;".parse::<Value>().unwrap_err();
+    /// assert_eq!(err.offset(), 5);
+    /// ```
+    pub fn offset(&self) -> usize {
+        self.offset.unwrap_or(0)
+    }
+
+    /// Returns the 1-based line number at which the error occurred, or 0 if
+    /// the error is not located in any input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::Value;
+    ///
+    /// let err = "a: 1,\nb: \"2;".parse::<Value>().unwrap_err();
+    /// assert_eq!((err.line(), err.column()), (2, 4));
+    /// ```
+    pub fn line(&self) -> usize {
+        self.snippet.as_ref().map_or(0, |s| s.line)
+    }
+
+    /// Returns the 1-based column, in characters, at which the error occurred,
+    /// or 0 if the error is not located in any input.
+    pub fn column(&self) -> usize {
+        self.snippet.as_ref().map_or(0, |s| s.column)
+    }
+
+    /// Returns the set of tokens that were expected where the error occurred.
+    /// The set is empty for errors other than unexpected tokens or end of
+    /// input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::{Value, de::ExpectedToken};
+    ///
+    /// let err = "a:1,b;".parse::<Value>().unwrap_err();
+    /// assert_eq!(err.expected().iter().collect::<Vec<_>>(), [ExpectedToken::Colon]);
+    /// ```
+    pub fn expected(&self) -> ExpectedTokens {
+        self.expected
+    }
+}
+
+impl fmt::Display for ParseError {
+    /// Formats the error message with its location, followed by the
+    /// offending line with a caret under the error.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.kind.fmt(f)?;
+        if !self.expected.is_empty() {
+            write!(f, ", expected {}", self.expected)?;
+        }
+
+        match (&self.snippet, self.offset) {
+            (Some(snippet), _) => {
+                write!(f, " at line {}, column {}", snippet.line, snippet.column)?;
+
+                let gutter = snippet.line.ilog10() as usize + 1;
+                write!(f, "\n{:gutter$} |", "")?;
+                write!(f, "\n{} | {}", snippet.line, snippet.text)?;
+                write!(f, "\n{:gutter$} | ", "")?;
+                // keep tabs so that the caret lines up with the text above
+                for c in snippet.text.chars().take(snippet.caret) {
+                    f.write_char(if c == '\t' { '\t' } else { ' ' })?;
+                }
+                f.write_char('^')
+            }
+            (None, Some(offset)) => write!(f, " at byte offset {}", offset),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+#[cfg(feature = "serde")]
+impl serde::de::Error for ParseError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        ParseError {
+            kind: ParseErrorKind::Custom(alloc::format!("{}", msg)),
+            expected: ExpectedTokens::NONE,
+            offset: None,
+            snippet: None,
+        }
+    }
+}
@@ -0,0 +1,292 @@
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::ops::Range;
+
+use super::{
+    error::{ParseError, ParseErrorKind},
+    number::{parse_number, parse_number_exact},
+};
+use crate::value::Number;
+
+/// A token of Twic text.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TokenKind<'a> {
+    /// `:`
+    Colon,
+    /// `;`
+    Semicolon,
+    /// `,`
+    Comma,
+    /// `null`
+    Null,
+    /// `true` or `false`
+    Boolean(bool),
+    /// A number literal.
+    Number(Number),
+    /// An unquoted or quoted string.
+    String(Cow<'a, str>),
+    /// A quoted string whose bytes, after processing `\xXX` escapes, are not
+    /// valid UTF-8.
+    Bytes(Vec<u8>),
+}
+
+/// A token together with its byte range in the input.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Token<'a> {
+    pub kind: TokenKind<'a>,
+    pub span: Range<usize>,
+}
+
+/// Splits Twic text into tokens, skipping whitespaces between them.
+pub(crate) struct Lexer<'a> {
+    input: &'a str,
+    pos: usize,
+    /// Whether number literals with a fraction or an exponent are read as
+    /// exact decimals.
+    decimals: bool,
+}
+
+/// Checks if the character is one of the structural characters `:`, `;`, `,`.
+pub(crate) fn is_structural(c: char) -> bool {
+    matches!(c, ':' | ';' | ',')
+}
+
+/// Checks if the string is one of the keywords `null`, `true`, `false`, `nan`,
+/// `inf`.
+pub(crate) fn is_keyword(s: &str) -> bool {
+    matches!(s, "null" | "true" | "false" | "nan" | "inf")
+}
+
+impl<'a> Lexer<'a> {
+    /// Creates a lexer over the given input.
+    pub fn new(input: &'a str) -> Self {
+        Lexer {
+            input,
+            pos: 0,
+            decimals: false,
+        }
+    }
+
+    /// Sets whether number literals with a fraction or an exponent are read
+    /// as exact decimals.
+    #[cfg(feature = "decimal")]
+    pub fn set_decimals(&mut self, decimals: bool) {
+        self.decimals = decimals;
+    }
+
+    /// Returns the input being tokenized.
+    pub fn input(&self) -> &'a str {
+        self.input
+    }
+
+    /// Returns the byte offset of the next unread character.
+    pub fn offset(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the next character without consuming it.
+    fn peek_char(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    /// Skips whitespaces and returns the offset of the next character.
+    pub fn skip_whitespace(&mut self) -> usize {
+        let rest = &self.input[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+        self.pos
+    }
+
+    /// Reads the next token, or returns `None` at the end of input.
+    pub fn next_token(&mut self) -> Result<Option<Token<'a>>, ParseError> {
+        let start = self.skip_whitespace();
+
+        let Some(c) = self.peek_char() else {
+            return Ok(None);
+        };
+
+        let kind = match c {
+            ':' => {
+                self.pos += 1;
+                TokenKind::Colon
+            }
+            ';' => {
+                self.pos += 1;
+                TokenKind::Semicolon
+            }
+            ',' => {
+                self.pos += 1;
+                TokenKind::Comma
+            }
+            '"' => self.quoted_string()?,
+            _ => self.word()?,
+        };
+
+        Ok(Some(Token {
+            kind,
+            span: start..self.pos,
+        }))
+    }
+
+    /// Reads a keyword, number or unquoted string.
+    fn word(&mut self) -> Result<TokenKind<'a>, ParseError> {
+        let start = self.pos;
+        let rest = &self.input[start..];
+        let len = rest
+            .find(|c: char| c.is_whitespace() || is_structural(c))
+            .unwrap_or(rest.len());
+        let word = &rest[..len];
+        self.pos += len;
+
+        Ok(match word {
+            "null" => TokenKind::Null,
+            "true" => TokenKind::Boolean(true),
+            "false" => TokenKind::Boolean(false),
+            "nan" => TokenKind::Number(Number::NaN),
+            "inf" => TokenKind::Number(Number::Inf { negative: false }),
+            _ if word.starts_with(|c: char| c.is_ascii_digit() || c == '+' || c == '-') => {
+                let number = if self.decimals {
+                    parse_number_exact(word)
+                } else {
+                    parse_number(word)
+                };
+                match number {
+                    Some(n) => TokenKind::Number(n),
+                    None => return Err(ParseError::new(ParseErrorKind::InvalidNumber, start)),
+                }
+            }
+            _ => TokenKind::String(Cow::Borrowed(word)),
+        })
+    }
+
+    /// Reads a quoted string, processing escape sequences. The string is
+    /// borrowed from the input if it contains no escape sequences, and read as
+    /// bytes if the escape sequences make it invalid UTF-8.
+    fn quoted_string(&mut self) -> Result<TokenKind<'a>, ParseError> {
+        let quote = self.pos;
+        let bytes = self.input.as_bytes();
+        let mut buf: Option<Vec<u8>> = None;
+        let mut chunk = quote + 1;
+        let mut i = chunk;
+
+        loop {
+            match bytes.get(i) {
+                None => {
+                    return Err(ParseError::new(ParseErrorKind::UnterminatedString, quote));
+                }
+                Some(b'"') => break,
+                Some(b'\\') => {
+                    let buf = buf.get_or_insert_with(Vec::new);
+                    buf.extend_from_slice(&bytes[chunk..i]);
+                    i = self.escape(i, buf)?;
+                    chunk = i;
+                }
+                Some(_) => i += 1,
+            }
+        }
+
+        self.pos = i + 1;
+
+        Ok(match buf {
+            None => TokenKind::String(Cow::Borrowed(&self.input[chunk..i])),
+            Some(mut buf) => {
+                buf.extend_from_slice(&bytes[chunk..i]);
+                match String::from_utf8(buf) {
+                    Ok(s) => TokenKind::String(Cow::Owned(s)),
+                    Err(e) => TokenKind::Bytes(e.into_bytes()),
+                }
+            }
+        })
+    }
+
+    /// Decodes the escape sequence starting with the backslash at `start` into
+    /// `buf`, returning the offset right after the sequence.
+    fn escape(&self, start: usize, buf: &mut Vec<u8>) -> Result<usize, ParseError> {
+        let bytes = self.input.as_bytes();
+        let invalid = |kind| Err(ParseError::new(kind, start));
+
+        let simple = match bytes.get(start + 1) {
+            Some(b'"') => b'"',
+            Some(b'\\') => b'\\',
+            Some(b'/') => b'/',
+            Some(b'b') => b'\x08',
+            Some(b'f') => b'\x0c',
+            Some(b'n') => b'\n',
+            Some(b'r') => b'\r',
+            Some(b't') => b'\t',
+            Some(b'x') => {
+                let byte = self.hex(start + 2, 2)?;
+                buf.push(byte as u8);
+                return Ok(start + 4);
+            }
+            Some(b'u') => {
+                let (code, end) = self.unicode_escape(start)?;
+                let Some(c) = char::from_u32(code) else {
+                    return invalid(ParseErrorKind::InvalidUnicodeEscape);
+                };
+                buf.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                return Ok(end);
+            }
+            _ => return invalid(ParseErrorKind::InvalidEscape),
+        };
+
+        buf.push(simple);
+        Ok(start + 2)
+    }
+
+    /// Decodes a `\uXXXX` or `\u{X...}` escape sequence starting at `start`,
+    /// combining UTF-16 surrogate pairs written as two `\uXXXX` sequences.
+    /// Returns the code point and the offset right after the sequence.
+    fn unicode_escape(&self, start: usize) -> Result<(u32, usize), ParseError> {
+        let bytes = self.input.as_bytes();
+        let invalid = |kind| Err(ParseError::new(kind, start));
+
+        if bytes.get(start + 2) == Some(&b'{') {
+            let digits = bytes[start + 3..]
+                .iter()
+                .take_while(|b| b.is_ascii_hexdigit())
+                .count();
+
+            let end = start + 3 + digits;
+            if digits > 8 {
+                return invalid(ParseErrorKind::InvalidEscape);
+            }
+            if bytes.get(end) != Some(&b'}') {
+                return Err(ParseError::new(ParseErrorKind::InvalidHexDigit, end));
+            }
+            if digits == 0 {
+                return invalid(ParseErrorKind::InvalidEscape);
+            }
+
+            let code = self.hex(start + 3, digits)?;
+            return Ok((code, end + 1));
+        }
+
+        let code = self.hex(start + 2, 4)?;
+
+        if (0xD800..0xDC00).contains(&code)
+            && bytes[start + 6..].starts_with(b"\\u")
+            && let Ok(low @ 0xDC00..0xE000) = self.hex(start + 8, 4)
+        {
+            let code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+            return Ok((code, start + 12));
+        }
+
+        Ok((code, start + 6))
+    }
+
+    /// Parses exactly `len` hexadecimal digits starting at `start`.
+    fn hex(&self, start: usize, len: usize) -> Result<u32, ParseError> {
+        let bytes = self.input.as_bytes();
+        if let Some(i) =
+            (start..start + len).find(|&i| !bytes.get(i).is_some_and(u8::is_ascii_hexdigit))
+        {
+            return Err(ParseError::new(
+                ParseErrorKind::InvalidHexDigit,
+                i.min(bytes.len()),
+            ));
+        }
+
+        // all digits are ASCII, so the slice is a valid `str`, and at most 8
+        // digits always fit
+        Ok(u32::from_str_radix(&self.input[start..start + len], 16).unwrap_or(u32::MAX))
+    }
+}
@@ -0,0 +1,515 @@
+#[cfg(feature = "bigint")]
+use crate::value::BigInt;
+use alloc::string::String;
+
+#[cfg(feature = "decimal")]
+use crate::value::Decimal;
+use crate::{
+    ser::write_number,
+    value::{Notation, Number, NumberRepr, Radix},
+};
+
+/// The maximum number of digits of a decimal integer literal parsed into a big
+/// integer, as the conversion takes quadratic time. Longer literals are parsed
+/// into floats, which takes linear time.
+#[cfg(feature = "bigint")]
+const MAX_BIG_DIGITS: usize = 10_000;
+
+/// Parses a Twic number literal (`decimal | hex | special`).
+///
+/// Integer literals outside the range of `[-2^64, 2^64 - 1]` are converted to
+/// big integers with the `bigint` feature, unless they have more than
+/// [`MAX_BIG_DIGITS`] decimal digits, or to the nearest float otherwise.
+/// Literals with a fraction or an exponent are parsed to the nearest float.
+/// Returns `None` if `s` is not a valid number literal.
+pub(crate) fn parse_number(s: &str) -> Option<Number> {
+    let (negative, body) = match s.as_bytes().first() {
+        Some(b'+') => (false, &s[1..]),
+        Some(b'-') => (true, &s[1..]),
+        _ => (false, s),
+    };
+
+    if body == "inf" {
+        return Some(Number::Inf { negative });
+    }
+
+    if body == "nan" {
+        // `nan` does not take a sign
+        return if body.len() == s.len() {
+            Some(Number::NaN)
+        } else {
+            None
+        };
+    }
+
+    if let Some(digits) = body.strip_prefix("0x") {
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        #[cfg(feature = "bigint")]
+        return accumulate(digits, 16)
+            .and_then(|m| integer(negative, m))
+            .or_else(|| BigInt::from_digits(negative, digits, 16).map(Number::from));
+
+        #[cfg(not(feature = "bigint"))]
+        return Some(match accumulate(digits, 16) {
+            Some(magnitude) => {
+                integer(negative, magnitude).unwrap_or_else(|| float(negative, magnitude as f64))
+            }
+            None => float(negative, hex_to_f64(digits)),
+        });
+    }
+
+    let bytes = body.as_bytes();
+    let mut i = skip_digits(bytes, 0)?;
+    let mut is_float = false;
+
+    if bytes.get(i) == Some(&b'.') {
+        i = skip_digits(bytes, i + 1)?;
+        is_float = true;
+    }
+
+    if matches!(bytes.get(i), Some(b'e' | b'E')) {
+        i += 1;
+        if matches!(bytes.get(i), Some(b'+' | b'-')) {
+            i += 1;
+        }
+        i = skip_digits(bytes, i)?;
+        is_float = true;
+    }
+
+    if i != bytes.len() {
+        return None;
+    }
+
+    if !is_float && let Some(number) = accumulate(body, 10).and_then(|m| integer(negative, m)) {
+        return Some(number);
+    }
+
+    #[cfg(feature = "bigint")]
+    if !is_float && body.len() <= MAX_BIG_DIGITS {
+        return BigInt::from_digits(negative, body, 10).map(Number::from);
+    }
+
+    // the grammar checked above is a subset of what `f64::from_str` accepts
+    s.parse::<f64>().ok().map(Number::from)
+}
+
+/// Parses a Twic number literal like [`parse_number`], except that literals
+/// with a fraction or an exponent are parsed as exact decimals with the
+/// `decimal` feature when they fit.
+pub(crate) fn parse_number_exact(s: &str) -> Option<Number> {
+    // decimal literals never contain `x`, and hexadecimal ones always do
+    #[cfg(feature = "decimal")]
+    if s.contains(['.', 'e', 'E'])
+        && !s.contains('x')
+        && let Some(d) = parse_decimal(s)
+    {
+        return Some(Number::Decimal(d));
+    }
+    parse_number(s)
+}
+
+/// Parses a decimal number literal as an exact decimal. Returns `None` if `s`
+/// is not a valid decimal literal, or if its significant digits do not fit in
+/// a `u128` or its exponent is out of the range of `i16`.
+#[cfg(feature = "decimal")]
+pub(crate) fn parse_decimal(s: &str) -> Option<Decimal> {
+    let (negative, body) = match s.as_bytes().first() {
+        Some(b'+') => (false, &s[1..]),
+        Some(b'-') => (true, &s[1..]),
+        _ => (false, s),
+    };
+    if !body.starts_with(|c: char| c.is_ascii_digit()) || body.contains('x') {
+        return None;
+    }
+    // validates the grammar
+    parse_number(s)?;
+    decimal(negative, body)
+}
+
+/// Parses the unsigned body of a decimal literal exactly, returning `None` if
+/// its significant digits do not fit in a `u128` or its exponent is out of the
+/// range of `i16`.
+#[cfg(feature = "decimal")]
+fn decimal(negative: bool, body: &str) -> Option<Decimal> {
+    let (digits, exponent) = match body.find(['e', 'E']) {
+        Some(i) => (&body[..i], exponent(&body[i + 1..])),
+        None => (body, 0),
+    };
+    let (int, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+
+    // trailing zeros are moved to the exponent so they do not overflow
+    let digits = || int.bytes().chain(fraction.bytes());
+    let zeros = digits().rev().take_while(|&b| b == b'0').count();
+    let mantissa = digits()
+        .take(int.len() + fraction.len() - zeros)
+        .try_fold(0u128, |acc, b| {
+            acc.checked_mul(10)?.checked_add((b - b'0') as u128)
+        })?;
+
+    let exponent = exponent
+        .saturating_sub(fraction.len() as i64)
+        .saturating_add(zeros as i64);
+    Decimal::from_parts(negative, mantissa, exponent)
+}
+
+/// Parses an exponent with an optional sign, saturating on overflow.
+#[cfg(feature = "decimal")]
+fn exponent(s: &str) -> i64 {
+    let (negative, digits) = match s.as_bytes().first() {
+        Some(b'+') => (false, &s[1..]),
+        Some(b'-') => (true, &s[1..]),
+        _ => (false, s),
+    };
+    let magnitude = digits.bytes().fold(0i64, |acc, b| {
+        acc.saturating_mul(10).saturating_add((b - b'0') as i64)
+    });
+    if negative { -magnitude } else { magnitude }
+}
+
+/// Gets the spelling of a valid number literal parsed as `n`, or `None` if the
+/// literal is spelled the way `n` is written by default.
+pub(crate) fn number_repr(literal: &str, n: &Number) -> Option<NumberRepr> {
+    let mut default = String::new();
+    // writing to a `String` never fails
+    let _ = write_number(&mut default, n);
+    if literal == default {
+        return None;
+    }
+
+    let mut repr = NumberRepr {
+        plus: literal.starts_with('+'),
+        ..NumberRepr::default()
+    };
+    let body = literal.strip_prefix(['+', '-']).unwrap_or(literal);
+    let leading_zeros = |digits: &str| {
+        if digits.len() > 1 && digits.starts_with('0') {
+            digits.len()
+        } else {
+            0
+        }
+    };
+
+    if let Some(digits) = body.strip_prefix("0x") {
+        repr.radix = Radix::Hexadecimal;
+        repr.min_digits = leading_zeros(digits);
+        repr.uppercase = digits.bytes().any(|b| b.is_ascii_uppercase());
+    } else if let Some(i) = body.find(['e', 'E']) {
+        repr.notation = Notation::Scientific;
+        repr.uppercase = body.as_bytes()[i] == b'E';
+        repr.exponent_plus = body[i + 1..].starts_with('+');
+    } else if n.is_integer() {
+        repr.min_digits = leading_zeros(body);
+    } else if body.bytes().all(|b| b.is_ascii_digit() || b == b'.') {
+        // including integer literals out of range parsed as floats
+        repr.notation = Notation::Fixed;
+    }
+    Some(repr)
+}
+
+/// Skips at least one ASCII digit starting at `start`, returning the index of
+/// the first non-digit byte, or `None` if there is no digit at `start`.
+fn skip_digits(bytes: &[u8], start: usize) -> Option<usize> {
+    let len = bytes[start.min(bytes.len())..]
+        .iter()
+        .take_while(|b| b.is_ascii_digit())
+        .count();
+
+    if len == 0 { None } else { Some(start + len) }
+}
+
+/// Accumulates digits in the given radix, returning `None` on overflow.
+fn accumulate(digits: &str, radix: u32) -> Option<u128> {
+    digits.chars().try_fold(0u128, |acc, c| {
+        acc.checked_mul(radix as u128)?
+            .checked_add(c.to_digit(radix)? as u128)
+    })
+}
+
+/// Creates an integer `Number` from a sign and a magnitude, returning `None` if
+/// the value is out of range.
+fn integer(negative: bool, magnitude: u128) -> Option<Number> {
+    if !negative || magnitude == 0 {
+        u64::try_from(magnitude).ok().map(Number::PosInt)
+    } else if magnitude <= 1u128 << 64 {
+        Some(Number::NegInt((magnitude as u64).wrapping_neg()))
+    } else {
+        None
+    }
+}
+
+/// Creates a float `Number` from a sign and a magnitude.
+#[cfg(not(feature = "bigint"))]
+fn float(negative: bool, magnitude: f64) -> Number {
+    Number::from(if negative { -magnitude } else { magnitude })
+}
+
+/// Converts a string of hexadecimal digits too large for `u128` to the nearest
+/// `f64`.
+#[cfg(not(feature = "bigint"))]
+fn hex_to_f64(digits: &str) -> f64 {
+    let digits = digits.trim_start_matches('0');
+    let (head, tail) = digits.split_at(16);
+
+    // the head has at least 61 significant bits, so folding the remaining
+    // digits into its lowest bit as a sticky bit keeps the rounding correct
+    let mut mantissa = u64::from_str_radix(head, 16).unwrap_or(u64::MAX);
+    if tail.bytes().any(|b| b != b'0') {
+        mantissa |= 1;
+    }
+
+    let mut result = mantissa as f64;
+    for _ in 0..tail.len() {
+        result *= 16.0;
+        if result.is_infinite() {
+            break;
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::{format, string::String, vec::Vec};
+    use core::fmt::Write;
+
+    use super::parse_number;
+    use crate::{ser::write_number, value::Number};
+
+    fn float(s: &str) -> f64 {
+        match parse_number(s) {
+            Some(Number::Float(f)) => f,
+            Some(Number::Inf { negative }) => {
+                if negative {
+                    f64::NEG_INFINITY
+                } else {
+                    f64::INFINITY
+                }
+            }
+            #[cfg(feature = "bigint")]
+            Some(Number::BigInt(n)) => n.to_f64(),
+            #[cfg(feature = "decimal")]
+            Some(Number::Decimal(d)) => d.to_f64(),
+            n => panic!("{} parsed as {:?}", s, n),
+        }
+    }
+
+    #[test]
+    fn test_rounding() {
+        for (s, expected) in [
+            ("0.1", 0.1),
+            ("9007199254740993.0", 9007199254740992.0),
+            ("9007199254740995.0", 9007199254740996.0),
+            // halfway between 1 and the next float, ties to even
+            (
+                "1.00000000000000011102230246251565404236316680908203125",
+                1.0,
+            ),
+            (
+                "1.000000000000000111022302462515654042363166809082031250001",
+                1.0000000000000002,
+            ),
+            ("2.2250738585072011e-308", 2.225073858507201e-308),
+            ("2.2250738585072012e-308", 2.2250738585072014e-308),
+            ("4.9406564584124654e-324", 5e-324),
+            ("2.4703282292062327e-324", 0.0),
+            ("2.4703282292062328e-324", 5e-324),
+            ("1.7976931348623157e308", f64::MAX),
+            ("1.7976931348623158e308", f64::MAX),
+            ("1.7976931348623159e308", f64::INFINITY),
+            ("1e400", f64::INFINITY),
+            ("-1e400", f64::NEG_INFINITY),
+            ("1e-400", 0.0),
+            ("0e999999999999", 0.0),
+            ("18446744073709551616", 18446744073709551616.0),
+            ("-18446744073709551617", -18446744073709551616.0),
+            ("0x1000000000000080000000000000000000", 2f64.powi(132)),
+            (
+                "0x1000000000000080000000000000000001",
+                2f64.powi(132) + 2f64.powi(80),
+            ),
+        ] {
+            assert_eq!(float(s).to_bits(), expected.to_bits(), "{}", s);
+        }
+
+        assert!(float("-1e-400").is_sign_negative());
+
+        let mut long = String::from("0.");
+        long.extend(core::iter::repeat_n('3', 800));
+        assert_eq!(float(&long), 1.0 / 3.0);
+
+        let mut long = String::from("1");
+        long.extend(core::iter::repeat_n('0', 400));
+        long.push_str("e-400");
+        assert_eq!(float(&long), 1.0);
+    }
+
+    /// Returns the decimal digits of `k * base^exp`.
+    fn digits(k: u64, base: u64, exp: u32) -> String {
+        // little-endian limbs of 9 decimal digits
+        let mut limbs = Vec::from([k % 1_000_000_000, k / 1_000_000_000]);
+        for _ in 0..exp {
+            let mut carry = 0;
+            for limb in &mut limbs {
+                let n = *limb * base + carry;
+                *limb = n % 1_000_000_000;
+                carry = n / 1_000_000_000;
+            }
+            if carry > 0 {
+                limbs.push(carry);
+            }
+        }
+        while limbs.len() > 1 && limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+
+        let mut s = String::new();
+        for (i, limb) in limbs.iter().rev().enumerate() {
+            if i == 0 {
+                write!(s, "{}", limb).unwrap();
+            } else {
+                write!(s, "{:09}", limb).unwrap();
+            }
+        }
+        s
+    }
+
+    /// Subtracts one from a string of decimal digits.
+    fn decrement(s: &mut String) {
+        let mut bytes = core::mem::take(s).into_bytes();
+        for b in bytes.iter_mut().rev() {
+            if *b == b'0' {
+                *b = b'9';
+            } else {
+                *b -= 1;
+                break;
+            }
+        }
+        *s = String::from_utf8(bytes).unwrap();
+    }
+
+    #[test]
+    fn test_rounding_boundaries() {
+        let min_subnormal = 5e-324;
+        let max_subnormal = f64::from_bits(0x000f_ffff_ffff_ffff);
+        // (digits, exponent) of halfway points between two consecutive
+        // floats, and the values a little below, at and a little above them
+        let cases = [
+            // `1 + 1.5 * 2^-52`, `k / 2^n` being `k * 5^n / 10^n`
+            (
+                digits(1 << 53 | 3, 5, 53),
+                -53,
+                [
+                    1.0 + f64::EPSILON,
+                    1.0 + 2.0 * f64::EPSILON,
+                    1.0 + 2.0 * f64::EPSILON,
+                ],
+            ),
+            // between `0` and the smallest subnormal
+            (digits(1, 5, 1075), -1075, [0.0, 0.0, min_subnormal]),
+            // between the two smallest subnormals
+            (
+                digits(3, 5, 1075),
+                -1075,
+                [min_subnormal, 2.0 * min_subnormal, 2.0 * min_subnormal],
+            ),
+            // between the largest subnormal and the smallest normal float, with
+            // 768 significant digits
+            (
+                digits((1 << 53) - 1, 5, 1075),
+                -1075,
+                [max_subnormal, f64::MIN_POSITIVE, f64::MIN_POSITIVE],
+            ),
+            // between `f64::MAX` and `2^1024`, rounding to infinity
+            (
+                digits((1 << 54) - 1, 2, 970),
+                0,
+                [f64::MAX, f64::INFINITY, f64::INFINITY],
+            ),
+        ];
+
+        for (halfway, exp, [below, at, above]) in cases {
+            let mut smaller = halfway.clone();
+            decrement(&mut smaller);
+            let inputs = [
+                format!("{}{}e{}", smaller, "9".repeat(100), exp - 100),
+                format!("{}e{}", halfway, exp),
+                format!("{}{}1e{}", halfway, "0".repeat(100), exp - 101),
+            ];
+            for (input, expected) in inputs.iter().zip([below, at, above]) {
+                assert_eq!(float(input).to_bits(), expected.to_bits(), "{}", input);
+                assert_eq!(
+                    float(&format!("-{}", input)).to_bits(),
+                    (-expected).to_bits(),
+                    "-{}",
+                    input
+                );
+            }
+        }
+        assert_eq!(digits((1 << 53) - 1, 5, 1075).len(), 768);
+    }
+
+    #[test]
+    #[cfg(feature = "bigint")]
+    fn test_big_digits() {
+        let digits = "9".repeat(super::MAX_BIG_DIGITS);
+        assert!(matches!(parse_number(&digits), Some(Number::BigInt(_))));
+        // longer literals would take too long to convert exactly
+        assert!(matches!(
+            parse_number(&format!("-{}9", digits)),
+            Some(Number::Inf { negative: true })
+        ));
+        let hex = format!("0x{}", "f".repeat(super::MAX_BIG_DIGITS * 10));
+        assert!(matches!(parse_number(&hex), Some(Number::BigInt(_))));
+    }
+
+    #[test]
+    fn test_shortest() {
+        for (f, expected) in [
+            (0.1 + 0.2, "0.30000000000000004"),
+            (1.0 / 3.0, "0.3333333333333333"),
+            (100.0, "100.0"),
+            (1e21, "1e21"),
+            (123456789012345680.0, "1.2345678901234568e17"),
+            (f64::MIN_POSITIVE, "2.2250738585072014e-308"),
+            (5e-324, "5e-324"),
+            (-f64::MAX, "-1.7976931348623157e308"),
+        ] {
+            let mut s = String::new();
+            write_number(&mut s, &Number::Float(f)).unwrap();
+            assert_eq!(s, expected);
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        // xorshift64, covering all exponents and both signs
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut bits = Vec::new();
+        for _ in 0..100_000 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            bits.push(state);
+        }
+        bits.extend([0, 1, 0x000f_ffff_ffff_ffff, 0x0010_0000_0000_0000]);
+        bits.extend([0x7fef_ffff_ffff_ffff, 0x3ff0_0000_0000_0001]);
+
+        let mut s = String::new();
+        for bits in bits {
+            for bits in [bits, bits ^ (1 << 63)] {
+                let f = f64::from_bits(bits);
+                if !f.is_finite() {
+                    continue;
+                }
+
+                s.clear();
+                write_number(&mut s, &Number::Float(f)).unwrap();
+                assert_eq!(float(&s).to_bits(), bits, "{}", s);
+            }
+        }
+    }
+}
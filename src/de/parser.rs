@@ -0,0 +1,328 @@
+use alloc::{borrow::Cow, vec::Vec};
+use core::ops::Range;
+
+use super::{
+    error::{ExpectedToken, ExpectedTokens, ParseError, ParseErrorKind},
+    lexer::{Lexer, Token, TokenKind},
+};
+use crate::value::Number;
+
+/// A scalar Twic value read by a [`Reader`](super::Reader).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Scalar<'a> {
+    /// `null`.
+    Null,
+    /// `true` or `false`.
+    Boolean(bool),
+    /// A number.
+    Number(Number),
+    /// A string, borrowed from the input unless it is a quoted string with
+    /// escape sequences.
+    String(Cow<'a, str>),
+    /// A quoted string whose bytes, after processing `\xXX` escapes, are not
+    /// valid UTF-8.
+    Bytes(Vec<u8>),
+}
+
+/// A parsing event read by a [`Reader`](super::Reader).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event<'a> {
+    /// The start of a map. Followed by pairs of [`Event::Key`] and the events
+    /// of the value, and terminated by [`Event::End`].
+    StartMap,
+    /// A map key, borrowed from the input unless it is a quoted string with
+    /// escape sequences.
+    Key(Cow<'a, str>),
+    /// The start of a vector. Followed by the events of the elements, and
+    /// terminated by [`Event::End`].
+    StartVector,
+    /// A scalar value.
+    Scalar(Scalar<'a>),
+    /// The end of the innermost map or vector.
+    End,
+}
+
+/// What the parser expects next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// A value.
+    Value,
+    /// A value or the `;` closing an empty vector.
+    VectorHead,
+    /// A map key.
+    Key,
+    /// A `,` or `;` inside a container, or the end of input at top level.
+    AfterValue,
+    /// Nothing, the top-level value and the end of input have been read.
+    Done,
+}
+
+/// The kind of an open container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Container {
+    Vector,
+    Map,
+}
+
+/// A pull parser turning Twic text into a sequence of [`Event`]s.
+pub(crate) struct Parser<'a> {
+    lexer: Lexer<'a>,
+    peeked: Option<Token<'a>>,
+    pending: Option<Event<'a>>,
+    stack: Vec<Container>,
+    state: State,
+    event_span: Range<usize>,
+}
+
+impl<'a> Parser<'a> {
+    /// Creates a parser over the given input.
+    pub fn new(input: &'a str) -> Self {
+        Parser {
+            lexer: Lexer::new(input),
+            peeked: None,
+            pending: None,
+            stack: Vec::new(),
+            state: State::Value,
+            event_span: 0..0,
+        }
+    }
+
+    /// Sets whether number literals with a fraction or an exponent are parsed
+    /// as exact decimals.
+    #[cfg(feature = "decimal")]
+    pub fn set_decimals(&mut self, decimals: bool) {
+        self.lexer.set_decimals(decimals);
+    }
+
+    /// Returns the input being parsed.
+    pub fn input(&self) -> &'a str {
+        self.lexer.input()
+    }
+
+    /// Returns the byte range of the token that produced the last returned
+    /// event.
+    ///
+    /// For [`Event::StartMap`] this is the first key, or the `;` of an empty
+    /// map, and for [`Event::End`] the closing `;`.
+    pub fn event_span(&self) -> Range<usize> {
+        self.event_span.clone()
+    }
+
+    /// Reads the next token.
+    fn next_token(&mut self) -> Result<Option<Token<'a>>, ParseError> {
+        match self.peeked.take() {
+            Some(token) => Ok(Some(token)),
+            None => self.lexer.next_token(),
+        }
+    }
+
+    /// Peeks the next token without consuming it.
+    fn peek_token(&mut self) -> Result<Option<&Token<'a>>, ParseError> {
+        if self.peeked.is_none() {
+            self.peeked = self.lexer.next_token()?;
+        }
+        Ok(self.peeked.as_ref())
+    }
+
+    /// Returns the tokens expected in the current state.
+    fn expected(&self) -> ExpectedTokens {
+        let expected = ExpectedTokens::NONE;
+        match self.state {
+            State::Value => expected.with(ExpectedToken::Value),
+            State::VectorHead => expected
+                .with(ExpectedToken::Value)
+                .with(ExpectedToken::Semicolon),
+            State::Key => expected.with(ExpectedToken::Key),
+            State::AfterValue if self.stack.is_empty() => expected.with(ExpectedToken::Eof),
+            State::AfterValue => expected
+                .with(ExpectedToken::Comma)
+                .with(ExpectedToken::Semicolon),
+            State::Done => expected,
+        }
+    }
+
+    /// Creates an error for an unexpected token, or unexpected end of input if
+    /// `token` is `None`, where the given tokens are expected.
+    fn unexpected(&mut self, token: Option<Token<'a>>, expected: ExpectedTokens) -> ParseError {
+        let error = match token {
+            Some(token) => {
+                let c = self.char_at(token.span.start);
+                ParseError::new(ParseErrorKind::UnexpectedChar(c), token.span.start)
+            }
+            None if expected.contains(ExpectedToken::Semicolon)
+                && !expected.contains(ExpectedToken::Value) =>
+            {
+                ParseError::new(ParseErrorKind::MissingSemicolon, self.lexer.offset())
+            }
+            None => ParseError::new(ParseErrorKind::UnexpectedEof, self.lexer.offset()),
+        };
+        error.expecting(expected)
+    }
+
+    /// Returns the character starting at the given offset of the input.
+    fn char_at(&self, offset: usize) -> char {
+        self.lexer.input()[offset..]
+            .chars()
+            .next()
+            .unwrap_or_default()
+    }
+
+    /// Returns the next event, or `None` once the whole input has been parsed.
+    pub fn next_event(&mut self) -> Result<Option<Event<'a>>, ParseError> {
+        self.read_event().map_err(|e| e.locate(self.input()))
+    }
+
+    /// Reads the next event.
+    fn read_event(&mut self) -> Result<Option<Event<'a>>, ParseError> {
+        if let Some(event) = self.pending.take() {
+            return Ok(Some(event));
+        }
+
+        loop {
+            let event = match self.state {
+                State::Done => return Ok(None),
+                State::Value | State::VectorHead => {
+                    let token = self.next_token()?;
+                    self.value(token)?
+                }
+                State::Key => {
+                    let token = self.next_token()?;
+                    if let Some(Token {
+                        kind: TokenKind::Bytes(_),
+                        span,
+                    }) = token
+                    {
+                        // map keys are always valid UTF-8
+                        return Err(ParseError::new(ParseErrorKind::InvalidUtf8, span.start));
+                    }
+                    let Some(Token {
+                        kind: TokenKind::String(key),
+                        span,
+                    }) = token
+                    else {
+                        return Err(self.unexpected(token, self.expected()));
+                    };
+
+                    let colon = self.next_token()?;
+                    if !matches!(
+                        colon,
+                        Some(Token {
+                            kind: TokenKind::Colon,
+                            ..
+                        })
+                    ) {
+                        let expected = ExpectedTokens::NONE.with(ExpectedToken::Colon);
+                        return Err(self.unexpected(colon, expected));
+                    }
+
+                    self.state = State::Value;
+                    self.event_span = span;
+                    Some(Event::Key(key))
+                }
+                State::AfterValue => {
+                    let token = self.next_token()?;
+                    match (self.stack.last(), token) {
+                        (None, None) => {
+                            self.state = State::Done;
+                            return Ok(None);
+                        }
+                        (
+                            Some(container),
+                            Some(Token {
+                                kind: TokenKind::Comma,
+                                ..
+                            }),
+                        ) => {
+                            self.state = match container {
+                                Container::Vector => State::Value,
+                                Container::Map => State::Key,
+                            };
+                            None
+                        }
+                        (
+                            Some(_),
+                            Some(Token {
+                                kind: TokenKind::Semicolon,
+                                span,
+                            }),
+                        ) => {
+                            self.stack.pop();
+                            self.event_span = span;
+                            Some(Event::End)
+                        }
+                        (_, token) => return Err(self.unexpected(token, self.expected())),
+                    }
+                }
+            };
+
+            if let Some(event) = event {
+                return Ok(Some(event));
+            }
+        }
+    }
+
+    /// Handles the first token of a value.
+    fn value(&mut self, token: Option<Token<'a>>) -> Result<Option<Event<'a>>, ParseError> {
+        let Some(Token { kind, span }) = token else {
+            return Err(self.unexpected(None, self.expected()));
+        };
+
+        self.event_span = span.clone();
+
+        let scalar = match kind {
+            TokenKind::Semicolon if self.state == State::VectorHead => {
+                self.stack.pop();
+                self.state = State::AfterValue;
+                return Ok(Some(Event::End));
+            }
+            TokenKind::Semicolon => {
+                // an empty map
+                self.state = State::AfterValue;
+                self.pending = Some(Event::End);
+                return Ok(Some(Event::StartMap));
+            }
+            TokenKind::Colon => {
+                self.stack.push(Container::Vector);
+                self.state = State::VectorHead;
+                return Ok(Some(Event::StartVector));
+            }
+            TokenKind::Comma => {
+                return Err(self.unexpected(Some(Token { kind, span }), self.expected()));
+            }
+            TokenKind::Null => Scalar::Null,
+            TokenKind::Boolean(b) => Scalar::Boolean(b),
+            TokenKind::Number(n) => Scalar::Number(n),
+            TokenKind::String(s) => {
+                if let Some(Token {
+                    kind: TokenKind::Colon,
+                    ..
+                }) = self.peek_token()?
+                {
+                    // the first key of a map
+                    self.peeked = None;
+                    self.stack.push(Container::Map);
+                    self.state = State::Value;
+                    self.pending = Some(Event::Key(s));
+                    return Ok(Some(Event::StartMap));
+                }
+
+                Scalar::String(s)
+            }
+            TokenKind::Bytes(b) => {
+                if let Some(Token {
+                    kind: TokenKind::Colon,
+                    ..
+                }) = self.peek_token()?
+                {
+                    // map keys are always valid UTF-8
+                    return Err(ParseError::new(ParseErrorKind::InvalidUtf8, span.start));
+                }
+
+                Scalar::Bytes(b)
+            }
+        };
+
+        self.state = State::AfterValue;
+        Ok(Some(Event::Scalar(scalar)))
+    }
+}
@@ -0,0 +1,202 @@
+use core::ops::Range;
+
+use super::{
+    error::{ParseError, ParseErrorKind},
+    parser::{Event, Parser},
+};
+
+/// A pull-based reader yielding the [`Event`]s of Twic text one at a time,
+/// without building a [`Value`](crate::Value).
+///
+/// Unquoted strings, and quoted strings without escape sequences, are borrowed
+/// from the input. The events of a map are [`Event::StartMap`], then an
+/// [`Event::Key`] followed by the events of its value for each entry, and
+/// finally [`Event::End`]. Vectors are read likewise, starting with
+/// [`Event::StartVector`]. Duplicate keys in a map are not detected.
+///
+/// A reader is also an [`Iterator`] over the events, which stops after the
+/// first error.
+///
+/// # Examples
+///
+/// ```
+/// use twic::de::{Event, Reader, Scalar};
+///
+/// let mut reader = Reader::new("users::alice,bob;;");
+/// assert_eq!(reader.next_event().unwrap(), Some(Event::StartMap));
+/// assert_eq!(reader.next_event().unwrap(), Some(Event::Key("users".into())));
+/// assert_eq!(reader.next_event().unwrap(), Some(Event::StartVector));
+/// assert_eq!(
+///     reader.next_event().unwrap(),
+///     Some(Event::Scalar(Scalar::String("alice".into())))
+/// );
+/// assert_eq!(reader.span(), 7..12);
+///
+/// let rest: Result<Vec<_>, _> = reader.collect();
+/// assert_eq!(rest.unwrap().len(), 3);
+/// ```
+pub struct Reader<'a> {
+    parser: Parser<'a>,
+    failed: bool,
+}
+
+impl<'a> Reader<'a> {
+    /// Creates a reader over the given Twic text.
+    pub fn new(input: &'a str) -> Self {
+        Reader {
+            parser: Parser::new(input),
+            failed: false,
+        }
+    }
+
+    /// Creates a reader over the given bytes, which must be valid UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ParseErrorKind::InvalidUtf8`] located at the
+    /// first invalid byte if the input is not valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::de::{Event, ParseErrorKind, Reader};
+    ///
+    /// let mut reader = Reader::from_slice(b":;").unwrap();
+    /// assert_eq!(reader.next_event().unwrap(), Some(Event::StartVector));
+    ///
+    /// let err = Reader::from_slice(b"ok:\xff;").err().unwrap();
+    /// assert_eq!(err.kind(), &ParseErrorKind::InvalidUtf8);
+    /// assert_eq!(err.offset(), 3);
+    /// ```
+    pub fn from_slice(input: &'a [u8]) -> Result<Self, ParseError> {
+        match core::str::from_utf8(input) {
+            Ok(s) => Ok(Reader::new(s)),
+            Err(e) => {
+                let valid = &input[..e.valid_up_to()];
+                // the prefix is valid UTF-8 by definition
+                let valid = core::str::from_utf8(valid).unwrap_or_default();
+                Err(ParseError::new(ParseErrorKind::InvalidUtf8, valid.len()).locate(valid))
+            }
+        }
+    }
+
+    /// Sets whether number literals with a fraction or an exponent are read
+    /// as exact [`Decimal`](crate::value::Decimal)s when they fit, instead of
+    /// the nearest floats. Disabled by default.
+    ///
+    /// Available with the `decimal` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::de::{Event, Reader, Scalar};
+    /// use twic::value::{Decimal, Number};
+    ///
+    /// let mut reader = Reader::new("0.10").decimals(true);
+    /// assert_eq!(
+    ///     reader.next_event().unwrap(),
+    ///     Some(Event::Scalar(Scalar::Number(Number::Decimal(Decimal::new(1, -1).unwrap()))))
+    /// );
+    /// ```
+    #[cfg(feature = "decimal")]
+    pub fn decimals(mut self, decimals: bool) -> Self {
+        self.parser.set_decimals(decimals);
+        self
+    }
+
+    /// Returns the input being read.
+    pub fn input(&self) -> &'a str {
+        self.parser.input()
+    }
+
+    /// Reads the next event, or returns `None` once the whole input has been
+    /// read.
+    ///
+    /// After an error is returned, the reader must not be used anymore, as
+    /// the events it returns are unspecified.
+    pub fn next_event(&mut self) -> Result<Option<Event<'a>>, ParseError> {
+        self.parser.next_event()
+    }
+
+    /// Returns the byte range of the token that produced the last returned
+    /// event.
+    ///
+    /// This is the literal of a scalar or a key, the `:` of
+    /// [`Event::StartVector`] and the `;` of [`Event::End`]. For
+    /// [`Event::StartMap`], it is the first key, or the `;` of an empty map.
+    pub fn span(&self) -> Range<usize> {
+        self.parser.event_span()
+    }
+}
+
+impl<'a> Iterator for Reader<'a> {
+    type Item = Result<Event<'a>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        let result = self.next_event();
+        self.failed = result.is_err();
+        result.transpose()
+    }
+}
+
+impl core::iter::FusedIterator for Reader<'_> {}
+
+#[cfg(test)]
+mod test {
+    use alloc::{borrow::Cow, vec::Vec};
+
+    use super::Reader;
+    use crate::{
+        de::{Event, ParseErrorKind, Scalar},
+        value::Number,
+    };
+
+    #[test]
+    fn test_events() {
+        let events: Vec<_> = Reader::new(r#"a: :1, "x\ty", ;;, "b": null;"#)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            events,
+            [
+                Event::StartMap,
+                Event::Key("a".into()),
+                Event::StartVector,
+                Event::Scalar(Scalar::Number(Number::PosInt(1))),
+                Event::Scalar(Scalar::String("x\ty".into())),
+                Event::StartMap,
+                Event::End,
+                Event::End,
+                Event::Key("b".into()),
+                Event::Scalar(Scalar::Null),
+                Event::End,
+            ]
+        );
+
+        let borrowed = Reader::new(r#":plain, "quoted", "esc\n";"#)
+            .filter_map(|e| match e.unwrap() {
+                Event::Scalar(Scalar::String(s)) => Some(matches!(s, Cow::Borrowed(_))),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(borrowed, [true, true, false]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut reader = Reader::new(":1 2;");
+        assert!(reader.next().unwrap().is_ok());
+        assert!(reader.next().unwrap().is_ok());
+        let e = reader.next().unwrap().unwrap_err();
+        assert_eq!(e.kind(), &ParseErrorKind::UnexpectedChar('2'));
+        assert!(reader.next().is_none());
+
+        let e = Reader::from_slice(b"a:1,\nb:\xc3;").err().unwrap();
+        assert_eq!(e.kind(), &ParseErrorKind::InvalidUtf8);
+        assert_eq!((e.offset(), e.line(), e.column()), (7, 2, 3));
+    }
+}
@@ -0,0 +1,264 @@
+use alloc::{borrow::Cow, collections::BTreeMap, string::String, vec::Vec};
+use core::ops::Range;
+
+use super::{
+    error::ParseError,
+    parser::Scalar,
+    reader::Reader,
+    tree::{Tree, build},
+};
+use crate::value::{Map, Number, Value};
+
+/// A value together with its byte range in the parsed input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned<T> {
+    /// The value.
+    pub value: T,
+    /// The byte range of the value in the input.
+    pub span: Range<usize>,
+}
+
+impl<T> Spanned<T> {
+    /// Creates a spanned value.
+    pub fn new(value: T, span: Range<usize>) -> Self {
+        Spanned { value, span }
+    }
+
+    /// Returns the value, discarding its span.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+/// A map parsed by [`parse_spanned`], mapping string keys to entries which
+/// carry the spans of both the key and the value.
+pub type SpannedMap = BTreeMap<String, SpannedEntry>;
+
+/// An entry of a [`SpannedMap`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpannedEntry {
+    /// The byte range of the key in the input, including the quotes of a
+    /// quoted key.
+    pub key_span: Range<usize>,
+    /// The value with its span.
+    pub value: Spanned<SpannedValue>,
+}
+
+/// A Twic value whose nested values are annotated with their source locations,
+/// as returned by [`parse_spanned`].
+///
+/// It mirrors [`Value`], and can be converted into one with
+/// [`SpannedValue::into_value`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpannedValue {
+    /// Represents a Twic null value.
+    Null,
+    /// Represents a Twic boolean value.
+    Boolean(bool),
+    /// Represents a Twic number value.
+    Number(Number),
+    /// Represents a Twic string value.
+    String(String),
+    /// Represents a Twic string value that is not valid UTF-8.
+    Bytes(Vec<u8>),
+    /// Represents a Twic vector value.
+    Vector(Vec<Spanned<SpannedValue>>),
+    /// Represents a Twic map value.
+    Map(SpannedMap),
+}
+
+impl SpannedValue {
+    /// Returns the element at the given index if the value is a vector, `None`
+    /// otherwise or if the index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let v = twic::de::parse_spanned(":a, b;").unwrap();
+    /// assert_eq!(v.value.get_index(1).unwrap().span, 4..5);
+    /// assert!(v.value.get_index(2).is_none());
+    /// ```
+    pub fn get_index(&self, index: usize) -> Option<&Spanned<SpannedValue>> {
+        match self {
+            SpannedValue::Vector(v) => v.get(index),
+            _ => None,
+        }
+    }
+
+    /// Returns the entry of the given key if the value is a map, `None`
+    /// otherwise or if the key does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let v = twic::de::parse_spanned("port: 70000;").unwrap();
+    /// let entry = v.value.get_entry("port").unwrap();
+    /// assert_eq!(entry.key_span, 0..4);
+    /// assert_eq!(entry.value.span, 6..11);
+    /// ```
+    pub fn get_entry(&self, key: &str) -> Option<&SpannedEntry> {
+        match self {
+            SpannedValue::Map(m) => m.get(key),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of the given key if the value is a map, `None`
+    /// otherwise or if the key does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let v = twic::de::parse_spanned("port: 70000;").unwrap();
+    /// assert_eq!(v.value.get("port").unwrap().span, 6..11);
+    /// assert!(v.value.get("host").is_none());
+    /// ```
+    pub fn get(&self, key: &str) -> Option<&Spanned<SpannedValue>> {
+        self.get_entry(key).map(|entry| &entry.value)
+    }
+
+    /// Converts the value into a [`Value`], discarding all spans.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::Value;
+    ///
+    /// let v = twic::de::parse_spanned("users::alice,bob;;").unwrap();
+    /// assert_eq!(
+    ///     v.value.into_value(),
+    ///     Value::map_from([("users", Value::vector_from(["alice", "bob"]))])
+    /// );
+    /// ```
+    pub fn into_value(self) -> Value {
+        match self {
+            SpannedValue::Null => Value::Null,
+            SpannedValue::Boolean(b) => Value::Boolean(b),
+            SpannedValue::Number(n) => Value::Number(n),
+            SpannedValue::String(s) => Value::String(s),
+            SpannedValue::Bytes(b) => Value::Bytes(b),
+            SpannedValue::Vector(v) => {
+                Value::Vector(v.into_iter().map(|e| e.value.into_value()).collect())
+            }
+            SpannedValue::Map(m) => Value::Map(
+                m.into_iter()
+                    .map(|(k, entry)| (k, entry.value.value.into_value()))
+                    .collect::<Map>(),
+            ),
+        }
+    }
+}
+
+impl From<SpannedValue> for Value {
+    fn from(value: SpannedValue) -> Self {
+        value.into_value()
+    }
+}
+
+impl<'a> Tree<'a> for Spanned<SpannedValue> {
+    type Map = SpannedMap;
+
+    fn scalar(scalar: Scalar<'a>, span: Range<usize>) -> Self {
+        let value = match scalar {
+            Scalar::Null => SpannedValue::Null,
+            Scalar::Boolean(b) => SpannedValue::Boolean(b),
+            Scalar::Number(n) => SpannedValue::Number(n),
+            Scalar::String(s) => SpannedValue::String(s.into_owned()),
+            Scalar::Bytes(b) => SpannedValue::Bytes(b),
+        };
+        Spanned::new(value, span)
+    }
+
+    fn vector(elements: Vec<Self>, span: Range<usize>) -> Self {
+        Spanned::new(SpannedValue::Vector(elements), span)
+    }
+
+    fn map(map: SpannedMap, span: Range<usize>) -> Self {
+        Spanned::new(SpannedValue::Map(map), span)
+    }
+
+    fn contains_key(map: &SpannedMap, key: &str) -> bool {
+        map.contains_key(key)
+    }
+
+    fn insert(map: &mut SpannedMap, key: Cow<'a, str>, key_span: Range<usize>, value: Self) {
+        map.insert(key.into_owned(), SpannedEntry { key_span, value });
+    }
+}
+
+/// Parses Twic text into a [`SpannedValue`], recording the byte range of every
+/// value and map key in the input.
+///
+/// The span of a scalar covers its literal, including the quotes of a quoted
+/// string. The span of a vector runs from its opening `:` to its closing `;`,
+/// and the span of a map from its first key (or the `;` of an empty map) to its
+/// closing `;`. Whitespaces around the top-level value are not included.
+///
+/// Otherwise, it behaves exactly like [`from_str`](crate::from_str) parsing
+/// into a [`Value`].
+///
+/// # Examples
+///
+/// ```
+/// let input = "server: host: example.com, port: 70000;;";
+/// let v = twic::de::parse_spanned(input).unwrap();
+///
+/// let port = v.value.get("server").unwrap().value.get("port").unwrap();
+/// assert_eq!(port.value.clone().into_value(), 70000);
+/// assert_eq!(&input[port.span.clone()], "70000");
+/// ```
+pub fn parse_spanned(s: &str) -> Result<Spanned<SpannedValue>, ParseError> {
+    build(Reader::new(s))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SpannedValue, parse_spanned};
+    use crate::de::ParseErrorKind;
+    use crate::value::Value;
+
+    #[test]
+    fn test_spans() {
+        let input = r#" a: 1, "b c": :x, ;;, d: e: null;; "#;
+        let v = parse_spanned(input).unwrap();
+        assert_eq!(v.span, 1..34);
+        assert_eq!(
+            &input[v.span.clone()],
+            r#"a: 1, "b c": :x, ;;, d: e: null;;"#
+        );
+
+        let a = v.value.get_entry("a").unwrap();
+        assert_eq!((a.key_span.clone(), a.value.span.clone()), (1..2, 4..5));
+
+        let bc = v.value.get_entry("b c").unwrap();
+        assert_eq!(&input[bc.key_span.clone()], r#""b c""#);
+        assert_eq!(&input[bc.value.span.clone()], ":x, ;;");
+        assert_eq!(bc.value.value.get_index(0).unwrap().span, 15..16);
+        assert_eq!(
+            &input[bc.value.value.get_index(1).unwrap().span.clone()],
+            ";"
+        );
+
+        let d = v.value.get("d").unwrap();
+        assert_eq!(&input[d.span.clone()], "e: null;");
+        assert_eq!(d.value.get("e").unwrap().value, SpannedValue::Null);
+
+        assert_eq!(v.value.into_value(), crate::de::parse_value(input).unwrap());
+        assert_eq!(parse_spanned(" 42 ").unwrap().span, 1..3);
+        assert_eq!(
+            parse_spanned(":;").unwrap().value.into_value(),
+            Value::vector_empty()
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let e = parse_spanned("a:1,\n  a:2;").unwrap_err();
+        assert_eq!(e.kind(), &ParseErrorKind::DuplicateKey("a".into()));
+        assert_eq!((e.offset(), e.line(), e.column()), (7, 2, 3));
+        assert_eq!(
+            parse_spanned("a:1").unwrap_err().kind(),
+            &ParseErrorKind::MissingSemicolon
+        );
+    }
+}
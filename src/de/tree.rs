@@ -0,0 +1,85 @@
+use alloc::{borrow::Cow, vec::Vec};
+use core::ops::Range;
+
+use super::{
+    error::{ParseError, ParseErrorKind},
+    parser::{Event, Scalar},
+    reader::Reader,
+};
+
+/// A tree of values that [`build`] can fill from the events of a [`Reader`].
+pub(crate) trait Tree<'a>: Sized {
+    /// The map type of the tree.
+    type Map: Default;
+
+    /// Creates a scalar value from its event and the span of its literal.
+    fn scalar(scalar: Scalar<'a>, span: Range<usize>) -> Self;
+
+    /// Creates a vector value from its elements and its span.
+    fn vector(elements: Vec<Self>, span: Range<usize>) -> Self;
+
+    /// Creates a map value from its entries and its span.
+    fn map(map: Self::Map, span: Range<usize>) -> Self;
+
+    /// Checks if the map has an entry for the key.
+    fn contains_key(map: &Self::Map, key: &str) -> bool;
+
+    /// Inserts an entry into the map, whose key is not in the map yet.
+    fn insert(map: &mut Self::Map, key: Cow<'a, str>, key_span: Range<usize>, value: Self);
+}
+
+/// A container being built by [`build`], with the offset it starts at.
+enum Partial<'a, T: Tree<'a>> {
+    Vector(Vec<T>, usize),
+    /// A map and the key whose value is being parsed.
+    Map(T::Map, usize, Option<(Cow<'a, str>, Range<usize>)>),
+}
+
+/// Reads a whole value from a reader into a tree, rejecting duplicate keys.
+pub(crate) fn build<'a, T: Tree<'a>>(mut reader: Reader<'a>) -> Result<T, ParseError> {
+    let mut stack: Vec<Partial<'a, T>> = Vec::new();
+    let mut root = None;
+
+    while let Some(event) = reader.next_event()? {
+        let span = reader.span();
+        let value = match event {
+            Event::StartMap => {
+                stack.push(Partial::Map(T::Map::default(), span.start, None));
+                continue;
+            }
+            Event::StartVector => {
+                stack.push(Partial::Vector(Vec::new(), span.start));
+                continue;
+            }
+            Event::Key(key) => {
+                if let Some(Partial::Map(_, _, pending)) = stack.last_mut() {
+                    *pending = Some((key, span));
+                }
+                continue;
+            }
+            Event::Scalar(scalar) => T::scalar(scalar, span),
+            Event::End => match stack.pop() {
+                Some(Partial::Vector(v, start)) => T::vector(v, start..span.end),
+                Some(Partial::Map(m, start, _)) => T::map(m, start..span.end),
+                None => unreachable!("the parser never emits unbalanced `End` events"),
+            },
+        };
+
+        match stack.last_mut() {
+            None => root = Some(value),
+            Some(Partial::Vector(v, _)) => v.push(value),
+            Some(Partial::Map(m, _, pending)) => {
+                let (key, key_span) = pending
+                    .take()
+                    .expect("the parser always emits a key before a map value");
+                if T::contains_key(m, &key) {
+                    let kind = ParseErrorKind::DuplicateKey(key.into_owned());
+                    return Err(ParseError::new(kind, key_span.start).locate(reader.input()));
+                }
+                T::insert(m, key, key_span, value);
+            }
+        }
+    }
+
+    Ok(root.expect("the parser emits a complete value before the end of input"))
+}
@@ -0,0 +1,903 @@
+//! Format-preserving editing of Twic text.
+//!
+//! A [`Document`] is a lossless concrete syntax tree of Twic text. It keeps
+//! every whitespace and the original spelling of every literal, so that
+//! unmodified parts of the text are written back byte for byte.
+//!
+//! # Examples
+//!
+//! ```
+//! use twic::edit::Document;
+//!
+//! let mut doc: Document = "name: twic,  version: 0x01 ;".parse().unwrap();
+//! doc.set("version", 2).unwrap();
+//! doc.insert("users", twic::Value::vector_from(["alice"])).unwrap();
+//! assert_eq!(doc.to_string().unwrap(), "name: twic,  version: 2,  users: :alice; ;");
+//! ```
+
+use alloc::{string::String, vec::Vec};
+use core::{
+    fmt::Write,
+    mem,
+    ops::{Deref, DerefMut},
+    str::FromStr,
+};
+
+use crate::{
+    de::{ParseError, number_repr},
+    ser::{SerializeError, SerializeErrorKind, write_bytes, write_number, write_str},
+    value::{self, NumberRepr, Value, ValueIndexError},
+};
+
+mod index;
+mod parse;
+
+#[doc(inline)]
+pub use index::ItemIndex;
+
+/// A Twic document, the top-level [`Item`] together with the whitespaces
+/// around it.
+///
+/// A document dereferences to its top-level item, so the item methods can be
+/// called on it directly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Document {
+    prefix: String,
+    root: Item,
+    suffix: String,
+}
+
+/// A node of a [`Document`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Item {
+    /// A null, boolean, number or string.
+    Scalar(Scalar),
+    /// A vector.
+    Vector(Vector),
+    /// A map.
+    Map(Map),
+}
+
+/// A scalar [`Item`] together with its spelling in the text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scalar {
+    value: Value,
+    repr: String,
+}
+
+/// A map key together with its spelling in the text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Key {
+    name: String,
+    repr: String,
+}
+
+/// A vector [`Item`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Vector {
+    entries: Vec<VectorEntry>,
+    /// The whitespaces before the closing `;`.
+    close: String,
+}
+
+/// An element of a [`Vector`].
+#[derive(Debug, Clone, PartialEq)]
+struct VectorEntry {
+    /// The whitespaces after the preceding `:` or `,`.
+    prefix: String,
+    item: Item,
+    /// The whitespaces before the following `,`, always empty for the last
+    /// element.
+    suffix: String,
+}
+
+/// A map [`Item`]. Entries are kept in the order they appear in the text.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Map {
+    entries: Vec<MapEntry>,
+    /// The whitespaces before the closing `;`.
+    close: String,
+}
+
+/// An entry of a [`Map`].
+#[derive(Debug, Clone, PartialEq)]
+struct MapEntry {
+    /// The whitespaces after the preceding `,`, always empty for the first
+    /// entry, as whitespaces before a map belong to its parent.
+    key_prefix: String,
+    key: Key,
+    /// The whitespaces before the `:`.
+    key_suffix: String,
+    /// The whitespaces after the `:`.
+    value_prefix: String,
+    item: Item,
+    /// The whitespaces before the following `,`, always empty for the last
+    /// entry.
+    value_suffix: String,
+}
+
+impl Document {
+    /// Parses Twic text into a document.
+    ///
+    /// The text is validated exactly like [`from_str`](crate::from_str) does,
+    /// including the rejection of duplicate keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::edit::Document;
+    ///
+    /// let text = " a : 0xFF ,b:\"x\" ;\n";
+    /// let doc = Document::parse(text).unwrap();
+    /// assert_eq!(doc["a"].to_value(), 255);
+    /// assert_eq!(doc.to_string().unwrap(), text);
+    /// ```
+    pub fn parse(s: &str) -> Result<Document, ParseError> {
+        parse::parse_document(s)
+    }
+
+    /// Returns the top-level item.
+    pub fn root(&self) -> &Item {
+        &self.root
+    }
+
+    /// Returns a mutable reference to the top-level item.
+    pub fn root_mut(&mut self) -> &mut Item {
+        &mut self.root
+    }
+
+    /// Converts the document into its top-level item, discarding the
+    /// whitespaces around it.
+    pub fn into_root(self) -> Item {
+        self.root
+    }
+}
+
+impl Deref for Document {
+    type Target = Item;
+
+    fn deref(&self) -> &Item {
+        &self.root
+    }
+}
+
+impl DerefMut for Document {
+    fn deref_mut(&mut self) -> &mut Item {
+        &mut self.root
+    }
+}
+
+impl FromStr for Document {
+    type Err = ParseError;
+
+    /// Parses Twic text into a document. See [`Document::parse`] for details.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Document::parse(s)
+    }
+}
+
+impl From<Item> for Document {
+    fn from(root: Item) -> Self {
+        Document {
+            prefix: String::new(),
+            root,
+            suffix: String::new(),
+        }
+    }
+}
+
+impl From<Value> for Document {
+    fn from(value: Value) -> Self {
+        Item::from(value).into()
+    }
+}
+
+/// Predicates and accessors for [`Item`].
+impl Item {
+    /// Checks if the item is a scalar.
+    pub fn is_scalar(&self) -> bool {
+        matches!(self, Item::Scalar(_))
+    }
+
+    /// Returns the scalar if the item is a scalar, `None` otherwise.
+    pub fn as_scalar(&self) -> Option<&Scalar> {
+        if let Item::Scalar(s) = self {
+            Some(s)
+        } else {
+            None
+        }
+    }
+
+    /// Checks if the item is a vector.
+    pub fn is_vector(&self) -> bool {
+        matches!(self, Item::Vector(_))
+    }
+
+    /// Returns the vector if the item is a vector, `None` otherwise.
+    pub fn as_vector(&self) -> Option<&Vector> {
+        if let Item::Vector(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
+
+    /// Checks if the item is a map.
+    pub fn is_map(&self) -> bool {
+        matches!(self, Item::Map(_))
+    }
+
+    /// Returns the map if the item is a map, `None` otherwise.
+    pub fn as_map(&self) -> Option<&Map> {
+        if let Item::Map(m) = self {
+            Some(m)
+        } else {
+            None
+        }
+    }
+
+    /// Converts the item into a [`Value`], discarding all formatting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::{Value, edit::Document};
+    ///
+    /// let doc: Document = "users: :alice, bob;;".parse().unwrap();
+    /// assert_eq!(doc["users"].to_value(), Value::vector_from(["alice", "bob"]));
+    /// ```
+    pub fn to_value(&self) -> Value {
+        match self {
+            Item::Scalar(s) => s.value.clone(),
+            Item::Vector(v) => Value::Vector(v.iter().map(Item::to_value).collect()),
+            Item::Map(m) => Value::Map(
+                m.iter()
+                    .map(|(k, v)| (k.into(), v.to_value()))
+                    .collect::<value::Map>(),
+            ),
+        }
+    }
+}
+
+/// Indexing and editing support for [`Item`].
+impl Item {
+    /// Indexes into the item using the provided index. Returns `Some(&Item)` if
+    /// the item is indexable and the index exists, `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::edit::Document;
+    ///
+    /// let doc: Document = "users: :alice, bob;;".parse().unwrap();
+    /// assert_eq!(doc.get("users").and_then(|u| u.get(1)).unwrap().to_value(), "bob");
+    /// assert!(doc.get("groups").is_none());
+    /// ```
+    pub fn get<I: ItemIndex>(&self, index: I) -> Option<&Item> {
+        index.index_into(self).ok()
+    }
+
+    /// Indexes into the item using the provided index mutably. Returns
+    /// `Some(&mut Item)` if the item is indexable and the index exists, `None`
+    /// otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::edit::Document;
+    ///
+    /// let mut doc: Document = "server: port: 80 ;;".parse().unwrap();
+    /// doc.get_mut("server").unwrap().set("port", 8080).unwrap();
+    /// assert_eq!(doc.to_string().unwrap(), "server: port: 8080 ;;");
+    /// ```
+    pub fn get_mut<I: ItemIndex>(&mut self, index: I) -> Option<&mut Item> {
+        index.index_into_mut(self).ok()
+    }
+
+    /// Sets the value at the given index, keeping the whitespaces around it.
+    /// Returns the replaced item, if any.
+    ///
+    /// A missing key is appended to a map, and an index equal to the length of
+    /// a vector appends to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the item is not indexable, the index type is
+    /// incompatible, or the index is past the end of a vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::edit::Document;
+    ///
+    /// let mut doc: Document = ":1 , 0x2 ;".parse().unwrap();
+    /// let old = doc.set(0, "one").unwrap().unwrap();
+    /// assert_eq!(old.to_value(), 1);
+    /// doc.set(2, 3).unwrap();
+    /// assert!(doc.set(4, 5).is_err());
+    /// assert_eq!(doc.to_string().unwrap(), ":one , 0x2 , 3 ;");
+    /// ```
+    pub fn set<I: ItemIndex, V: Into<Value>>(
+        &mut self,
+        index: I,
+        value: V,
+    ) -> Result<Option<Item>, ValueIndexError> {
+        let item = Item::from(value.into());
+        match index.index_into_mut(self) {
+            Ok(slot) => Ok(Some(mem::replace(slot, item))),
+            Err(ValueIndexError::KeyNotFound) => index.insert_into(self, item),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Inserts a value at the given index. Returns the replaced item, if any.
+    ///
+    /// Inserting into a map behaves like [`Item::set`]. Inserting into a vector
+    /// shifts the elements after the index to the right, like
+    /// [`Vec::insert`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the item is not indexable, the index type is
+    /// incompatible, or the index is past the end of a vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::edit::Document;
+    ///
+    /// let mut doc: Document = ":a, c;".parse().unwrap();
+    /// doc.insert(1, "b").unwrap();
+    /// assert_eq!(doc.to_string().unwrap(), ":a, b, c;");
+    /// ```
+    pub fn insert<I: ItemIndex, V: Into<Value>>(
+        &mut self,
+        index: I,
+        value: V,
+    ) -> Result<Option<Item>, ValueIndexError> {
+        index.insert_into(self, Item::from(value.into()))
+    }
+
+    /// Removes and returns the item at the given index, together with the
+    /// whitespaces and separator around it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the item is not indexable, the index type is
+    /// incompatible, or the index does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::edit::Document;
+    ///
+    /// let mut doc: Document = "a: 1, b: 2, c: 3;".parse().unwrap();
+    /// assert_eq!(doc.remove("b").unwrap().to_value(), 2);
+    /// assert!(doc.remove("b").is_err());
+    /// assert_eq!(doc.to_string().unwrap(), "a: 1, c: 3;");
+    /// ```
+    pub fn remove<I: ItemIndex>(&mut self, index: I) -> Result<Item, ValueIndexError> {
+        index.remove_from(self)
+    }
+}
+
+impl From<Value> for Item {
+    /// Creates an item from a value, formatted as compact Twic text.
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Vector(v) => Item::Vector(Vector {
+                entries: v
+                    .into_iter()
+                    .map(|item| VectorEntry {
+                        prefix: String::new(),
+                        item: item.into(),
+                        suffix: String::new(),
+                    })
+                    .collect(),
+                close: String::new(),
+            }),
+            Value::Map(m) => Item::Map(Map {
+                entries: m
+                    .into_iter()
+                    .map(|(key, item)| MapEntry {
+                        key_prefix: String::new(),
+                        key: Key::new(key),
+                        key_suffix: String::new(),
+                        value_prefix: String::new(),
+                        item: item.into(),
+                        value_suffix: String::new(),
+                    })
+                    .collect(),
+                close: String::new(),
+            }),
+            scalar => Item::Scalar(Scalar::new(scalar)),
+        }
+    }
+}
+
+impl Scalar {
+    /// Creates a scalar with its compact spelling. `value` must not be a vector
+    /// or a map.
+    fn new(value: Value) -> Self {
+        let mut repr = String::new();
+        // writing to a `String` never fails
+        let _ = match &value {
+            Value::Null => repr.write_str("null"),
+            Value::Boolean(b) => repr.write_str(if *b { "true" } else { "false" }),
+            Value::Number(n) => write_number(&mut repr, n),
+            Value::String(s) => write_str(&mut repr, s),
+            Value::Bytes(b) => write_bytes(&mut repr, b),
+            Value::Vector(_) | Value::Map(_) => unreachable!("scalars are never containers"),
+        };
+        Scalar { value, repr }
+    }
+
+    /// Returns the value of the scalar.
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+
+    /// Returns the scalar as written in the text, for example `0xFF` or
+    /// `"quoted"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::edit::Document;
+    ///
+    /// let doc: Document = "mask: 0xFF;".parse().unwrap();
+    /// let mask = doc["mask"].as_scalar().unwrap();
+    /// assert_eq!(*mask.value(), 255);
+    /// assert_eq!(mask.repr(), "0xFF");
+    /// ```
+    pub fn repr(&self) -> &str {
+        &self.repr
+    }
+
+    /// Returns the spelling of the scalar if it is a number written with a
+    /// spelling other than the default one, `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::{edit::Document, value::Radix};
+    ///
+    /// let doc: Document = "mask: 0xFF, count: 3;".parse().unwrap();
+    /// let repr = doc["mask"].as_scalar().unwrap().number_repr().unwrap();
+    /// assert_eq!((repr.radix, repr.uppercase), (Radix::Hexadecimal, true));
+    /// assert_eq!(doc["count"].as_scalar().unwrap().number_repr(), None);
+    /// ```
+    pub fn number_repr(&self) -> Option<NumberRepr> {
+        match &self.value {
+            Value::Number(n) => number_repr(&self.repr, n),
+            _ => None,
+        }
+    }
+}
+
+impl Key {
+    /// Creates a key with its compact spelling.
+    fn new(name: String) -> Self {
+        let mut repr = String::new();
+        // writing to a `String` never fails
+        let _ = write_str(&mut repr, &name);
+        Key { name, repr }
+    }
+
+    /// Returns the key.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the key as written in the text.
+    pub fn repr(&self) -> &str {
+        &self.repr
+    }
+}
+
+impl Vector {
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = &Item> {
+        self.entries.iter().map(|e| &e.item)
+    }
+
+    /// Returns the element at the given index.
+    fn get(&self, index: usize) -> Option<&Item> {
+        self.entries.get(index).map(|e| &e.item)
+    }
+
+    /// Returns a mutable reference to the element at the given index.
+    fn get_mut(&mut self, index: usize) -> Option<&mut Item> {
+        self.entries.get_mut(index).map(|e| &mut e.item)
+    }
+
+    /// Inserts an element at the given index, which must not be greater than
+    /// the length, copying the whitespaces around its neighbors.
+    fn insert(&mut self, index: usize, item: Item) {
+        let len = self.entries.len();
+        let prefix = match self.entries.get(index).or(self.entries.last()) {
+            Some(e) => e.prefix.clone(),
+            None => String::new(),
+        };
+        let suffix = if len >= 2 {
+            self.entries[len - 2].suffix.clone()
+        } else {
+            String::new()
+        };
+
+        if index == len {
+            if let Some(last) = self.entries.last_mut() {
+                last.suffix = suffix;
+            }
+            self.entries.push(VectorEntry {
+                prefix,
+                item,
+                suffix: String::new(),
+            });
+        } else {
+            self.entries.insert(
+                index,
+                VectorEntry {
+                    prefix,
+                    item,
+                    suffix,
+                },
+            );
+        }
+    }
+
+    /// Removes the element at the given index, which must exist.
+    fn remove(&mut self, index: usize) -> Item {
+        let removed = self.entries.remove(index);
+        if index == 0
+            && let Some(first) = self.entries.first_mut()
+        {
+            first.prefix = removed.prefix;
+        }
+        if index == self.entries.len()
+            && let Some(last) = self.entries.last_mut()
+        {
+            last.suffix.clear();
+        }
+        removed.item
+    }
+}
+
+impl Map {
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks if the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns an iterator over the entries, in the order they appear in the
+    /// text.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Item)> {
+        self.entries.iter().map(|e| (e.key.name(), &e.item))
+    }
+
+    /// Returns an iterator over the keys with their spelling, in the order they
+    /// appear in the text.
+    pub fn keys(&self) -> impl Iterator<Item = &Key> {
+        self.entries.iter().map(|e| &e.key)
+    }
+
+    /// Returns the position of the given key.
+    fn position(&self, key: &str) -> Option<usize> {
+        self.entries.iter().position(|e| e.key.name == key)
+    }
+
+    /// Returns the value of the given key.
+    fn get(&self, key: &str) -> Option<&Item> {
+        self.position(key).map(|i| &self.entries[i].item)
+    }
+
+    /// Returns a mutable reference to the value of the given key.
+    fn get_mut(&mut self, key: &str) -> Option<&mut Item> {
+        self.position(key).map(|i| &mut self.entries[i].item)
+    }
+
+    /// Sets the value of the given key, appending a new entry after the last
+    /// one and copying its whitespaces if the key is missing.
+    fn insert(&mut self, key: &str, item: Item) -> Option<Item> {
+        if let Some(slot) = self.get_mut(key) {
+            return Some(mem::replace(slot, item));
+        }
+
+        let len = self.entries.len();
+        let entry = match self.entries.last() {
+            Some(last) => MapEntry {
+                key_prefix: match &self.entries[..] {
+                    [_, .., last] => last.key_prefix.clone(),
+                    [first] if !first.value_prefix.is_empty() => " ".into(),
+                    _ => String::new(),
+                },
+                key: Key::new(key.into()),
+                key_suffix: last.key_suffix.clone(),
+                value_prefix: last.value_prefix.clone(),
+                item,
+                value_suffix: String::new(),
+            },
+            None => MapEntry {
+                key_prefix: String::new(),
+                key: Key::new(key.into()),
+                key_suffix: String::new(),
+                value_prefix: String::new(),
+                item,
+                value_suffix: String::new(),
+            },
+        };
+
+        if len >= 1 {
+            self.entries[len - 1].value_suffix = if len >= 2 {
+                self.entries[len - 2].value_suffix.clone()
+            } else {
+                String::new()
+            };
+        }
+        self.entries.push(entry);
+        None
+    }
+
+    /// Removes the entry of the given key.
+    fn remove(&mut self, key: &str) -> Option<Item> {
+        let index = self.position(key)?;
+        let removed = self.entries.remove(index);
+        if index == 0
+            && let Some(first) = self.entries.first_mut()
+        {
+            first.key_prefix = removed.key_prefix;
+        }
+        if index == self.entries.len()
+            && let Some(last) = self.entries.last_mut()
+        {
+            last.value_suffix.clear();
+        }
+        Some(removed.item)
+    }
+}
+
+impl<I: ItemIndex> core::ops::Index<I> for Item {
+    type Output = Item;
+
+    /// Indexes into the item using the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the item is not indexable, the index type is incompatible, or
+    /// the index does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::edit::Document;
+    ///
+    /// let doc: Document = "users: :alice, bob;;".parse().unwrap();
+    /// assert_eq!(doc["users"][0].to_value(), "alice");
+    /// ```
+    fn index(&self, index: I) -> &Item {
+        match index.index_into(self) {
+            Ok(item) => item,
+            Err(ValueIndexError::NotIndexable) => panic!("twic item is not indexable"),
+            Err(ValueIndexError::IncompatibleIndexType) => {
+                panic!("incompatible index type for twic item")
+            }
+            Err(ValueIndexError::KeyNotFound) => panic!("index not found in twic item"),
+        }
+    }
+}
+
+/// Writing [`Document`]s back as text.
+impl Document {
+    /// Writes the document back as Twic text. Unmodified parts are written
+    /// exactly as they were parsed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`SerializeErrorKind::EmptyMapAtVectorHead`]
+    /// if a vector has an empty map as its first element, which has no Twic
+    /// representation. Edits can lead there, for example by removing the last
+    /// key of a map that is the first element of a vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::{edit::Document, ser::SerializeErrorKind};
+    ///
+    /// let mut doc: Document = ":a: 1;, 2;".parse().unwrap();
+    /// assert_eq!(doc.to_string().unwrap(), ":a: 1;, 2;");
+    /// doc.get_mut(0).unwrap().remove("a").unwrap();
+    /// let err = doc.to_string().unwrap_err();
+    /// assert_eq!(err.kind(), &SerializeErrorKind::EmptyMapAtVectorHead);
+    /// ```
+    pub fn to_string(&self) -> Result<String, SerializeError> {
+        let mut s = String::new();
+        self.to_writer(&mut s)?;
+        Ok(s)
+    }
+
+    /// Writes the document back as Twic text to the given writer. See
+    /// [`Document::to_string`] for details.
+    pub fn to_writer<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), SerializeError> {
+        writer.write_str(&self.prefix)?;
+        self.root.to_writer(writer)?;
+        writer.write_str(&self.suffix)?;
+        Ok(())
+    }
+}
+
+/// Writing [`Item`]s back as text.
+impl Item {
+    /// Writes the item as Twic text. See [`Document::to_string`] for details.
+    pub fn to_string(&self) -> Result<String, SerializeError> {
+        let mut s = String::new();
+        self.to_writer(&mut s)?;
+        Ok(s)
+    }
+
+    /// Writes the item as Twic text to the given writer. See
+    /// [`Document::to_string`] for details.
+    pub fn to_writer<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), SerializeError> {
+        match self {
+            Item::Scalar(s) => writer.write_str(&s.repr)?,
+            Item::Vector(v) => {
+                if v.get(0).and_then(Item::as_map).is_some_and(Map::is_empty) {
+                    return Err(SerializeError::new(
+                        SerializeErrorKind::EmptyMapAtVectorHead,
+                    ));
+                }
+
+                writer.write_char(':')?;
+                for (i, e) in v.entries.iter().enumerate() {
+                    if i > 0 {
+                        writer.write_char(',')?;
+                    }
+                    writer.write_str(&e.prefix)?;
+                    e.item.to_writer(writer)?;
+                    writer.write_str(&e.suffix)?;
+                }
+                writer.write_str(&v.close)?;
+                writer.write_char(';')?;
+            }
+            Item::Map(m) => {
+                for (i, e) in m.entries.iter().enumerate() {
+                    if i > 0 {
+                        writer.write_char(',')?;
+                    }
+                    writer.write_str(&e.key_prefix)?;
+                    writer.write_str(&e.key.repr)?;
+                    writer.write_str(&e.key_suffix)?;
+                    writer.write_char(':')?;
+                    writer.write_str(&e.value_prefix)?;
+                    e.item.to_writer(writer)?;
+                    writer.write_str(&e.value_suffix)?;
+                }
+                writer.write_str(&m.close)?;
+                writer.write_char(';')?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Document;
+    use crate::{
+        ser::SerializeErrorKind,
+        value::{Value, ValueIndexError},
+    };
+
+    fn round_trip(s: &str) -> Document {
+        let doc = Document::parse(s).unwrap();
+        assert_eq!(doc.to_string().unwrap(), s);
+        assert_eq!(doc.to_value(), crate::de::parse_value(s).unwrap());
+        doc
+    }
+
+    #[test]
+    fn test_lossless() {
+        for s in [
+            "null",
+            " \t0x0fF\n",
+            r#""a\u0041" "#,
+            ";",
+            " ; ",
+            ":;",
+            ": \n ;",
+            ":1 ,2, \"3\" ;",
+            "a:1;",
+            "  a  :  +1.50e0  ,\n  b:c:d;  ,\"e f\":  :  ;\n;\n",
+            ":a:1;, :x; , ; ;",
+        ] {
+            round_trip(s);
+        }
+
+        assert!(Document::parse("a:1,a:2;").is_err());
+        assert!(Document::parse(":1,;").is_err());
+    }
+
+    #[test]
+    fn test_map_edits() {
+        let mut doc = round_trip("name: twic,\n  version: 0x01\n;");
+        assert_eq!(doc.set("version", 2).unwrap().unwrap().to_value(), 1);
+        assert_eq!(doc.to_string().unwrap(), "name: twic,\n  version: 2\n;");
+
+        doc.insert("user list", Value::vector_from(["a"])).unwrap();
+        assert_eq!(
+            doc.to_string().unwrap(),
+            "name: twic,\n  version: 2,\n  \"user list\": :a;\n;"
+        );
+
+        doc.remove("name").unwrap();
+        assert_eq!(
+            doc.to_string().unwrap(),
+            "version: 2,\n  \"user list\": :a;\n;"
+        );
+        doc.remove("user list").unwrap();
+        assert_eq!(doc.to_string().unwrap(), "version: 2\n;");
+        doc.remove("version").unwrap();
+        assert_eq!(doc.to_string().unwrap(), "\n;");
+        doc.set("a", 1).unwrap();
+        assert_eq!(doc.to_string().unwrap(), "a:1\n;");
+
+        let mut doc = round_trip("a: 1;");
+        doc.set("b", 2).unwrap();
+        assert_eq!(doc.to_string().unwrap(), "a: 1, b: 2;");
+        assert_eq!(doc["b"].as_scalar().unwrap().repr(), "2");
+
+        let mut doc = round_trip(":x:1;;");
+        doc.get_mut(0).unwrap().set("y", true).unwrap();
+        assert_eq!(doc.to_string().unwrap(), ":x:1,y:true;;");
+    }
+
+    #[test]
+    fn test_vector_edits() {
+        let mut doc = round_trip(": 1 , 2 ;");
+        doc.insert(0, 0).unwrap();
+        assert_eq!(doc.to_string().unwrap(), ": 0 , 1 , 2 ;");
+        doc.insert(3, 3).unwrap();
+        assert_eq!(doc.to_string().unwrap(), ": 0 , 1 , 2 , 3 ;");
+        doc.remove(0).unwrap();
+        doc.remove(2).unwrap();
+        assert_eq!(doc.to_string().unwrap(), ": 1 , 2 ;");
+        doc.remove(1).unwrap();
+        doc.remove(0).unwrap();
+        assert_eq!(doc.to_string().unwrap(), ": ;");
+        doc.set(0, "x").unwrap();
+        assert_eq!(doc.to_string().unwrap(), ":x ;");
+
+        assert_eq!(doc.set(2, 1), Err(ValueIndexError::KeyNotFound));
+        assert_eq!(doc.set("a", 1), Err(ValueIndexError::IncompatibleIndexType));
+        assert_eq!(
+            doc.get_mut(0).unwrap().remove(0),
+            Err(ValueIndexError::NotIndexable)
+        );
+    }
+
+    #[test]
+    fn test_unrepresentable() {
+        let mut doc = round_trip(":a:1;, 2;");
+        doc.get_mut(0).unwrap().remove("a").unwrap();
+        let err = doc.to_string().unwrap_err();
+        assert_eq!(err.kind(), &SerializeErrorKind::EmptyMapAtVectorHead);
+        assert!(doc[0].to_string().is_ok());
+
+        doc.remove(0).unwrap();
+        assert_eq!(doc.to_string().unwrap(), ":2;");
+    }
+}
@@ -0,0 +1,132 @@
+use alloc::string::String;
+
+use super::Item;
+use crate::value::ValueIndexError;
+
+/// Trait for types that can index into an [`Item`], mirroring
+/// [`IndexInto`](crate::value::IndexInto) for [`Value`](crate::Value)s.
+pub trait ItemIndex {
+    /// Indexes into the given item, returning a reference to the indexed item
+    /// or an error if the index is invalid or the item is not indexable.
+    fn index_into<'a>(&self, item: &'a Item) -> Result<&'a Item, ValueIndexError>;
+    /// Indexes into the given mutable item, returning a mutable reference to
+    /// the indexed item or an error if the index is invalid or the item is not
+    /// indexable.
+    fn index_into_mut<'a>(&self, item: &'a mut Item) -> Result<&'a mut Item, ValueIndexError>;
+    /// Inserts `value` into the given item at the index, returning the
+    /// replaced item if any, or an error if the index is invalid or the item is
+    /// not indexable.
+    fn insert_into(&self, item: &mut Item, value: Item) -> Result<Option<Item>, ValueIndexError>;
+    /// Removes the indexed item from the given item, returning it or an error
+    /// if the index is invalid or the item is not indexable.
+    fn remove_from(&self, item: &mut Item) -> Result<Item, ValueIndexError>;
+}
+
+impl ItemIndex for usize {
+    fn index_into<'a>(&self, item: &'a Item) -> Result<&'a Item, ValueIndexError> {
+        match item {
+            Item::Vector(v) => v.get(*self).ok_or(ValueIndexError::KeyNotFound),
+            Item::Map(_) => Err(ValueIndexError::IncompatibleIndexType),
+            Item::Scalar(_) => Err(ValueIndexError::NotIndexable),
+        }
+    }
+
+    fn index_into_mut<'a>(&self, item: &'a mut Item) -> Result<&'a mut Item, ValueIndexError> {
+        match item {
+            Item::Vector(v) => v.get_mut(*self).ok_or(ValueIndexError::KeyNotFound),
+            Item::Map(_) => Err(ValueIndexError::IncompatibleIndexType),
+            Item::Scalar(_) => Err(ValueIndexError::NotIndexable),
+        }
+    }
+
+    fn insert_into(&self, item: &mut Item, value: Item) -> Result<Option<Item>, ValueIndexError> {
+        match item {
+            Item::Vector(v) if *self > v.len() => Err(ValueIndexError::KeyNotFound),
+            Item::Vector(v) => {
+                v.insert(*self, value);
+                Ok(None)
+            }
+            Item::Map(_) => Err(ValueIndexError::IncompatibleIndexType),
+            Item::Scalar(_) => Err(ValueIndexError::NotIndexable),
+        }
+    }
+
+    fn remove_from(&self, item: &mut Item) -> Result<Item, ValueIndexError> {
+        match item {
+            Item::Vector(v) if *self >= v.len() => Err(ValueIndexError::KeyNotFound),
+            Item::Vector(v) => Ok(v.remove(*self)),
+            Item::Map(_) => Err(ValueIndexError::IncompatibleIndexType),
+            Item::Scalar(_) => Err(ValueIndexError::NotIndexable),
+        }
+    }
+}
+
+impl ItemIndex for str {
+    fn index_into<'a>(&self, item: &'a Item) -> Result<&'a Item, ValueIndexError> {
+        match item {
+            Item::Map(m) => m.get(self).ok_or(ValueIndexError::KeyNotFound),
+            Item::Vector(_) => Err(ValueIndexError::IncompatibleIndexType),
+            Item::Scalar(_) => Err(ValueIndexError::NotIndexable),
+        }
+    }
+
+    fn index_into_mut<'a>(&self, item: &'a mut Item) -> Result<&'a mut Item, ValueIndexError> {
+        match item {
+            Item::Map(m) => m.get_mut(self).ok_or(ValueIndexError::KeyNotFound),
+            Item::Vector(_) => Err(ValueIndexError::IncompatibleIndexType),
+            Item::Scalar(_) => Err(ValueIndexError::NotIndexable),
+        }
+    }
+
+    fn insert_into(&self, item: &mut Item, value: Item) -> Result<Option<Item>, ValueIndexError> {
+        match item {
+            Item::Map(m) => Ok(m.insert(self, value)),
+            Item::Vector(_) => Err(ValueIndexError::IncompatibleIndexType),
+            Item::Scalar(_) => Err(ValueIndexError::NotIndexable),
+        }
+    }
+
+    fn remove_from(&self, item: &mut Item) -> Result<Item, ValueIndexError> {
+        match item {
+            Item::Map(m) => m.remove(self).ok_or(ValueIndexError::KeyNotFound),
+            Item::Vector(_) => Err(ValueIndexError::IncompatibleIndexType),
+            Item::Scalar(_) => Err(ValueIndexError::NotIndexable),
+        }
+    }
+}
+
+impl ItemIndex for String {
+    fn index_into<'a>(&self, item: &'a Item) -> Result<&'a Item, ValueIndexError> {
+        self.as_str().index_into(item)
+    }
+
+    fn index_into_mut<'a>(&self, item: &'a mut Item) -> Result<&'a mut Item, ValueIndexError> {
+        self.as_str().index_into_mut(item)
+    }
+
+    fn insert_into(&self, item: &mut Item, value: Item) -> Result<Option<Item>, ValueIndexError> {
+        self.as_str().insert_into(item, value)
+    }
+
+    fn remove_from(&self, item: &mut Item) -> Result<Item, ValueIndexError> {
+        self.as_str().remove_from(item)
+    }
+}
+
+impl<T: ItemIndex + ?Sized> ItemIndex for &T {
+    fn index_into<'a>(&self, item: &'a Item) -> Result<&'a Item, ValueIndexError> {
+        (**self).index_into(item)
+    }
+
+    fn index_into_mut<'a>(&self, item: &'a mut Item) -> Result<&'a mut Item, ValueIndexError> {
+        (**self).index_into_mut(item)
+    }
+
+    fn insert_into(&self, item: &mut Item, value: Item) -> Result<Option<Item>, ValueIndexError> {
+        (**self).insert_into(item, value)
+    }
+
+    fn remove_from(&self, item: &mut Item) -> Result<Item, ValueIndexError> {
+        (**self).remove_from(item)
+    }
+}
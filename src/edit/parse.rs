@@ -0,0 +1,172 @@
+use alloc::{string::String, vec::Vec};
+use core::ops::Range;
+
+use super::{Document, Item, Key, Map, MapEntry, Scalar, Vector, VectorEntry};
+use crate::{
+    de::{self, Event, ParseError, ParseErrorKind, Parser},
+    value::Value,
+};
+
+/// Builds a [`Document`] from the events of a [`Parser`], recovering the
+/// whitespaces between tokens from the input.
+struct Builder<'a> {
+    input: &'a str,
+    events: Vec<(Event<'a>, Range<usize>)>,
+    pos: usize,
+}
+
+/// Parses Twic text into a [`Document`].
+pub(super) fn parse_document(s: &str) -> Result<Document, ParseError> {
+    let mut parser = Parser::new(s);
+    let mut events = Vec::new();
+    while let Some(event) = parser.next_event()? {
+        events.push((event, parser.event_span()));
+    }
+
+    let mut builder = Builder {
+        input: s,
+        events,
+        pos: 0,
+    };
+    let start = builder.peek_span().start;
+    let (root, end) = builder.item()?;
+
+    Ok(Document {
+        prefix: s[..start].into(),
+        root,
+        suffix: s[end..].into(),
+    })
+}
+
+/// Splits whitespaces around a structural character `c`.
+fn split_at(gap: &str, c: char) -> (String, String) {
+    let (before, after) = gap
+        .split_once(c)
+        .expect("the parser only skips whitespaces and structural characters");
+    (before.into(), after.into())
+}
+
+impl<'a> Builder<'a> {
+    /// Returns the span of the next event.
+    fn peek_span(&self) -> Range<usize> {
+        self.events[self.pos].1.clone()
+    }
+
+    /// Checks if the next event is [`Event::End`].
+    fn peek_end(&self) -> bool {
+        matches!(self.events[self.pos].0, Event::End)
+    }
+
+    /// Returns the next event.
+    fn next(&mut self) -> (Event<'a>, Range<usize>) {
+        self.pos += 1;
+        self.events[self.pos - 1].clone()
+    }
+
+    /// Builds the next item, returning it with the offset it ends at.
+    fn item(&mut self) -> Result<(Item, usize), ParseError> {
+        let (event, span) = self.next();
+        match event {
+            Event::Scalar(scalar) => {
+                let value = match scalar {
+                    de::Scalar::Null => Value::Null,
+                    de::Scalar::Boolean(b) => Value::Boolean(b),
+                    de::Scalar::Number(n) => Value::Number(n),
+                    de::Scalar::String(s) => Value::String(s.into_owned()),
+                    de::Scalar::Bytes(b) => Value::Bytes(b),
+                };
+                let repr = self.input[span.clone()].into();
+                Ok((Item::Scalar(Scalar { value, repr }), span.end))
+            }
+            Event::StartVector => self.vector(span.end),
+            Event::StartMap => self.map(),
+            Event::Key(_) | Event::End => {
+                unreachable!("the parser emits keys and ends only inside containers")
+            }
+        }
+    }
+
+    /// Builds a vector whose `:` ends at `end`.
+    fn vector(&mut self, mut end: usize) -> Result<(Item, usize), ParseError> {
+        let mut vector = Vector::default();
+
+        loop {
+            let span = self.peek_span();
+            let gap = &self.input[end..span.start];
+
+            if self.peek_end() {
+                self.next();
+                vector.close = gap.into();
+                return Ok((Item::Vector(vector), span.end));
+            }
+
+            let prefix = match vector.entries.last_mut() {
+                Some(last) => {
+                    let (suffix, prefix) = split_at(gap, ',');
+                    last.suffix = suffix;
+                    prefix
+                }
+                None => gap.into(),
+            };
+
+            let (item, item_end) = self.item()?;
+            vector.entries.push(VectorEntry {
+                prefix,
+                item,
+                suffix: String::new(),
+            });
+            end = item_end;
+        }
+    }
+
+    /// Builds a map whose [`Event::StartMap`] has been read.
+    fn map(&mut self) -> Result<(Item, usize), ParseError> {
+        let mut map = Map::default();
+        let mut end = None;
+
+        loop {
+            let (event, span) = self.next();
+            let gap = end.map(|end| &self.input[end..span.start]);
+
+            let key = match event {
+                Event::End => {
+                    map.close = gap.unwrap_or_default().into();
+                    return Ok((Item::Map(map), span.end));
+                }
+                Event::Key(key) => key.into_owned(),
+                _ => unreachable!("the parser emits a key or an end inside a map"),
+            };
+
+            let key_prefix = match (map.entries.last_mut(), gap) {
+                (Some(last), Some(gap)) => {
+                    let (suffix, prefix) = split_at(gap, ',');
+                    last.value_suffix = suffix;
+                    prefix
+                }
+                _ => String::new(),
+            };
+
+            if map.entries.iter().any(|e| e.key.name == key) {
+                return Err(
+                    ParseError::new(ParseErrorKind::DuplicateKey(key), span.start)
+                        .locate(self.input),
+                );
+            }
+
+            let value_start = self.peek_span().start;
+            let (key_suffix, value_prefix) = split_at(&self.input[span.end..value_start], ':');
+            let repr = self.input[span].into();
+            let (item, item_end) = self.item()?;
+
+            map.entries.push(MapEntry {
+                key_prefix,
+                key: Key { name: key, repr },
+                key_suffix,
+                value_prefix,
+                item,
+                value_suffix: String::new(),
+            });
+            end = Some(item_end);
+        }
+    }
+}
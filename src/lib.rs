@@ -3,7 +3,20 @@
 
 extern crate alloc;
 
+pub mod borrowed;
+pub mod de;
+pub mod edit;
+pub mod ser;
+#[cfg(feature = "serde")]
+pub mod serde;
 pub mod value;
 
 #[doc(inline)]
-pub use value::{Map, Number, Value};
+pub use de::{ParseError, from_str};
+#[doc(inline)]
+pub use ser::{SerializeError, to_string};
+#[doc(inline)]
+pub use value::{Map, Number, Path, Value};
+#[cfg(feature = "serde")]
+#[doc(inline)]
+pub use value::{from_value, to_value};
@@ -0,0 +1,570 @@
+//! Serializing [`Value`]s into Twic text.
+
+use alloc::{format, string::String, vec::Vec};
+use core::{
+    fmt::{self, Write},
+    iter,
+};
+
+use crate::{
+    de::{is_keyword, is_structural},
+    value::{Notation, Number, NumberRepr, Radix, Value},
+};
+use spelling::{Spellings, Step};
+
+mod compact;
+mod error;
+mod pretty;
+#[cfg(feature = "serde")]
+pub(crate) mod serializer;
+mod spelling;
+mod writer;
+
+#[doc(inline)]
+pub use compact::CompactFormatter;
+#[doc(inline)]
+pub use error::{SerializeError, SerializeErrorKind};
+#[doc(inline)]
+pub use pretty::PrettyFormatter;
+#[cfg(feature = "serde")]
+#[doc(inline)]
+pub use serializer::{Compound, Serializer};
+#[doc(inline)]
+pub use writer::Writer;
+
+/// Serializes a [`Value`] into compact Twic text.
+///
+/// Strings are quoted only when the unquoted form would be ambiguous, and
+/// numbers are written in a form that parses back to the identical [`Number`]
+/// variant. No whitespace is written between tokens. Use a
+/// [`CompactFormatter`] to write numbers with other spellings.
+///
+/// # Errors
+///
+/// Returns an error if the value contains a vector whose first element is an
+/// empty map, which has no Twic representation. Such values can be built, so
+/// [`Value`] does not implement [`Display`](fmt::Display), and this function
+/// and [`to_writer`] are the way to write one as text.
+///
+/// # Examples
+///
+/// ```
+/// use twic::Value;
+///
+/// let v = Value::map_from([
+///     ("users", Value::vector_from(["alice", "bob smith"])),
+///     ("port", Value::number(8080)),
+///     ("ratio", Value::number(1.0)),
+///     ("note", Value::string("true")),
+/// ]);
+/// assert_eq!(
+///     twic::to_string(&v).unwrap(),
+///     r#"note:"true",port:8080,ratio:1.0,users::alice,"bob smith";;"#,
+/// );
+/// ```
+pub fn to_string(value: &Value) -> Result<String, SerializeError> {
+    let mut s = String::new();
+    write_value(&mut s, value)?;
+    Ok(s)
+}
+
+/// Serializes a [`Value`] into compact Twic text, writing it to the given
+/// writer. See [`to_string`] for details.
+///
+/// # Examples
+///
+/// ```
+/// use twic::Value;
+///
+/// let mut s = String::from("config=");
+/// twic::ser::to_writer(&mut s, &Value::vector_from([1, 2])).unwrap();
+/// assert_eq!(s, "config=:1,2;");
+/// ```
+pub fn to_writer<W: Write + ?Sized>(writer: &mut W, value: &Value) -> Result<(), SerializeError> {
+    write_value(writer, value)
+}
+
+/// Writes a [`Value`] as compact Twic text.
+pub(crate) fn write_value<W: Write + ?Sized>(
+    writer: &mut W,
+    value: &Value,
+) -> Result<(), SerializeError> {
+    write_compact(writer, value, &Spellings::default(), &mut Vec::new())
+}
+
+/// Writes a [`Value`] as compact Twic text with the given spellings of
+/// numbers, where `path` holds the map keys and vector indices leading to the
+/// value.
+fn write_compact<'v, W: Write + ?Sized>(
+    writer: &mut W,
+    value: &'v Value,
+    numbers: &Spellings,
+    path: &mut Vec<Step<'v>>,
+) -> Result<(), SerializeError> {
+    match value {
+        Value::Null => writer.write_str("null")?,
+        Value::Boolean(b) => writer.write_str(if *b { "true" } else { "false" })?,
+        Value::Number(n) => write_number_repr(writer, n, numbers.get(path, n))?,
+        Value::String(s) => write_str(writer, s)?,
+        Value::Bytes(b) => write_bytes(writer, b)?,
+        Value::Vector(v) => {
+            if v.first().is_some_and(is_empty_map) {
+                return Err(SerializeError::new(
+                    SerializeErrorKind::EmptyMapAtVectorHead,
+                ));
+            }
+
+            writer.write_char(':')?;
+            for (i, item) in v.iter().enumerate() {
+                if i > 0 {
+                    writer.write_char(',')?;
+                }
+                path.push(Step::Index(i));
+                write_compact(writer, item, numbers, path)?;
+                path.pop();
+            }
+            writer.write_char(';')?;
+        }
+        Value::Map(m) => {
+            for (i, (key, value)) in m.iter().enumerate() {
+                if i > 0 {
+                    writer.write_char(',')?;
+                }
+                write_str(writer, key)?;
+                writer.write_char(':')?;
+                path.push(Step::Key(key));
+                write_compact(writer, value, numbers, path)?;
+                path.pop();
+            }
+            writer.write_char(';')?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Serializes a [`Value`] into human-readable Twic text, using a
+/// [`PrettyFormatter`] with the default settings.
+///
+/// # Examples
+///
+/// ```
+/// use twic::Value;
+///
+/// let v = Value::map_from([("name", "twic"), ("version", "0.1")]);
+/// assert_eq!(twic::ser::to_string_pretty(&v).unwrap(), "name: twic, version: \"0.1\";");
+/// ```
+pub fn to_string_pretty(value: &Value) -> Result<String, SerializeError> {
+    PrettyFormatter::new().to_string(value)
+}
+
+/// Checks if the value is an empty map.
+pub(crate) fn is_empty_map(value: &Value) -> bool {
+    value.as_map().is_some_and(|m| m.is_empty())
+}
+
+/// Checks if the string must be quoted to be read back as the same string.
+pub(crate) fn needs_quotes(s: &str) -> bool {
+    s.is_empty()
+        || s.starts_with(|c: char| c == '"' || c == '+' || c == '-' || c.is_ascii_digit())
+        || s.contains(|c: char| c.is_whitespace() || is_structural(c))
+        || is_keyword(s)
+}
+
+/// Writes a string, quoting it only if necessary.
+pub(crate) fn write_str<W: Write + ?Sized>(writer: &mut W, s: &str) -> fmt::Result {
+    if needs_quotes(s) {
+        write_quoted(writer, s)
+    } else {
+        writer.write_str(s)
+    }
+}
+
+/// Writes a string as a quoted string, escaping `"`, `\` and ASCII control
+/// characters.
+pub(crate) fn write_quoted<W: Write + ?Sized>(writer: &mut W, s: &str) -> fmt::Result {
+    writer.write_char('"')?;
+    write_escaped(writer, s)?;
+    writer.write_char('"')
+}
+
+/// Writes bytes as a quoted string, escaping them like [`write_quoted`] does,
+/// and writing bytes that are not part of valid UTF-8 as `\xXX` escapes.
+pub(crate) fn write_bytes<W: Write + ?Sized>(writer: &mut W, b: &[u8]) -> fmt::Result {
+    writer.write_char('"')?;
+    for chunk in b.utf8_chunks() {
+        write_escaped(writer, chunk.valid())?;
+        for b in chunk.invalid() {
+            write!(writer, "\\x{:02x}", b)?;
+        }
+    }
+    writer.write_char('"')
+}
+
+/// Writes the content of a quoted string, escaping `"`, `\` and ASCII control
+/// characters.
+fn write_escaped<W: Write + ?Sized>(writer: &mut W, s: &str) -> fmt::Result {
+    let mut chunk = 0;
+    for (i, b) in s.bytes().enumerate() {
+        let escaped = match b {
+            b'"' => "\\\"",
+            b'\\' => "\\\\",
+            b'\n' => "\\n",
+            b'\r' => "\\r",
+            b'\t' => "\\t",
+            b'\x08' => "\\b",
+            b'\x0c' => "\\f",
+            0x00..0x20 | 0x7f => "",
+            _ => continue,
+        };
+
+        writer.write_str(&s[chunk..i])?;
+        if escaped.is_empty() {
+            write!(writer, "\\x{:02x}", b)?;
+        } else {
+            writer.write_str(escaped)?;
+        }
+        chunk = i + 1;
+    }
+
+    writer.write_str(&s[chunk..])
+}
+
+/// Writes a number in a form that parses back to the identical variant, given
+/// that decimals are parsed back only when reading them is enabled.
+pub(crate) fn write_number<W: Write + ?Sized>(writer: &mut W, n: &Number) -> fmt::Result {
+    match n {
+        Number::PosInt(n) => write!(writer, "{}", n),
+        Number::NegInt(0) => writer.write_str("-18446744073709551616"),
+        Number::NegInt(n) => write!(writer, "-{}", n.wrapping_neg()),
+        // `Debug` prints the shortest representation that round-trips, and
+        // always includes a `.` or an exponent, so it reads back as a float
+        Number::Float(n) => write!(writer, "{:?}", n),
+        Number::NaN => writer.write_str("nan"),
+        Number::Inf { negative: false } => writer.write_str("inf"),
+        Number::Inf { negative: true } => writer.write_str("-inf"),
+        #[cfg(feature = "bigint")]
+        Number::BigInt(n) => write!(writer, "{}", n),
+        #[cfg(feature = "decimal")]
+        Number::Decimal(d) => write!(writer, "{}", d),
+    }
+}
+
+/// Writes a number with the given spelling, or as by [`write_number`] if there
+/// is none.
+pub(crate) fn write_number_repr<W: Write + ?Sized>(
+    writer: &mut W,
+    n: &Number,
+    repr: Option<&NumberRepr>,
+) -> fmt::Result {
+    let Some(repr) = repr else {
+        return write_number(writer, n);
+    };
+
+    let (negative, magnitude) = match n {
+        Number::PosInt(n) => (false, integer_magnitude(*n as u128, repr)),
+        Number::NegInt(n) => (true, integer_magnitude((1 << 64) - *n as u128, repr)),
+        Number::Float(f) => {
+            let magnitude = match repr.notation {
+                Notation::Auto => format!("{:?}", f.abs()),
+                Notation::Fixed => {
+                    let mut s = format!("{}", f.abs());
+                    if !s.contains('.') {
+                        s.push_str(".0");
+                    }
+                    s
+                }
+                Notation::Scientific => format!("{:e}", f.abs()),
+            };
+            (f.is_sign_negative(), spell_exponent(magnitude, repr))
+        }
+        // `nan` does not take a sign
+        Number::NaN => return writer.write_str("nan"),
+        Number::Inf { negative } => (*negative, String::from("inf")),
+        #[cfg(feature = "bigint")]
+        Number::BigInt(n) => (n.is_negative(), integer_magnitude(n, repr)),
+        #[cfg(feature = "decimal")]
+        Number::Decimal(d) => {
+            let scientific = match repr.notation {
+                Notation::Auto => d.is_scientific(),
+                Notation::Fixed => false,
+                Notation::Scientific => true,
+            };
+            let mut magnitude = String::new();
+            d.write_magnitude(&mut magnitude, scientific)?;
+            (d.is_sign_negative(), spell_exponent(magnitude, repr))
+        }
+    };
+
+    if negative {
+        writer.write_char('-')?;
+    } else if repr.plus {
+        writer.write_char('+')?;
+    }
+    writer.write_str(&magnitude)
+}
+
+/// Formats the magnitude of an integer in the radix, case and minimum number
+/// of digits of `repr`.
+fn integer_magnitude<T>(n: T, repr: &NumberRepr) -> String
+where
+    T: fmt::Display + fmt::LowerHex + fmt::UpperHex,
+{
+    let (prefix, digits) = match (repr.radix, repr.uppercase) {
+        (Radix::Decimal, _) => ("", format!("{}", n)),
+        (Radix::Hexadecimal, false) => ("0x", format!("{:x}", n)),
+        (Radix::Hexadecimal, true) => ("0x", format!("{:X}", n)),
+    };
+    // big integers are formatted with their sign
+    let digits = digits.trim_start_matches('-');
+
+    let mut s = String::from(prefix);
+    s.extend(iter::repeat_n(
+        '0',
+        repr.min_digits.saturating_sub(digits.len()),
+    ));
+    s.push_str(digits);
+    s
+}
+
+/// Applies the case and explicit `+` of the exponent of `repr` to the
+/// magnitude of a float literal.
+fn spell_exponent(mut magnitude: String, repr: &NumberRepr) -> String {
+    if let Some(i) = magnitude.find('e') {
+        if repr.exponent_plus && !magnitude[i + 1..].starts_with('-') {
+            magnitude.insert(i + 1, '+');
+        }
+        if repr.uppercase {
+            magnitude.replace_range(i..=i, "E");
+        }
+    }
+    magnitude
+}
+
+impl fmt::Display for Number {
+    /// Formats the number as a Twic number literal.
+    ///
+    /// Integers are written exactly, and floats in the shortest form that
+    /// parses back to the same value, always with a `.` or an exponent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::Number;
+    ///
+    /// assert_eq!(Number::NegInt(0).to_string(), "-18446744073709551616");
+    /// assert_eq!(Number::Float(2.0).to_string(), "2.0");
+    /// assert_eq!(Number::Float(1e-7).to_string(), "1e-7");
+    /// assert_eq!(Number::Inf { negative: true }.to_string(), "-inf");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_number(f, self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::{String, ToString};
+
+    use super::{SerializeError, write_number_repr, write_value};
+    use crate::{
+        de::{number_repr, parse_value},
+        value::{Notation, Number, NumberRepr, Radix, Value},
+    };
+
+    fn to_string(v: &Value) -> Result<String, SerializeError> {
+        let mut s = String::new();
+        write_value(&mut s, v)?;
+        Ok(s)
+    }
+
+    fn round_trip(v: &Value) -> String {
+        let s = to_string(v).unwrap();
+        assert_eq!(&parse_value(&s).unwrap(), v, "{}", s);
+        s
+    }
+
+    #[test]
+    fn test_strings() {
+        for (s, expected) in [
+            ("hello!", "hello!"),
+            ("a\"b", "a\"b"),
+            (".5", ".5"),
+            ("nanny", "nanny"),
+            ("", r#""""#),
+            ("null", r#""null""#),
+            ("inf", r#""inf""#),
+            ("0day", r#""0day""#),
+            ("+1", r#""+1""#),
+            ("-x", r#""-x""#),
+            ("\"q", r#""\"q""#),
+            ("a b", r#""a b""#),
+            ("a\u{3000}b", "\"a\u{3000}b\""),
+            ("a:b", r#""a:b""#),
+            ("a;b", r#""a;b""#),
+            ("a,b", r#""a,b""#),
+            ("\\", "\\"),
+            ("x\\ y", r#""x\\ y""#),
+            ("\n\t\u{1}\u{7f}", r#""\n\t\x01\x7f""#),
+            ("é😀", "é😀"),
+        ] {
+            assert_eq!(round_trip(&Value::string(s)), expected);
+        }
+    }
+
+    #[test]
+    fn test_bytes() {
+        for (b, expected) in [
+            (&b"\xff"[..], r#""\xff""#),
+            (b"a b\xc3", r#""a b\xc3""#),
+            (b"\"\n\xc3\xa9\xe9", r#""\"\né\xe9""#),
+        ] {
+            assert_eq!(round_trip(&Value::Bytes(b.into())), expected);
+        }
+    }
+
+    #[test]
+    fn test_numbers() {
+        for (n, expected) in [
+            (Number::PosInt(0), "0"),
+            (Number::PosInt(u64::MAX), "18446744073709551615"),
+            (Number::NegInt(u64::MAX), "-1"),
+            (Number::NegInt(0), "-18446744073709551616"),
+            (Number::Float(1.0), "1.0"),
+            (Number::Float(-0.0), "-0.0"),
+            (Number::Float(0.1), "0.1"),
+            (Number::Float(1e20), "1e20"),
+            (Number::Float(1.5e-7), "1.5e-7"),
+            (Number::Float(f64::MAX), "1.7976931348623157e308"),
+            (Number::Float(5e-324), "5e-324"),
+            (Number::NaN, "nan"),
+            (Number::Inf { negative: false }, "inf"),
+            (Number::Inf { negative: true }, "-inf"),
+        ] {
+            let s = round_trip(&Value::number(n.clone()));
+            assert_eq!(s, expected);
+            assert_eq!(n.to_string(), expected);
+
+            let parsed = parse_value(&s).unwrap().as_number().unwrap();
+            assert_eq!(
+                core::mem::discriminant(&parsed),
+                core::mem::discriminant(&n)
+            );
+        }
+
+        // decimals are parsed back when reading them is enabled
+        #[cfg(feature = "decimal")]
+        for (s, expected) in [("-1.50", "-1.5"), ("2e-3", "0.002"), ("1e30", "1e30")] {
+            let v = Value::number(Number::Decimal(s.parse().unwrap()));
+            let written = to_string(&v).unwrap();
+            assert_eq!(written, expected);
+            assert_eq!(crate::de::from_str_decimal(&written).unwrap(), v);
+        }
+    }
+
+    #[test]
+    fn test_containers() {
+        let v = parse_value("profile:name:twic,version:0.1;,users::alice,bob;;").unwrap();
+        assert_eq!(
+            round_trip(&v),
+            "profile:name:twic,version:0.1;,users::alice,bob;;"
+        );
+
+        assert_eq!(round_trip(&Value::map_empty()), ";");
+        assert_eq!(round_trip(&Value::vector_empty()), ":;");
+        assert_eq!(
+            round_trip(&Value::vector([Value::vector_empty(), Value::map_empty()])),
+            "::;,;;"
+        );
+        assert_eq!(
+            round_trip(&Value::map_from([("", Value::map_empty())])),
+            r#""":;;"#
+        );
+        assert_eq!(
+            round_trip(&Value::vector([Value::map_from([("a", 1)]), Value::Null])),
+            ":a:1;,null;"
+        );
+
+        assert!(to_string(&Value::vector([Value::map_empty()])).is_err());
+        assert!(to_string(&Value::map_from([("a", [Value::map_empty()])])).is_err());
+    }
+
+    #[test]
+    fn test_number_reprs() {
+        fn spell(n: &Number, repr: Option<&NumberRepr>) -> String {
+            let mut s = String::new();
+            write_number_repr(&mut s, n, repr).unwrap();
+            s
+        }
+
+        for s in ["255", "-3", "1.5", "1e-7", "nan", "-inf"] {
+            let n = s.parse::<Number>().unwrap();
+            assert_eq!(number_repr(s, &n), None, "{}", s);
+            assert_eq!(spell(&n, None), s);
+        }
+
+        for s in [
+            "0xff",
+            "0xFF00",
+            "-0x10",
+            "0x00ff",
+            "+0x1",
+            "007",
+            "-0042",
+            "+5",
+            "+0",
+            "+1.5",
+            "1.5e3",
+            "1.5E+3",
+            "-2.5e-3",
+            "+1E+0",
+            "0.00001",
+            "100000000000000000.0",
+            "+inf",
+            "+0x0",
+        ] {
+            let n = s.parse::<Number>().unwrap();
+            let repr = number_repr(s, &n);
+            assert!(repr.is_some(), "{}", s);
+            assert_eq!(spell(&n, repr.as_ref()), s);
+        }
+
+        // spellings are kept as far as the number allows
+        for (s, expected) in [("1.50", "1.5"), ("1500.00", "1500.0")] {
+            let n = s.parse::<Number>().unwrap();
+            assert_eq!(spell(&n, number_repr(s, &n).as_ref()), expected);
+        }
+
+        // integer literals out of range are floats, written in fixed notation
+        #[cfg(not(feature = "bigint"))]
+        {
+            let s = "18446744073709551616000";
+            let n = s.parse::<Number>().unwrap();
+            assert_eq!(
+                spell(&n, number_repr(s, &n).as_ref()),
+                "18446744073709552000000.0"
+            );
+        }
+
+        let repr = NumberRepr {
+            radix: Radix::Hexadecimal,
+            min_digits: 4,
+            plus: true,
+            uppercase: true,
+            ..NumberRepr::default()
+        };
+        assert_eq!(spell(&Number::from(0xab), Some(&repr)), "+0x00AB");
+        assert_eq!(spell(&Number::from(-1), Some(&repr)), "-0x0001");
+        assert_eq!(spell(&Number::from(2.5), Some(&repr)), "+2.5");
+        assert_eq!(spell(&Number::NaN, Some(&repr)), "nan");
+
+        let repr = NumberRepr {
+            notation: Notation::Scientific,
+            exponent_plus: true,
+            ..NumberRepr::default()
+        };
+        assert_eq!(spell(&Number::from(1500.0), Some(&repr)), "1.5e+3");
+        assert_eq!(spell(&Number::from(-0.25), Some(&repr)), "-2.5e-1");
+        assert_eq!(spell(&Number::from(7), Some(&repr)), "7");
+    }
+}
@@ -0,0 +1,135 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+
+use super::{SerializeError, spelling::Spellings, write_compact};
+use crate::value::{NumberRepr, NumberReprs, Path, Value};
+
+/// Serializes [`Value`]s into compact Twic text like
+/// [`to_string`](super::to_string), writing numbers with configurable
+/// spellings.
+///
+/// Numbers are written with the spelling set for their path with
+/// [`number_repr_at`](CompactFormatter::number_repr_at), or else with the one
+/// recorded for them with [`number_reprs`](CompactFormatter::number_reprs),
+/// or else with the spelling set with
+/// [`number_repr`](CompactFormatter::number_repr), like a
+/// [`PrettyFormatter`](super::PrettyFormatter) does.
+///
+/// # Examples
+///
+/// ```
+/// use twic::ser::CompactFormatter;
+///
+/// let (v, reprs) = twic::de::from_str_with_reprs("mask: 0x00FF, ids: :007, 1;;").unwrap();
+/// assert_eq!(twic::to_string(&v).unwrap(), "ids::7,1;,mask:255;");
+///
+/// let f = CompactFormatter::new().number_reprs(reprs);
+/// assert_eq!(f.to_string(&v).unwrap(), "ids::007,1;,mask:0x00FF;");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompactFormatter {
+    numbers: Spellings,
+}
+
+impl CompactFormatter {
+    /// Creates a formatter writing numbers the default way.
+    pub fn new() -> Self {
+        CompactFormatter {
+            numbers: Spellings::default(),
+        }
+    }
+
+    /// Sets the spelling of numbers that have none set for their path.
+    pub fn number_repr(mut self, repr: NumberRepr) -> Self {
+        self.numbers.repr = Some(repr);
+        self
+    }
+
+    /// Sets the spelling of numbers at the given path, where `[*]` matches
+    /// every element of a vector. See
+    /// [`PrettyFormatter::number_repr_at`](super::PrettyFormatter::number_repr_at)
+    /// for details.
+    pub fn number_repr_at(mut self, path: Path, repr: NumberRepr) -> Self {
+        self.numbers.set_at(path, repr);
+        self
+    }
+
+    /// Sets the spellings of numbers by their exact path, such as the
+    /// spellings returned by
+    /// [`from_str_with_reprs`](crate::de::from_str_with_reprs), replacing
+    /// those set before.
+    pub fn number_reprs(mut self, reprs: NumberReprs) -> Self {
+        self.numbers.reprs = reprs;
+        self
+    }
+
+    /// Serializes a [`Value`] into compact Twic text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value contains a vector whose first element is
+    /// an empty map, which has no Twic representation.
+    pub fn to_string(&self, value: &Value) -> Result<String, SerializeError> {
+        let mut s = String::new();
+        self.to_writer(&mut s, value)?;
+        Ok(s)
+    }
+
+    /// Serializes a [`Value`] into compact Twic text, writing it to the given
+    /// writer. See [`CompactFormatter::to_string`] for details.
+    pub fn to_writer<W: Write + ?Sized>(
+        &self,
+        writer: &mut W,
+        value: &Value,
+    ) -> Result<(), SerializeError> {
+        write_compact(writer, value, &self.numbers, &mut Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CompactFormatter;
+    use crate::{
+        de::{from_str_with_reprs, parse_value},
+        value::{Map, Notation, NumberRepr, Radix, Value},
+    };
+
+    #[test]
+    fn test_number_reprs() {
+        let s = "a:+0x1F,b::007,-1.5E3,c:+2;;,d:1;";
+        let (v, reprs) = from_str_with_reprs(s).unwrap();
+        let f = CompactFormatter::new().number_reprs(reprs);
+        assert_eq!(f.to_string(&v).unwrap(), s);
+        assert_eq!(CompactFormatter::new().to_string(&v), crate::to_string(&v));
+
+        // spellings apply only to the numbers they were recorded for
+        let mut v = v;
+        *v["a"].as_number_mut().unwrap() = 31.5.into();
+        v["b"].as_vector_mut().unwrap().remove(0);
+        assert_eq!(f.to_string(&v).unwrap(), "a:31.5,b::-1500.0,c:2;;,d:1;");
+        assert_eq!(parse_value(&f.to_string(&v).unwrap()).unwrap(), v);
+
+        let v = Value::vector([Value::Map(Map::new())]);
+        assert!(f.to_string(&v).is_err());
+    }
+
+    #[test]
+    fn test_number_options() {
+        let hex = NumberRepr {
+            radix: Radix::Hexadecimal,
+            ..NumberRepr::default()
+        };
+        let scientific = NumberRepr {
+            notation: Notation::Scientific,
+            ..NumberRepr::default()
+        };
+        let v: Value = "masks::255,16;,ratio:0.5,n:10;".parse().unwrap();
+        let f = CompactFormatter::new()
+            .number_repr(scientific)
+            .number_repr_at("masks[*]".parse().unwrap(), hex);
+        assert_eq!(
+            f.to_string(&v).unwrap(),
+            "masks::0xff,0x10;,n:10,ratio:5e-1;"
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use alloc::string::String;
+use core::fmt;
+
+/// The kind of error that occurred while serializing a value to Twic text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SerializeErrorKind {
+    /// An empty map is the first element of a vector. Such a vector has no
+    /// Twic representation, as `:;` always denotes an empty vector.
+    EmptyMapAtVectorHead,
+    /// A map key is not a string, a character or an integer.
+    KeyMustBeString,
+    /// An integer is outside the range of integer [`Number`](crate::Number)s.
+    IntegerOutOfRange,
+    /// A [`Writer`](super::Writer) was asked to write a key outside a map, or
+    /// before the value of the previous key.
+    UnexpectedKey,
+    /// A [`Writer`](super::Writer) was asked to write a value in a map without
+    /// a key, or after the top-level value.
+    UnexpectedValue,
+    /// A [`Writer`](super::Writer) was asked to close a container while none is
+    /// open, or while a key has no value.
+    UnexpectedEnd,
+    /// A [`Writer`](super::Writer) was finished before the top-level value was
+    /// complete.
+    Unfinished,
+    /// The underlying writer returned an error.
+    Write,
+    /// A custom error reported by a `Serialize` implementation.
+    Custom(String),
+}
+
+impl fmt::Display for SerializeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SerializeErrorKind::EmptyMapAtVectorHead => {
+                write!(f, "an empty map cannot be the first element of a vector")
+            }
+            SerializeErrorKind::KeyMustBeString => {
+                write!(f, "map key must be a string, a character or an integer")
+            }
+            SerializeErrorKind::IntegerOutOfRange => write!(f, "integer out of range"),
+            SerializeErrorKind::UnexpectedKey => write!(f, "unexpected map key"),
+            SerializeErrorKind::UnexpectedValue => write!(f, "unexpected value"),
+            SerializeErrorKind::UnexpectedEnd => write!(f, "unexpected end of map or vector"),
+            SerializeErrorKind::Unfinished => write!(f, "top-level value is incomplete"),
+            SerializeErrorKind::Write => write!(f, "failed to write to the underlying writer"),
+            SerializeErrorKind::Custom(msg) => f.write_str(msg),
+        }
+    }
+}
+
+/// An error that occurred while serializing a value to Twic text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerializeError {
+    kind: SerializeErrorKind,
+}
+
+impl SerializeError {
+    /// Creates a new serialize error of the given kind.
+    pub(crate) fn new(kind: SerializeErrorKind) -> Self {
+        SerializeError { kind }
+    }
+
+    /// Returns the kind of the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::{Value, ser::SerializeErrorKind};
+    ///
+    /// let v = Value::vector([Value::map_empty()]);
+    /// let err = twic::to_string(&v).unwrap_err();
+    /// assert_eq!(err.kind(), &SerializeErrorKind::EmptyMapAtVectorHead);
+    /// ```
+    pub fn kind(&self) -> &SerializeErrorKind {
+        &self.kind
+    }
+}
+
+impl From<fmt::Error> for SerializeError {
+    fn from(_: fmt::Error) -> Self {
+        SerializeError::new(SerializeErrorKind::Write)
+    }
+}
+
+impl fmt::Display for SerializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.kind.fmt(f)
+    }
+}
+
+impl core::error::Error for SerializeError {}
+
+#[cfg(feature = "serde")]
+impl serde::ser::Error for SerializeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        SerializeError::new(SerializeErrorKind::Custom(alloc::format!("{}", msg)))
+    }
+}
@@ -0,0 +1,618 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Write};
+
+use super::{
+    SerializeError, SerializeErrorKind, is_empty_map,
+    spelling::{Spellings, Step},
+    write_bytes, write_number_repr, write_str,
+};
+use crate::value::{NumberRepr, NumberReprs, Path, Value};
+
+/// Serializes [`Value`]s into human-readable Twic text.
+///
+/// Containers that fit into the remaining line width are written on a single
+/// line, with a space after each `,` and after the `:` of each key-value pair.
+/// Other containers are broken across lines, putting each element or
+/// key-value pair on its own line:
+///
+/// - Elements of a vector are indented one level deeper than the line holding
+///   its opening `:`, and the closing `;` is put on its own line at the level
+///   of the opening line.
+/// - Key-value pairs of a map that is the value of another key-value pair are
+///   indented one level deeper than the outer key, and the closing `;` is put
+///   on its own line at the level of the outer key. Key-value pairs of any
+///   other map, and its closing `;`, are at the level of the map itself.
+///
+/// Numbers are written with the spelling set for their path with
+/// [`number_repr_at`](PrettyFormatter::number_repr_at), or else with the one
+/// set for their path with [`number_reprs`](PrettyFormatter::number_reprs),
+/// or else with the spelling set with
+/// [`number_repr`](PrettyFormatter::number_repr).
+///
+/// # Examples
+///
+/// ```
+/// use twic::{Value, ser::PrettyFormatter};
+///
+/// let v: Value = "profile:name:twic,version:0.1;,users::alice,bob;;".parse().unwrap();
+///
+/// assert_eq!(
+///     PrettyFormatter::new().to_string(&v).unwrap(),
+///     "profile: name: twic, version: 0.1;, users: :alice, bob;;",
+/// );
+///
+/// let formatted = PrettyFormatter::new().max_width(20).to_string(&v).unwrap();
+/// assert_eq!(
+///     formatted,
+///     "profile:
+///   name: twic,
+///   version: 0.1
+/// ;,
+/// users: :alice, bob;
+/// ;",
+/// );
+/// assert_eq!(formatted.parse::<Value>().unwrap(), v);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrettyFormatter {
+    indent: usize,
+    max_width: usize,
+    numbers: Spellings,
+}
+
+impl Default for PrettyFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PrettyFormatter {
+    /// Creates a formatter indenting by 2 spaces per level, with a maximum line
+    /// width of 80 characters.
+    pub fn new() -> Self {
+        PrettyFormatter {
+            indent: 2,
+            max_width: 80,
+            numbers: Spellings::default(),
+        }
+    }
+
+    /// Sets the number of spaces per indentation level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::{Value, ser::PrettyFormatter};
+    ///
+    /// let v = Value::vector_from(["alice", "bob"]);
+    /// let f = PrettyFormatter::new().indent(4).max_width(0);
+    /// assert_eq!(f.to_string(&v).unwrap(), ":\n    alice,\n    bob\n;");
+    /// ```
+    pub fn indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Sets the maximum line width, in characters. Lines may still exceed this
+    /// width if a single key or scalar does not fit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::{Value, ser::PrettyFormatter};
+    ///
+    /// let v = Value::map_from([("a", 1), ("b", 2)]);
+    /// assert_eq!(PrettyFormatter::new().max_width(11).to_string(&v).unwrap(), "a: 1, b: 2;");
+    /// assert_eq!(PrettyFormatter::new().max_width(10).to_string(&v).unwrap(), "a: 1,\nb: 2\n;");
+    /// ```
+    pub fn max_width(mut self, max_width: usize) -> Self {
+        self.max_width = max_width;
+        self
+    }
+
+    /// Sets the spelling of numbers that have none set for their path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::{
+    ///     Value,
+    ///     ser::PrettyFormatter,
+    ///     value::{Notation, NumberRepr, Radix},
+    /// };
+    ///
+    /// let repr = NumberRepr {
+    ///     radix: Radix::Hexadecimal,
+    ///     notation: Notation::Scientific,
+    ///     ..NumberRepr::default()
+    /// };
+    /// let v: Value = "a:255,b:0.5;".parse().unwrap();
+    /// let f = PrettyFormatter::new().number_repr(repr);
+    /// assert_eq!(f.to_string(&v).unwrap(), "a: 0xff, b: 5e-1;");
+    /// ```
+    pub fn number_repr(mut self, repr: NumberRepr) -> Self {
+        self.numbers.repr = Some(repr);
+        self
+    }
+
+    /// Sets the spelling of numbers at the given path, overriding the
+    /// spellings set with [`number_reprs`](PrettyFormatter::number_reprs). A
+    /// [`PathSegment::AnyIndex`](crate::value::PathSegment::AnyIndex) segment,
+    /// written `[*]`, matches every element of a vector. If several paths set
+    /// this way match a number, the one set last applies.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::{
+    ///     Value,
+    ///     ser::PrettyFormatter,
+    ///     value::{NumberRepr, Radix},
+    /// };
+    ///
+    /// let hex = NumberRepr {
+    ///     radix: Radix::Hexadecimal,
+    ///     min_digits: 4,
+    ///     ..NumberRepr::default()
+    /// };
+    /// let v: Value = "perms:masks::255,4096;,count:2;;".parse().unwrap();
+    /// let f = PrettyFormatter::new().number_repr_at("perms.masks[*]".parse().unwrap(), hex);
+    /// assert_eq!(
+    ///     f.to_string(&v).unwrap(),
+    ///     "perms: count: 2, masks: :0x00ff, 0x1000;;;",
+    /// );
+    /// ```
+    pub fn number_repr_at(mut self, path: Path, repr: NumberRepr) -> Self {
+        self.numbers.set_at(path, repr);
+        self
+    }
+
+    /// Sets the spellings of numbers by their exact path, such as the
+    /// spellings returned by
+    /// [`from_str_with_reprs`](crate::de::from_str_with_reprs), replacing
+    /// those set before.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::ser::PrettyFormatter;
+    ///
+    /// let (v, reprs) = twic::de::from_str_with_reprs("mask:0x00FF,ratio:+1.5E-3,ids::007,1;;").unwrap();
+    /// let f = PrettyFormatter::new().number_reprs(reprs);
+    /// assert_eq!(f.to_string(&v).unwrap(), "ids: :007, 1;, mask: 0x00FF, ratio: +1.5E-3;");
+    /// ```
+    pub fn number_reprs(mut self, reprs: NumberReprs) -> Self {
+        self.numbers.reprs = reprs;
+        self
+    }
+
+    /// Serializes a [`Value`] into human-readable Twic text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value contains a vector whose first element is
+    /// an empty map, which has no Twic representation.
+    pub fn to_string(&self, value: &Value) -> Result<String, SerializeError> {
+        let mut s = String::new();
+        self.to_writer(&mut s, value)?;
+        Ok(s)
+    }
+
+    /// Serializes a [`Value`] into human-readable Twic text, writing it to the
+    /// given writer. See [`PrettyFormatter::to_string`] for details.
+    pub fn to_writer<W: Write + ?Sized>(
+        &self,
+        writer: &mut W,
+        value: &Value,
+    ) -> Result<(), SerializeError> {
+        let mut state = State {
+            formatter: self,
+            writer: ColumnWriter {
+                inner: writer,
+                column: 0,
+            },
+            path: Vec::new(),
+        };
+        state.value(value, 0, Position::Element)
+    }
+}
+
+/// Where a value is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Position {
+    /// At the top level, or as an element of a vector.
+    Element,
+    /// As the value of a key-value pair, right after the `:`.
+    MapValue,
+}
+
+/// A writer keeping track of the current column.
+struct ColumnWriter<'a, W: ?Sized> {
+    inner: &'a mut W,
+    column: usize,
+}
+
+impl<W: Write + ?Sized> Write for ColumnWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        match s.rfind('\n') {
+            Some(i) => self.column = s[i + 1..].chars().count(),
+            None => self.column += s.chars().count(),
+        }
+        self.inner.write_str(s)
+    }
+}
+
+/// A writer counting characters, failing once the count exceeds a limit.
+struct WidthCounter {
+    width: usize,
+    limit: usize,
+}
+
+impl Write for WidthCounter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.width += s.chars().count();
+        if self.width > self.limit || s.contains('\n') {
+            Err(fmt::Error)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+struct State<'a, 'v, W: ?Sized> {
+    formatter: &'a PrettyFormatter,
+    writer: ColumnWriter<'a, W>,
+    /// The map keys and vector indices leading to the current value.
+    path: Vec<Step<'v>>,
+}
+
+impl<'v, W: Write + ?Sized> State<'_, 'v, W> {
+    /// Checks if the value fits on the current line when written inline after
+    /// `extra` more characters.
+    fn fits_inline(&mut self, value: &'v Value, extra: usize) -> bool {
+        let Some(limit) = self
+            .formatter
+            .max_width
+            .checked_sub(self.writer.column + extra)
+        else {
+            return false;
+        };
+
+        let depth = self.path.len();
+        let counter = &mut WidthCounter { width: 0, limit };
+        let fits = write_inline(counter, value, self.formatter, &mut self.path).is_ok();
+        // writing stops early, leaving steps on the path, once the line is full
+        self.path.truncate(depth);
+        fits
+    }
+
+    /// Starts a new line at the given indentation level.
+    fn newline(&mut self, level: usize) -> fmt::Result {
+        self.writer.write_char('\n')?;
+        for _ in 0..level * self.formatter.indent {
+            self.writer.write_char(' ')?;
+        }
+        Ok(())
+    }
+
+    /// Writes a value, where `level` is the indentation level of the current
+    /// line.
+    fn value(
+        &mut self,
+        value: &'v Value,
+        level: usize,
+        position: Position,
+    ) -> Result<(), SerializeError> {
+        let space = match position {
+            Position::Element => 0,
+            Position::MapValue => 1,
+        };
+
+        if is_atomic(value) || self.fits_inline(value, space) {
+            if position == Position::MapValue {
+                self.writer.write_char(' ')?;
+            }
+            return write_inline(&mut self.writer, value, self.formatter, &mut self.path);
+        }
+
+        match value {
+            Value::Vector(v) => {
+                if v.first().is_some_and(is_empty_map) {
+                    return Err(SerializeError::new(
+                        SerializeErrorKind::EmptyMapAtVectorHead,
+                    ));
+                }
+
+                if position == Position::MapValue {
+                    self.writer.write_char(' ')?;
+                }
+
+                self.writer.write_char(':')?;
+                for (i, item) in v.iter().enumerate() {
+                    if i > 0 {
+                        self.writer.write_char(',')?;
+                    }
+                    self.newline(level + 1)?;
+                    self.path.push(Step::Index(i));
+                    self.value(item, level + 1, Position::Element)?;
+                    self.path.pop();
+                }
+                self.newline(level)?;
+                self.writer.write_char(';')?;
+            }
+            Value::Map(m) => {
+                let entry_level = match position {
+                    Position::Element => level,
+                    Position::MapValue => level + 1,
+                };
+
+                for (i, (key, value)) in m.iter().enumerate() {
+                    if i > 0 {
+                        self.writer.write_char(',')?;
+                    }
+                    if i > 0 || position == Position::MapValue {
+                        self.newline(entry_level)?;
+                    }
+                    write_str(&mut self.writer, key)?;
+                    self.writer.write_char(':')?;
+                    self.path.push(Step::Key(key));
+                    self.value(value, entry_level, Position::MapValue)?;
+                    self.path.pop();
+                }
+                self.newline(level)?;
+                self.writer.write_char(';')?;
+            }
+            _ => unreachable!("atomic values are always written inline"),
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks if the value is a scalar or an empty container, which are never
+/// broken across lines.
+fn is_atomic(value: &Value) -> bool {
+    match value {
+        Value::Vector(v) => v.is_empty(),
+        Value::Map(m) => m.is_empty(),
+        _ => true,
+    }
+}
+
+/// Writes a value on a single line, with a space after each `,` and after the
+/// `:` of each key-value pair, where `path` holds the map keys and vector
+/// indices leading to the value.
+fn write_inline<'v, W: Write + ?Sized>(
+    writer: &mut W,
+    value: &'v Value,
+    formatter: &PrettyFormatter,
+    path: &mut Vec<Step<'v>>,
+) -> Result<(), SerializeError> {
+    match value {
+        Value::Null => writer.write_str("null")?,
+        Value::Boolean(b) => writer.write_str(if *b { "true" } else { "false" })?,
+        Value::Number(n) => write_number_repr(writer, n, formatter.numbers.get(path, n))?,
+        Value::String(s) => write_str(writer, s)?,
+        Value::Bytes(b) => write_bytes(writer, b)?,
+        Value::Vector(v) => {
+            if v.first().is_some_and(is_empty_map) {
+                return Err(SerializeError::new(
+                    SerializeErrorKind::EmptyMapAtVectorHead,
+                ));
+            }
+
+            writer.write_char(':')?;
+            for (i, item) in v.iter().enumerate() {
+                if i > 0 {
+                    writer.write_str(", ")?;
+                }
+                path.push(Step::Index(i));
+                write_inline(writer, item, formatter, path)?;
+                path.pop();
+            }
+            writer.write_char(';')?;
+        }
+        Value::Map(m) => {
+            for (i, (key, value)) in m.iter().enumerate() {
+                if i > 0 {
+                    writer.write_str(", ")?;
+                }
+                write_str(writer, key)?;
+                writer.write_str(": ")?;
+                path.push(Step::Key(key));
+                write_inline(writer, value, formatter, path)?;
+                path.pop();
+            }
+            writer.write_char(';')?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::PrettyFormatter;
+    use crate::{
+        de::{from_str_with_reprs, parse_value},
+        value::{Map, Notation, NumberRepr, Path, Radix, Value},
+    };
+
+    #[test]
+    fn test_layout() {
+        let v = parse_value(
+            "name:twic,tags::tiny,writable,inline;,nested:a:1,b::x,y;,c:;;,list::k:v;,::;;,:1;;;",
+        )
+        .unwrap();
+
+        let f = PrettyFormatter::new().max_width(16);
+        let s = f.to_string(&v).unwrap();
+        assert_eq!(
+            s,
+            "\
+list: :
+  k: v;,
+  ::;;,
+  :1;
+;,
+name: twic,
+nested:
+  a: 1,
+  b: :x, y;,
+  c: ;
+;,
+tags: :
+  tiny,
+  writable,
+  inline
+;
+;"
+        );
+        assert_eq!(parse_value(&s).unwrap(), v);
+
+        let f = PrettyFormatter::new().max_width(0).indent(1);
+        let s = f.to_string(&v).unwrap();
+        assert_eq!(parse_value(&s).unwrap(), v);
+        assert!(s.lines().any(|l| l == " k: v"));
+    }
+
+    #[test]
+    fn test_map_elements() {
+        let v = Value::vector([
+            Value::map_from([("a", 1), ("b", 2)]),
+            Value::map_from([("c", Value::map_from([("d", 3)]))]),
+        ]);
+
+        let s = PrettyFormatter::new().max_width(0).to_string(&v).unwrap();
+        assert_eq!(
+            s,
+            "\
+:
+  a: 1,
+  b: 2
+  ;,
+  c:
+    d: 3
+  ;
+  ;
+;"
+        );
+        assert_eq!(parse_value(&s).unwrap(), v);
+    }
+
+    #[test]
+    fn test_number_reprs() {
+        let (v, reprs) =
+            from_str_with_reprs("mask:0x00FF,ratio:+1.5E-3,ids::007,-0x1,n:+2;;;").unwrap();
+        let f = PrettyFormatter::new().number_reprs(reprs);
+        assert_eq!(
+            f.to_string(&v).unwrap(),
+            "ids: :007, -0x1, n: +2;;, mask: 0x00FF, ratio: +1.5E-3;"
+        );
+        assert_eq!(
+            f.clone().max_width(0).to_string(&v).unwrap(),
+            "\
+ids: :
+  007,
+  -0x1,
+  n: +2
+  ;
+;,
+mask: 0x00FF,
+ratio: +1.5E-3
+;"
+        );
+
+        // spellings apply only to the numbers they were recorded for
+        let mut v = v;
+        *v["mask"].as_number_mut().unwrap() = 16.into();
+        v["ids"].as_vector_mut().unwrap().remove(0);
+        assert_eq!(
+            f.to_string(&v).unwrap(),
+            "ids: :-1, n: 2;;, mask: 16, ratio: +1.5E-3;"
+        );
+    }
+
+    #[test]
+    fn test_number_options() {
+        let path = |s: &str| s.parse::<Path>().unwrap();
+        let v = Value::map_from([
+            ("a", Value::map_from([("mask", -16), ("n", 7)])),
+            ("b", Value::vector_from([1e-5, 2.5e20])),
+            ("mask", Value::number(255)),
+        ]);
+        let hex = NumberRepr {
+            radix: Radix::Hexadecimal,
+            plus: true,
+            ..NumberRepr::default()
+        };
+        let fixed = NumberRepr {
+            notation: Notation::Fixed,
+            min_digits: 2,
+            ..NumberRepr::default()
+        };
+
+        let f = PrettyFormatter::new()
+            .number_repr(fixed)
+            .number_repr_at(path("a.mask"), hex);
+        assert_eq!(
+            f.to_string(&v).unwrap(),
+            "a: mask: -0x10, n: 07;, b: :0.00001, 250000000000000000000.0;, mask: 255;"
+        );
+
+        // paths are tracked when containers are broken across lines, even
+        // after checking if they fit on a line
+        let f = f.clone().max_width(12);
+        let s = f.to_string(&v).unwrap();
+        assert_eq!(
+            s,
+            "\
+a:
+  mask: -0x10,
+  n: 07
+;,
+b: :
+  0.00001,
+  250000000000000000000.0
+;,
+mask: 255
+;"
+        );
+
+        // per-path spellings override the spellings recorded when parsing
+        let scientific = NumberRepr {
+            notation: Notation::Scientific,
+            uppercase: true,
+            ..NumberRepr::default()
+        };
+        let (v, reprs) = from_str_with_reprs("x:0x0F,y:0x0F,z::1.25;;").unwrap();
+        let f = PrettyFormatter::new()
+            .number_repr(hex)
+            .number_reprs(reprs)
+            .number_repr_at(path("y"), fixed)
+            .number_repr_at(path("z[*]"), fixed)
+            .number_repr_at(path("z[*]"), scientific);
+        assert_eq!(f.to_string(&v).unwrap(), "x: 0x0F, y: 15, z: :1.25E0;;");
+
+        // wildcards match indices only, and later paths win over earlier ones
+        let v: Value = "a::1,2,k:3;;,b:k:4;;".parse().unwrap();
+        let f = PrettyFormatter::new()
+            .number_repr_at(path("a[1]"), hex)
+            .number_repr_at(path("a[*]"), fixed)
+            .number_repr_at(path("a[*].k"), hex)
+            .number_repr_at(path("[*].k"), fixed);
+        assert_eq!(f.to_string(&v).unwrap(), "a: :01, 02, k: +0x3;;, b: k: 4;;");
+        let f = f.number_repr_at(path("a[1]"), hex);
+        assert_eq!(
+            f.to_string(&v).unwrap(),
+            "a: :01, +0x2, k: +0x3;;, b: k: 4;;"
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let v = Value::map_from([("a", Value::vector([Value::Map(Map::new())]))]);
+        assert!(PrettyFormatter::new().to_string(&v).is_err());
+        assert!(PrettyFormatter::new().max_width(0).to_string(&v).is_err());
+    }
+}
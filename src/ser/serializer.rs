@@ -0,0 +1,820 @@
+use alloc::string::String;
+use core::{
+    fmt::{self, Write},
+    mem,
+};
+
+use serde::ser::{self, Serialize};
+
+use super::{SerializeError, SerializeErrorKind, write_bytes, write_number, write_str};
+#[cfg(any(feature = "bigint", feature = "decimal"))]
+use crate::de::parse_number_exact;
+use crate::value::Number;
+#[cfg(any(feature = "bigint", feature = "decimal"))]
+use crate::value::to_value;
+
+/// Serializes a Rust value into compact Twic text.
+///
+/// Maps, structs and struct variants are written as Twic maps, and sequences,
+/// tuples and tuple variants as Twic vectors. `None`, `()` and unit structs are
+/// written as `null`. Map keys must be strings, characters or integers, and
+/// are written as strings.
+///
+/// Enums use the externally tagged representation: a unit variant is written
+/// as the variant name, and any other variant as a map with the variant name
+/// as its single key. See the [crate documentation](crate#serde-support) for
+/// details.
+///
+/// # Errors
+///
+/// Returns an error if the value contains a vector whose first element is an
+/// empty map, which has no Twic representation, a map key of another type, an
+/// integer outside the range of integer [`Number`]s, or if the `Serialize`
+/// implementation fails.
+///
+/// # Examples
+///
+/// ```
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Config {
+///     name: String,
+///     port: u16,
+///     users: Vec<String>,
+///     proxy: Option<String>,
+/// }
+///
+/// let cfg = Config {
+///     name: "twic".into(),
+///     port: 8080,
+///     users: vec!["alice".into(), "bob smith".into()],
+///     proxy: None,
+/// };
+/// assert_eq!(
+///     twic::serde::to_string(&cfg).unwrap(),
+///     r#"name:twic,port:8080,users::alice,"bob smith";,proxy:null;"#,
+/// );
+/// ```
+pub fn to_string<T: Serialize + ?Sized>(value: &T) -> Result<String, SerializeError> {
+    let mut s = String::new();
+    to_writer(&mut s, value)?;
+    Ok(s)
+}
+
+/// Serializes a Rust value into compact Twic text, writing it to the given
+/// writer. See [`to_string`] for details.
+///
+/// # Examples
+///
+/// ```
+/// let mut s = String::from("config=");
+/// twic::serde::to_writer(&mut s, &[1, 2]).unwrap();
+/// assert_eq!(s, "config=:1,2;");
+/// ```
+pub fn to_writer<W: Write + ?Sized, T: Serialize + ?Sized>(
+    writer: &mut W,
+    value: &T,
+) -> Result<(), SerializeError> {
+    value.serialize(&mut Serializer::new(writer))
+}
+
+/// A serde [`Serializer`](ser::Serializer) writing compact Twic text.
+///
+/// See [`to_string`] for how Rust values are mapped to Twic values.
+pub struct Serializer<W> {
+    writer: W,
+    /// Whether the next value is the first element of a vector.
+    at_vector_head: bool,
+}
+
+impl<W: Write> Serializer<W> {
+    /// Creates a serializer writing to the given writer.
+    pub fn new(writer: W) -> Self {
+        Serializer {
+            writer,
+            at_vector_head: false,
+        }
+    }
+
+    /// Returns the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde::Serialize;
+    /// use twic::ser::Serializer;
+    ///
+    /// let mut ser = Serializer::new(String::new());
+    /// Some("hello!").serialize(&mut ser).unwrap();
+    /// assert_eq!(ser.into_inner(), "hello!");
+    /// ```
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Writes a scalar, which is never an empty map at the head of a vector.
+    fn scalar(&mut self, f: impl FnOnce(&mut W) -> fmt::Result) -> Result<(), SerializeError> {
+        self.at_vector_head = false;
+        Ok(f(&mut self.writer)?)
+    }
+
+    /// Starts a map, remembering whether it is the first element of a vector.
+    fn begin_map(&mut self, close: &'static str) -> Compound<'_, W> {
+        let at_vector_head = mem::take(&mut self.at_vector_head);
+        Compound {
+            ser: self,
+            first: true,
+            at_vector_head,
+            close,
+        }
+    }
+
+    /// Starts a vector.
+    fn begin_vector(&mut self, close: &'static str) -> Result<Compound<'_, W>, SerializeError> {
+        self.at_vector_head = false;
+        self.writer.write_char(':')?;
+        Ok(Compound {
+            ser: self,
+            first: true,
+            at_vector_head: false,
+            close,
+        })
+    }
+
+    /// Writes the key of a variant written as a single-key map.
+    fn variant_key(&mut self, variant: &str) -> Result<(), SerializeError> {
+        self.at_vector_head = false;
+        write_str(&mut self.writer, variant)?;
+        Ok(self.writer.write_char(':')?)
+    }
+}
+
+/// Writes an `i128` or `u128`, which must be in the range of integer
+/// [`Number`]s.
+fn write_wide_int<W: Write, T>(writer: &mut W, n: T) -> Result<(), SerializeError>
+where
+    Number: TryFrom<T>,
+{
+    let n = Number::try_from(n)
+        .map_err(|_| SerializeError::new(SerializeErrorKind::IntegerOutOfRange))?;
+    Ok(write_number(writer, &n)?)
+}
+
+impl<'a, W: Write> ser::Serializer for &'a mut Serializer<W> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    type SerializeSeq = Compound<'a, W>;
+    type SerializeTuple = Compound<'a, W>;
+    type SerializeTupleStruct = Compound<'a, W>;
+    type SerializeTupleVariant = Compound<'a, W>;
+    type SerializeMap = Compound<'a, W>;
+    type SerializeStruct = Compound<'a, W>;
+    type SerializeStructVariant = Compound<'a, W>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), SerializeError> {
+        self.scalar(|w| w.write_str(if v { "true" } else { "false" }))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), SerializeError> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), SerializeError> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), SerializeError> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), SerializeError> {
+        self.scalar(|w| write!(w, "{}", v))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), SerializeError> {
+        self.at_vector_head = false;
+        write_wide_int(&mut self.writer, v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), SerializeError> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), SerializeError> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), SerializeError> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), SerializeError> {
+        self.scalar(|w| write!(w, "{}", v))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), SerializeError> {
+        self.at_vector_head = false;
+        write_wide_int(&mut self.writer, v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), SerializeError> {
+        if v.is_finite() {
+            // print the shortest representation of the `f32`, not of the
+            // `f64` it converts to
+            self.scalar(|w| write!(w, "{:?}", v))
+        } else {
+            self.serialize_f64(v.into())
+        }
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), SerializeError> {
+        self.scalar(|w| write_number(w, &Number::from(v)))
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), SerializeError> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), SerializeError> {
+        self.scalar(|w| write_str(w, v))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), SerializeError> {
+        self.scalar(|w| match core::str::from_utf8(v) {
+            Ok(s) => write_str(w, s),
+            Err(_) => write_bytes(w, v),
+        })
+    }
+
+    fn serialize_none(self) -> Result<(), SerializeError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), SerializeError> {
+        self.scalar(|w| w.write_str("null"))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), SerializeError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), SerializeError> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        #[cfg(any(feature = "bigint", feature = "decimal"))]
+        if _name == Number::SERDE_TOKEN
+            && let Some(n) = to_value(value)?.as_str().and_then(parse_number_exact)
+        {
+            return self.scalar(|w| write_number(w, &n));
+        }
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.variant_key(variant)?;
+        value.serialize(&mut *self)?;
+        Ok(self.writer.write_char(';')?)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Compound<'a, W>, SerializeError> {
+        self.begin_vector(";")
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Compound<'a, W>, SerializeError> {
+        self.begin_vector(";")
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a, W>, SerializeError> {
+        self.begin_vector(";")
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a, W>, SerializeError> {
+        self.variant_key(variant)?;
+        self.begin_vector(";;")
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Compound<'a, W>, SerializeError> {
+        Ok(self.begin_map(";"))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a, W>, SerializeError> {
+        Ok(self.begin_map(";"))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a, W>, SerializeError> {
+        self.variant_key(variant)?;
+        Ok(self.begin_map(";;"))
+    }
+}
+
+/// Serializes the elements of a vector or the entries of a map.
+pub struct Compound<'a, W> {
+    ser: &'a mut Serializer<W>,
+    /// Whether no element or entry has been written yet.
+    first: bool,
+    /// Whether the map is the first element of a vector.
+    at_vector_head: bool,
+    /// The text closing the container, and the variant map wrapping it if any.
+    close: &'static str,
+}
+
+impl<W: Write> Compound<'_, W> {
+    /// Writes the `,` before an element or entry, unless it is the first one.
+    fn separator(&mut self) -> Result<(), SerializeError> {
+        if !mem::replace(&mut self.first, false) {
+            self.ser.writer.write_char(',')?;
+        }
+        Ok(())
+    }
+
+    /// Writes an element of a vector.
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        self.ser.at_vector_head = self.first;
+        self.separator()?;
+        value.serialize(&mut *self.ser)
+    }
+
+    /// Writes a key of a map.
+    fn key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerializeError> {
+        self.separator()?;
+        key.serialize(KeySerializer {
+            writer: &mut self.ser.writer,
+        })?;
+        Ok(self.ser.writer.write_char(':')?)
+    }
+
+    /// Closes the container.
+    fn close(self) -> Result<(), SerializeError> {
+        if self.first && self.at_vector_head {
+            return Err(SerializeError::new(
+                SerializeErrorKind::EmptyMapAtVectorHead,
+            ));
+        }
+        Ok(self.ser.writer.write_str(self.close)?)
+    }
+}
+
+impl<W: Write> ser::SerializeSeq for Compound<'_, W> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), SerializeError> {
+        self.close()
+    }
+}
+
+impl<W: Write> ser::SerializeTuple for Compound<'_, W> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), SerializeError> {
+        self.close()
+    }
+}
+
+impl<W: Write> ser::SerializeTupleStruct for Compound<'_, W> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), SerializeError> {
+        self.close()
+    }
+}
+
+impl<W: Write> ser::SerializeTupleVariant for Compound<'_, W> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), SerializeError> {
+        self.close()
+    }
+}
+
+impl<W: Write> ser::SerializeMap for Compound<'_, W> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerializeError> {
+        self.key(key)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<(), SerializeError> {
+        self.close()
+    }
+}
+
+impl<W: Write> ser::SerializeStruct for Compound<'_, W> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.key(key)?;
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<(), SerializeError> {
+        self.close()
+    }
+}
+
+impl<W: Write> ser::SerializeStructVariant for Compound<'_, W> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.key(key)?;
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<(), SerializeError> {
+        self.close()
+    }
+}
+
+/// Serializes a map key as a string.
+struct KeySerializer<'a, W> {
+    writer: &'a mut W,
+}
+
+impl<W: Write> KeySerializer<'_, W> {
+    /// Writes an integer key as a string.
+    fn int(self, n: impl fmt::Display) -> Result<(), SerializeError> {
+        let mut buf = String::new();
+        write!(buf, "{}", n)?;
+        Ok(write_str(self.writer, &buf)?)
+    }
+}
+
+/// Fails for a map key that is not a string, a character or an integer.
+fn key_must_be_string<T>() -> Result<T, SerializeError> {
+    Err(SerializeError::new(SerializeErrorKind::KeyMustBeString))
+}
+
+impl<W: Write> ser::Serializer for KeySerializer<'_, W> {
+    type Ok = ();
+    type Error = SerializeError;
+
+    type SerializeSeq = ser::Impossible<(), SerializeError>;
+    type SerializeTuple = ser::Impossible<(), SerializeError>;
+    type SerializeTupleStruct = ser::Impossible<(), SerializeError>;
+    type SerializeTupleVariant = ser::Impossible<(), SerializeError>;
+    type SerializeMap = ser::Impossible<(), SerializeError>;
+    type SerializeStruct = ser::Impossible<(), SerializeError>;
+    type SerializeStructVariant = ser::Impossible<(), SerializeError>;
+
+    fn serialize_str(self, v: &str) -> Result<(), SerializeError> {
+        Ok(write_str(self.writer, v)?)
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), SerializeError> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), SerializeError> {
+        self.int(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), SerializeError> {
+        self.int(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), SerializeError> {
+        self.int(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), SerializeError> {
+        self.int(v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), SerializeError> {
+        self.int(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), SerializeError> {
+        self.int(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), SerializeError> {
+        self.int(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), SerializeError> {
+        self.int(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), SerializeError> {
+        self.int(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), SerializeError> {
+        self.int(v)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), SerializeError> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_bool(self, _v: bool) -> Result<(), SerializeError> {
+        key_must_be_string()
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<(), SerializeError> {
+        key_must_be_string()
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<(), SerializeError> {
+        key_must_be_string()
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<(), SerializeError> {
+        key_must_be_string()
+    }
+
+    fn serialize_none(self) -> Result<(), SerializeError> {
+        key_must_be_string()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<(), SerializeError> {
+        key_must_be_string()
+    }
+
+    fn serialize_unit(self) -> Result<(), SerializeError> {
+        key_must_be_string()
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), SerializeError> {
+        key_must_be_string()
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), SerializeError> {
+        key_must_be_string()
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, SerializeError> {
+        key_must_be_string()
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, SerializeError> {
+        key_must_be_string()
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, SerializeError> {
+        key_must_be_string()
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerializeError> {
+        key_must_be_string()
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, SerializeError> {
+        key_must_be_string()
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, SerializeError> {
+        key_must_be_string()
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, SerializeError> {
+        key_must_be_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
+
+    use serde::Serialize;
+
+    use super::to_string;
+    use crate::ser::SerializeErrorKind;
+
+    #[derive(Serialize)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Rect(u32, u32),
+        Polygon { sides: u8, name: Option<String> },
+        Nothing {},
+    }
+
+    #[derive(Serialize)]
+    struct Unit;
+
+    #[derive(Serialize)]
+    struct Empty {}
+
+    #[derive(Serialize)]
+    struct Meters(f32);
+
+    #[test]
+    fn test_scalars() {
+        assert_eq!(to_string(&255u8).unwrap(), "255");
+        assert_eq!(to_string(&-16i64).unwrap(), "-16");
+        assert_eq!(to_string(&-(1i128 << 64)).unwrap(), "-18446744073709551616");
+        assert_eq!(
+            to_string(&(u64::MAX as u128)).unwrap(),
+            "18446744073709551615"
+        );
+        assert_eq!(to_string(&0.1f32).unwrap(), "0.1");
+        assert_eq!(to_string(&1.0f64).unwrap(), "1.0");
+        assert_eq!(to_string(&f64::NAN).unwrap(), "nan");
+        assert_eq!(to_string(&f32::NEG_INFINITY).unwrap(), "-inf");
+        assert_eq!(to_string(&true).unwrap(), "true");
+        assert_eq!(to_string(&'é').unwrap(), "é");
+        assert_eq!(to_string("true").unwrap(), r#""true""#);
+        assert_eq!(to_string(&()).unwrap(), "null");
+        assert_eq!(to_string(&Unit).unwrap(), "null");
+        assert_eq!(to_string(&Meters(1.5)).unwrap(), "1.5");
+        assert_eq!(to_string(&None::<u8>).unwrap(), "null");
+        assert_eq!(to_string(&Some(1u8)).unwrap(), "1");
+
+        #[cfg(feature = "bigint")]
+        {
+            assert_eq!(to_string(&(1i128 << 64)).unwrap(), "18446744073709551616");
+            assert_eq!(
+                to_string(&i128::MIN).unwrap(),
+                "-170141183460469231731687303715884105728"
+            );
+            assert_eq!(
+                to_string(&u128::MAX).unwrap(),
+                "340282366920938463463374607431768211455"
+            );
+        }
+        #[cfg(not(feature = "bigint"))]
+        for e in [
+            to_string(&(1i128 << 64)).unwrap_err(),
+            to_string(&(-(1i128 << 64) - 1)).unwrap_err(),
+            to_string(&u128::MAX).unwrap_err(),
+        ] {
+            assert_eq!(e.kind(), &SerializeErrorKind::IntegerOutOfRange);
+        }
+    }
+
+    #[test]
+    fn test_containers() {
+        assert_eq!(to_string(&[1, 2, 3]).unwrap(), ":1,2,3;");
+        assert_eq!(to_string(&Vec::<u8>::new()).unwrap(), ":;");
+        assert_eq!(to_string(&(1, "a b")).unwrap(), r#":1,"a b";"#);
+        assert_eq!(to_string(&vec![vec![1], vec![]]).unwrap(), "::1;,:;;");
+        assert_eq!(
+            to_string(&BTreeMap::from([(1, true), (20, false)])).unwrap(),
+            r#""1":true,"20":false;"#
+        );
+        assert_eq!(to_string(&BTreeMap::from([('a', ())])).unwrap(), "a:null;");
+        assert_eq!(to_string(&Empty {}).unwrap(), ";");
+        assert_eq!(
+            to_string(&[Some(Empty {}), None]).unwrap_err().kind(),
+            &SerializeErrorKind::EmptyMapAtVectorHead
+        );
+        assert_eq!(to_string(&(1, Empty {})).unwrap(), ":1,;;");
+        assert_eq!(
+            to_string(&[(Empty {},)]).unwrap_err().kind(),
+            &SerializeErrorKind::EmptyMapAtVectorHead
+        );
+        assert_eq!(
+            to_string(&[BTreeMap::from([(1, 1)])]).unwrap(),
+            r#":"1":1;;"#
+        );
+
+        assert_eq!(
+            to_string(&BTreeMap::from([(true, 1)])).unwrap_err().kind(),
+            &SerializeErrorKind::KeyMustBeString
+        );
+    }
+
+    #[test]
+    fn test_enums() {
+        assert_eq!(to_string(&Shape::Empty).unwrap(), "Empty");
+        assert_eq!(to_string(&Shape::Circle(1.5)).unwrap(), "Circle:1.5;");
+        assert_eq!(to_string(&Shape::Rect(2, 3)).unwrap(), "Rect::2,3;;");
+        assert_eq!(
+            to_string(&Shape::Polygon {
+                sides: 6,
+                name: None
+            })
+            .unwrap(),
+            "Polygon:sides:6,name:null;;"
+        );
+        assert_eq!(to_string(&Shape::Nothing {}).unwrap(), "Nothing:;;");
+        assert_eq!(
+            to_string(&[Shape::Nothing {}, Shape::Empty]).unwrap(),
+            ":Nothing:;;,Empty;"
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use alloc::vec::Vec;
+
+use crate::value::{Number, NumberRepr, NumberReprs, Path, PathSegment};
+
+/// A step of the path to the value being written.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Step<'v> {
+    /// A key of a map.
+    Key(&'v str),
+    /// An index of a vector.
+    Index(usize),
+}
+
+/// The spellings of numbers set on a serializer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Spellings {
+    /// The spelling of numbers that have none set for their path.
+    pub(crate) repr: Option<NumberRepr>,
+    /// Spellings of numbers by path, as recorded when parsing.
+    pub(crate) reprs: NumberReprs,
+    /// Spellings of numbers by path, possibly with wildcard indices, in the
+    /// order they were set.
+    reprs_at: Vec<(Path, NumberRepr)>,
+}
+
+impl Spellings {
+    /// Checks if no spelling is set, so all numbers are written the default
+    /// way.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.repr.is_none() && self.reprs.is_empty() && self.reprs_at.is_empty()
+    }
+
+    /// Sets the spelling of numbers at a path, replacing the one set for the
+    /// same path before.
+    pub(crate) fn set_at(&mut self, path: Path, repr: NumberRepr) {
+        self.reprs_at.retain(|(p, _)| *p != path);
+        self.reprs_at.push((path, repr));
+    }
+
+    /// Returns the spelling of the number `n` at the given path.
+    pub(crate) fn get(&self, path: &[Step<'_>], n: &Number) -> Option<&NumberRepr> {
+        let by_path = || {
+            if self.reprs.is_empty() {
+                return None;
+            }
+            let path: Path = path
+                .iter()
+                .map(|step| match step {
+                    Step::Key(key) => PathSegment::from(*key),
+                    Step::Index(index) => PathSegment::Index(*index),
+                })
+                .collect();
+            self.reprs.get(&path, n)
+        };
+
+        self.reprs_at
+            .iter()
+            .rev()
+            .find(|(p, _)| matches(p, path))
+            .map(|(_, repr)| repr)
+            .or_else(by_path)
+            .or(self.repr.as_ref())
+    }
+}
+
+/// Checks if a path, where [`PathSegment::AnyIndex`] matches any index, leads
+/// to the value at the given steps.
+fn matches(pattern: &Path, path: &[Step<'_>]) -> bool {
+    let segments = pattern.segments();
+    segments.len() == path.len()
+        && segments
+            .iter()
+            .zip(path)
+            .all(|(segment, step)| match (segment, step) {
+                (PathSegment::Key(a), Step::Key(b)) => a == b,
+                (PathSegment::Index(a), Step::Index(b)) => a == b,
+                (PathSegment::AnyIndex, Step::Index(_)) => true,
+                _ => false,
+            })
+}
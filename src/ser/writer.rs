@@ -0,0 +1,458 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+
+use super::{
+    SerializeError, SerializeErrorKind, is_empty_map,
+    spelling::{Spellings, Step},
+    write_compact, write_str,
+};
+use crate::value::{NumberRepr, NumberReprs, Path, Value};
+
+/// An open container of a [`Writer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Frame {
+    Vector {
+        /// The number of elements written.
+        len: usize,
+    },
+    Map {
+        /// The number of entries written.
+        len: usize,
+        /// Whether a key has been written and its value not yet.
+        has_key: bool,
+        /// The last key written, kept only if spellings of numbers are set.
+        key: String,
+        /// Whether the map is the first element of a vector.
+        at_vector_head: bool,
+    },
+}
+
+/// A push-based writer emitting compact Twic text incrementally, without
+/// building a [`Value`] first.
+///
+/// Containers are opened with [`begin_map`](Writer::begin_map) and
+/// [`begin_vector`](Writer::begin_vector) and closed with
+/// [`end`](Writer::end). Map entries are written as a [`key`](Writer::key)
+/// followed by a value, which is either a container or a [`Value`] written with
+/// [`value`](Writer::value). The writer inserts the `:`, `,` and `;` between
+/// them, and quotes strings exactly like [`to_string`](super::to_string) does.
+/// Numbers are written with the spellings set like on a
+/// [`CompactFormatter`](super::CompactFormatter), matched against the path of
+/// the keys and indices the writer is at.
+///
+/// # Examples
+///
+/// ```
+/// use twic::{Value, ser::Writer};
+///
+/// let mut w = Writer::new(String::new());
+/// w.begin_map()?;
+/// w.key("name")?;
+/// w.value(&Value::string("twic"))?;
+/// w.key("users")?;
+/// w.begin_vector()?;
+/// for user in ["alice", "bob smith"] {
+///     w.value(&Value::string(user))?;
+/// }
+/// w.end()?;
+/// w.end()?;
+/// assert_eq!(w.finish()?, r#"name:twic,users::alice,"bob smith";;"#);
+/// # Ok::<(), twic::SerializeError>(())
+/// ```
+pub struct Writer<W> {
+    writer: W,
+    stack: Vec<Frame>,
+    /// Whether the top-level value has been written completely.
+    done: bool,
+    numbers: Spellings,
+}
+
+impl<W: Write> Writer<W> {
+    /// Creates a writer writing to the given writer.
+    pub fn new(writer: W) -> Self {
+        Writer {
+            writer,
+            stack: Vec::new(),
+            done: false,
+            numbers: Spellings::default(),
+        }
+    }
+
+    /// Sets the spelling of numbers that have none set for their path.
+    pub fn number_repr(mut self, repr: NumberRepr) -> Self {
+        self.numbers.repr = Some(repr);
+        self
+    }
+
+    /// Sets the spelling of numbers at the given path, where `[*]` matches
+    /// every element of a vector. See
+    /// [`PrettyFormatter::number_repr_at`](super::PrettyFormatter::number_repr_at)
+    /// for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::{
+    ///     Value,
+    ///     ser::Writer,
+    ///     value::{NumberRepr, Radix},
+    /// };
+    ///
+    /// let hex = NumberRepr {
+    ///     radix: Radix::Hexadecimal,
+    ///     ..NumberRepr::default()
+    /// };
+    /// let mut w = Writer::new(String::new()).number_repr_at("masks[*]".parse()?, hex);
+    /// w.begin_map()?;
+    /// w.key("masks")?;
+    /// w.value(&Value::vector_from([255, 4096]))?;
+    /// w.key("count")?;
+    /// w.value(&Value::from(2))?;
+    /// w.end()?;
+    /// assert_eq!(w.finish()?, "masks::0xff,0x1000;,count:2;");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn number_repr_at(mut self, path: Path, repr: NumberRepr) -> Self {
+        self.numbers.set_at(path, repr);
+        self
+    }
+
+    /// Sets the spellings of numbers by their exact path, such as the
+    /// spellings returned by
+    /// [`from_str_with_reprs`](crate::de::from_str_with_reprs), replacing
+    /// those set before.
+    pub fn number_reprs(mut self, reprs: NumberReprs) -> Self {
+        self.numbers.reprs = reprs;
+        self
+    }
+
+    /// Returns the underlying writer, whether or not the top-level value has
+    /// been written completely.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Returns the underlying writer after checking that the top-level value
+    /// has been written completely.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`SerializeErrorKind::Unfinished`] if no value
+    /// has been written or a container is still open.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::ser::{SerializeErrorKind, Writer};
+    ///
+    /// let mut w = Writer::new(String::new());
+    /// w.begin_vector().unwrap();
+    /// assert_eq!(w.finish().unwrap_err().kind(), &SerializeErrorKind::Unfinished);
+    /// ```
+    pub fn finish(self) -> Result<W, SerializeError> {
+        if !self.done {
+            return Err(SerializeError::new(SerializeErrorKind::Unfinished));
+        }
+        Ok(self.writer)
+    }
+
+    /// Checks if a value may be written now and writes the `,` before it if
+    /// needed. Returns whether the value is the first element of a vector.
+    fn before_value(&mut self) -> Result<bool, SerializeError> {
+        match self.stack.last() {
+            None if self.done => Err(SerializeError::new(SerializeErrorKind::UnexpectedValue)),
+            None => Ok(false),
+            Some(Frame::Vector { len }) => {
+                if *len > 0 {
+                    self.writer.write_char(',')?;
+                }
+                Ok(*len == 0)
+            }
+            Some(Frame::Map { has_key: false, .. }) => {
+                Err(SerializeError::new(SerializeErrorKind::UnexpectedValue))
+            }
+            Some(Frame::Map { .. }) => Ok(false),
+        }
+    }
+
+    /// Records that a value has been written completely.
+    fn after_value(&mut self) {
+        match self.stack.last_mut() {
+            None => self.done = true,
+            Some(Frame::Vector { len }) => *len += 1,
+            Some(Frame::Map { len, has_key, .. }) => {
+                *len += 1;
+                *has_key = false;
+            }
+        }
+    }
+
+    /// Starts a map. Its entries are written with [`key`](Writer::key) and a
+    /// value each, and it is closed with [`end`](Writer::end).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a value is not allowed here, or if the underlying
+    /// writer fails.
+    pub fn begin_map(&mut self) -> Result<(), SerializeError> {
+        let at_vector_head = self.before_value()?;
+        self.stack.push(Frame::Map {
+            len: 0,
+            has_key: false,
+            key: String::new(),
+            at_vector_head,
+        });
+        Ok(())
+    }
+
+    /// Starts a vector. Its elements are written as values, and it is closed
+    /// with [`end`](Writer::end).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a value is not allowed here, or if the underlying
+    /// writer fails.
+    pub fn begin_vector(&mut self) -> Result<(), SerializeError> {
+        self.before_value()?;
+        self.writer.write_char(':')?;
+        self.stack.push(Frame::Vector { len: 0 });
+        Ok(())
+    }
+
+    /// Writes a map key, which must be followed by its value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`SerializeErrorKind::UnexpectedKey`] if the
+    /// innermost open container is not a map, or the previous key has no value
+    /// yet, or an error if the underlying writer fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::ser::{SerializeErrorKind, Writer};
+    ///
+    /// let mut w = Writer::new(String::new());
+    /// w.begin_vector().unwrap();
+    /// assert_eq!(w.key("a").unwrap_err().kind(), &SerializeErrorKind::UnexpectedKey);
+    /// ```
+    pub fn key(&mut self, key: &str) -> Result<(), SerializeError> {
+        let Some(Frame::Map {
+            len,
+            has_key: has_key @ false,
+            key: last_key,
+            ..
+        }) = self.stack.last_mut()
+        else {
+            return Err(SerializeError::new(SerializeErrorKind::UnexpectedKey));
+        };
+
+        *has_key = true;
+        if !self.numbers.is_empty() {
+            last_key.clear();
+            last_key.push_str(key);
+        }
+        if *len > 0 {
+            self.writer.write_char(',')?;
+        }
+        write_str(&mut self.writer, key)?;
+        Ok(self.writer.write_char(':')?)
+    }
+
+    /// Writes a complete value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`SerializeErrorKind::UnexpectedValue`] if the
+    /// innermost open container is a map with no key waiting for its value, or
+    /// the top-level value has been written already. Also returns an error if
+    /// the value has no Twic representation, or if the underlying writer fails.
+    pub fn value(&mut self, value: &Value) -> Result<(), SerializeError> {
+        if self.before_value()? && is_empty_map(value) {
+            return Err(SerializeError::new(
+                SerializeErrorKind::EmptyMapAtVectorHead,
+            ));
+        }
+        // the path is needed only to look up spellings
+        let mut path = Vec::new();
+        if !self.numbers.is_empty() {
+            path.extend(self.stack.iter().map(|frame| match frame {
+                Frame::Vector { len } => Step::Index(*len),
+                Frame::Map { key, .. } => Step::Key(key),
+            }));
+        }
+        write_compact(&mut self.writer, value, &self.numbers, &mut path)?;
+        self.after_value();
+        Ok(())
+    }
+
+    /// Closes the innermost open map or vector.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`SerializeErrorKind::UnexpectedEnd`] if no
+    /// container is open or a key has no value yet, and of kind
+    /// [`SerializeErrorKind::EmptyMapAtVectorHead`] if an empty map is closed
+    /// as the first element of a vector. Also returns an error if the
+    /// underlying writer fails.
+    pub fn end(&mut self) -> Result<(), SerializeError> {
+        match self.stack.last() {
+            None | Some(Frame::Map { has_key: true, .. }) => {
+                return Err(SerializeError::new(SerializeErrorKind::UnexpectedEnd));
+            }
+            Some(Frame::Map {
+                len: 0,
+                at_vector_head: true,
+                ..
+            }) => {
+                return Err(SerializeError::new(
+                    SerializeErrorKind::EmptyMapAtVectorHead,
+                ));
+            }
+            Some(_) => {}
+        }
+
+        self.stack.pop();
+        self.writer.write_char(';')?;
+        self.after_value();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::String;
+
+    use super::Writer;
+    use crate::{
+        ser::SerializeErrorKind,
+        value::{Number, NumberRepr, Radix, Value},
+    };
+
+    #[test]
+    fn test_nesting() {
+        let mut w = Writer::new(String::new());
+        w.begin_vector().unwrap();
+        w.begin_map().unwrap();
+        w.key("a b").unwrap();
+        w.begin_map().unwrap();
+        w.end().unwrap();
+        w.key("c").unwrap();
+        w.value(&Value::map_from([("x", Number::NaN)])).unwrap();
+        w.end().unwrap();
+        w.begin_vector().unwrap();
+        w.end().unwrap();
+        w.begin_map().unwrap();
+        w.end().unwrap();
+        w.value(&Value::string("null")).unwrap();
+        w.end().unwrap();
+
+        let s = w.finish().unwrap();
+        assert_eq!(s, r#":"a b":;,c:x:nan;;,:;,;,"null";"#);
+        assert_eq!(
+            crate::de::parse_value(&s).unwrap(),
+            Value::vector([
+                Value::map_from([
+                    ("a b", Value::map_empty()),
+                    ("c", Value::map_from([("x", Number::NaN)])),
+                ]),
+                Value::vector_empty(),
+                Value::map_empty(),
+                Value::string("null"),
+            ])
+        );
+
+        let mut w = Writer::new(String::new());
+        w.value(&Value::Null).unwrap();
+        assert_eq!(w.finish().unwrap(), "null");
+    }
+
+    #[test]
+    fn test_number_reprs() {
+        let s = ":0x1F,a:+2,b::007;;;";
+        let (v, reprs) = crate::de::from_str_with_reprs(s).unwrap();
+        let hex = NumberRepr {
+            radix: Radix::Hexadecimal,
+            ..NumberRepr::default()
+        };
+
+        // paths follow the containers opened with the writer and those inside
+        // the values written
+        let mut w = Writer::new(String::new())
+            .number_reprs(reprs)
+            .number_repr_at("[2][*]".parse().unwrap(), hex);
+        w.begin_vector().unwrap();
+        w.value(&v[0]).unwrap();
+        w.begin_map().unwrap();
+        w.key("a").unwrap();
+        w.value(&v[1]["a"]).unwrap();
+        w.key("b").unwrap();
+        w.value(&v[1]["b"]).unwrap();
+        w.end().unwrap();
+        w.value(&Value::vector_from([10, 11])).unwrap();
+        w.end().unwrap();
+        assert_eq!(w.finish().unwrap(), ":0x1F,a:+2,b::007;;,:0xa,0xb;;");
+    }
+
+    #[test]
+    fn test_errors() {
+        let kind = |f: fn(&mut Writer<String>) -> Result<(), crate::SerializeError>| {
+            f(&mut Writer::new(String::new()))
+                .unwrap_err()
+                .kind()
+                .clone()
+        };
+
+        assert_eq!(kind(|w| w.end()), SerializeErrorKind::UnexpectedEnd);
+        assert_eq!(kind(|w| w.key("a")), SerializeErrorKind::UnexpectedKey);
+        assert_eq!(
+            kind(|w| {
+                w.value(&Value::Null)?;
+                w.value(&Value::Null)
+            }),
+            SerializeErrorKind::UnexpectedValue
+        );
+        assert_eq!(
+            kind(|w| {
+                w.begin_map()?;
+                w.value(&Value::Null)
+            }),
+            SerializeErrorKind::UnexpectedValue
+        );
+        assert_eq!(
+            kind(|w| {
+                w.begin_map()?;
+                w.key("a")?;
+                w.key("b")
+            }),
+            SerializeErrorKind::UnexpectedKey
+        );
+        assert_eq!(
+            kind(|w| {
+                w.begin_map()?;
+                w.key("a")?;
+                w.end()
+            }),
+            SerializeErrorKind::UnexpectedEnd
+        );
+        assert_eq!(
+            kind(|w| {
+                w.begin_vector()?;
+                w.begin_map()?;
+                w.end()
+            }),
+            SerializeErrorKind::EmptyMapAtVectorHead
+        );
+        assert_eq!(
+            kind(|w| {
+                w.begin_vector()?;
+                w.value(&Value::map_empty())
+            }),
+            SerializeErrorKind::EmptyMapAtVectorHead
+        );
+        assert_eq!(
+            Writer::new(String::new()).finish().unwrap_err().kind(),
+            &SerializeErrorKind::Unfinished
+        );
+    }
+}
@@ -0,0 +1,15 @@
+//! Reading and writing Rust values as Twic text with serde.
+//!
+//! [`from_str`] and [`to_string`] work with any type implementing serde's
+//! `Deserialize` and `Serialize` traits, mapping Rust values to Twic values as
+//! described in the [crate documentation](crate#serde-support). The
+//! [`Value`](crate::Value) versions, [`twic::from_str`](crate::from_str) and
+//! [`twic::to_string`](crate::to_string), are available whether or not this
+//! feature is enabled.
+
+#[doc(inline)]
+pub use crate::de::deserializer::from_str;
+#[doc(inline)]
+pub use crate::ser::serializer::{to_string, to_writer};
+#[doc(inline)]
+pub use crate::value::{from_value, to_value};
@@ -1,21 +1,51 @@
 //! The Twic [`Value`] enum, representing all possible Twic values.
 
 use alloc::{string::String, vec::Vec};
+use core::hash::{Hash, Hasher};
 
+mod cmp;
 mod convert;
+#[cfg(feature = "serde")]
+mod de;
 mod index;
 mod map;
 mod number;
 mod partial_eq;
+mod path;
+#[cfg(feature = "serde")]
+mod ser;
+#[cfg(test)]
+mod test_util;
 
 #[doc(inline)]
 pub use index::{IndexInto, IndexMutResult, IndexResult, ValueIndexError};
 #[doc(inline)]
 pub use map::Map;
 #[doc(inline)]
-pub use number::Number;
+#[cfg(feature = "bigint")]
+pub use number::BigInt;
+#[doc(inline)]
+#[cfg(feature = "decimal")]
+pub use number::Decimal;
+pub use number::{
+    Notation, Number, NumberConversionError, NumberConversionErrorKind, NumberRepr, NumberReprs,
+    Radix,
+};
+
+#[doc(inline)]
+pub use path::{Path, PathParseError, PathParseErrorKind, PathSegment};
+
+#[cfg(feature = "serde")]
+#[doc(inline)]
+pub use de::from_value;
+#[cfg(feature = "serde")]
+#[doc(inline)]
+pub use ser::to_value;
 
 /// Represents a Twic value.
+///
+/// Values are totally ordered, see [`Value::cmp`], so they can be used as keys
+/// of hash maps and B-tree maps.
 #[derive(Debug, Clone, PartialEq, Default)]
 pub enum Value {
     /// Represents a Twic null value.
@@ -27,6 +57,13 @@ pub enum Value {
     Number(Number),
     /// Represents a Twic string value.
     String(String),
+    /// Represents a Twic string value that is not valid UTF-8, which can be
+    /// written with `\xXX` escapes in quoted strings.
+    ///
+    /// Parsing produces this variant only for strings that are not valid
+    /// UTF-8, and [`Value::bytes`] likewise creates a [`Value::String`] for
+    /// valid UTF-8, so that values round-trip through Twic text unchanged.
+    Bytes(Vec<u8>),
     /// Represents a Twic vector value.
     Vector(Vec<Value>),
     /// Represents a Twic map value.
@@ -143,7 +180,7 @@ impl Value {
     /// ```
     pub fn as_number(&self) -> Option<Number> {
         if let Value::Number(n) = self {
-            Some(*n)
+            Some(n.clone())
         } else {
             None
         }
@@ -152,6 +189,10 @@ impl Value {
     /// Returns a mutable reference to the number value if the value is a
     /// number, `None` otherwise.
     ///
+    /// A spelling recorded for the number in [`NumberReprs`] by
+    /// [`from_str_with_reprs`](crate::de::from_str_with_reprs) no longer
+    /// applies once the number is changed.
+    ///
     /// # Examples
     ///
     /// ```
@@ -243,6 +284,43 @@ impl Value {
         }
     }
 
+    /// Checks if the value is a string that is not valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Value;
+    ///
+    /// assert!(Value::bytes(b"\xff").is_bytes());
+    /// assert!(!Value::bytes(b"hello").is_bytes());
+    /// ```
+    pub fn is_bytes(&self) -> bool {
+        matches!(self, Value::Bytes(_))
+    }
+
+    /// Returns the bytes of the string if the value is a string, valid UTF-8
+    /// or not, `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Value;
+    ///
+    /// let v: Value = r#""a\xff""#.parse().unwrap();
+    /// assert_eq!(v.as_bytes(), Some(&b"a\xff"[..]));
+    /// assert_eq!(v.as_str(), None);
+    ///
+    /// let v = Value::string("hello");
+    /// assert_eq!(v.as_bytes(), Some(&b"hello"[..]));
+    /// ```
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::String(s) => Some(s.as_bytes()),
+            Value::Bytes(b) => Some(b),
+            _ => None,
+        }
+    }
+
     /// Checks if the value is a vector.
     ///
     /// # Examples
@@ -387,6 +465,24 @@ impl Value {
         Value::String(s.into())
     }
 
+    /// Creates a string value from bytes, which is a [`Value::String`] if the
+    /// bytes are valid UTF-8, and a [`Value::Bytes`] otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Value;
+    ///
+    /// assert_eq!(Value::bytes(b"hello"), Value::string("hello"));
+    /// assert_eq!(Value::bytes(b"\xc3"), Value::Bytes(vec![0xc3]));
+    /// ```
+    pub fn bytes<B: Into<Vec<u8>>>(b: B) -> Self {
+        match String::from_utf8(b.into()) {
+            Ok(s) => Value::String(s),
+            Err(e) => Value::Bytes(e.into_bytes()),
+        }
+    }
+
     /// Creates a vector value.
     ///
     /// # Examples
@@ -576,6 +672,142 @@ impl Value {
     pub fn get_or_insert<I: IndexInto>(&mut self, index: I) -> Option<&mut Value> {
         index.index_into_or_insert(self).ok()
     }
+
+    /// Looks up a nested value by a [`Path`] given as text, such as
+    /// `profile.users[1]`. Returns `None` if the path is not valid or does not
+    /// lead to a value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::Value;
+    ///
+    /// let v: Value = "profile:users::alice,bob;;;".parse().unwrap();
+    /// assert_eq!(v.pointer("profile.users[1]"), Some(&Value::from("bob")));
+    /// assert_eq!(v.pointer("profile.users[2]"), None);
+    /// assert_eq!(v.pointer("profile..users"), None);
+    /// ```
+    pub fn pointer(&self, path: &str) -> Option<&Value> {
+        self.get(Path::parse(path).ok()?)
+    }
+
+    /// Looks up a nested value mutably by a [`Path`] given as text. Returns
+    /// `None` if the path is not valid or does not lead to a value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::Value;
+    ///
+    /// let mut v: Value = "profile:users::alice,bob;;;".parse().unwrap();
+    /// *v.pointer_mut("profile.users[0]").unwrap() = Value::from("carol");
+    /// assert_eq!(twic::to_string(&v).unwrap(), "profile:users::carol,bob;;;");
+    /// ```
+    pub fn pointer_mut(&mut self, path: &str) -> Option<&mut Value> {
+        self.get_mut(Path::parse(path).ok()?)
+    }
+}
+
+/// Semantic comparison and normalization for [`Value`].
+impl Value {
+    /// Checks if two values are equal, comparing numbers by their mathematical
+    /// values with [`Number::numeric_eq`].
+    ///
+    /// Unlike `==`, integers equal floats of the same value, including inside
+    /// vectors and maps. Everything else is compared as by `==`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::Value;
+    ///
+    /// let a: Value = "port:8080,ratio:1;".parse().unwrap();
+    /// let b: Value = "port:8080.0,ratio:1e0;".parse().unwrap();
+    /// assert_ne!(a, b);
+    /// assert!(a.semantic_eq(&b));
+    /// ```
+    pub fn semantic_eq(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Number(a), Value::Number(b)) => a.numeric_eq(b),
+            (Value::Vector(a), Value::Vector(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.semantic_eq(b))
+            }
+            (Value::Map(a), Value::Map(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .zip(b)
+                        .all(|((ka, va), (kb, vb))| ka == kb && va.semantic_eq(vb))
+            }
+            _ => self == other,
+        }
+    }
+
+    /// Feeds the value into the given [`Hasher`], consistently with
+    /// [`semantic_eq`](Value::semantic_eq): semantically equal values hash
+    /// identically.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::hash::{BuildHasher, Hasher, RandomState};
+    /// use twic::Value;
+    ///
+    /// let s = RandomState::new();
+    /// let hash = |v: &Value| {
+    ///     let mut h = s.build_hasher();
+    ///     v.semantic_hash(&mut h);
+    ///     h.finish()
+    /// };
+    /// let a: Value = "port:8080;".parse().unwrap();
+    /// let b: Value = "port:8080.0;".parse().unwrap();
+    /// assert_eq!(hash(&a), hash(&b));
+    /// ```
+    pub fn semantic_hash<H: Hasher>(&self, state: &mut H) {
+        core::mem::discriminant(self).hash(state);
+        match self {
+            Value::Null => {}
+            Value::Boolean(b) => b.hash(state),
+            Value::Number(n) => n.numeric_hash(state),
+            Value::String(s) => s.hash(state),
+            Value::Bytes(b) => b.hash(state),
+            Value::Vector(v) => {
+                v.len().hash(state);
+                for value in v {
+                    value.semantic_hash(state);
+                }
+            }
+            Value::Map(m) => {
+                m.len().hash(state);
+                for (key, value) in m {
+                    key.hash(state);
+                    value.semantic_hash(state);
+                }
+            }
+        }
+    }
+
+    /// Replaces every number in the value, including those nested in vectors
+    /// and maps, with its canonical form given by [`Number::normalize`].
+    ///
+    /// After normalization, semantically equal values are also equal by `==`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::Value;
+    ///
+    /// let mut v: Value = "port:8080.0,ratios::0.5,-0.0;;".parse().unwrap();
+    /// v.normalize_numbers();
+    /// assert_eq!(v, "port:8080,ratios::0.5,0;;".parse::<Value>().unwrap());
+    /// ```
+    pub fn normalize_numbers(&mut self) {
+        match self {
+            Value::Number(n) => *n = n.normalize(),
+            Value::Vector(v) => v.iter_mut().for_each(Value::normalize_numbers),
+            Value::Map(m) => m.values_mut().for_each(Value::normalize_numbers),
+            _ => {}
+        }
+    }
 }
 
 impl Value {
@@ -595,6 +827,7 @@ impl Value {
             Value::Boolean(_) => "boolean",
             Value::Number(_) => "number",
             Value::String(_) => "string",
+            Value::Bytes(_) => "bytes",
             Value::Vector(_) => "vector",
             Value::Map(_) => "map",
         }
@@ -0,0 +1,166 @@
+use core::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+
+use super::Value;
+
+impl Value {
+    /// Returns the rank of the type of the value in the order of [`Ord`].
+    const fn type_rank(&self) -> u8 {
+        match self {
+            Value::Null => 0,
+            Value::Boolean(_) => 1,
+            Value::Number(_) => 2,
+            Value::String(_) => 3,
+            Value::Bytes(_) => 4,
+            Value::Vector(_) => 5,
+            Value::Map(_) => 6,
+        }
+    }
+}
+
+impl Eq for Value {}
+
+impl Hash for Value {
+    /// Hashes the value consistently with `==`. Use [`Value::semantic_hash`]
+    /// to hash integers and floats of the same value identically.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.type_rank().hash(state);
+        match self {
+            Value::Null => {}
+            Value::Boolean(b) => b.hash(state),
+            Value::Number(n) => n.hash(state),
+            Value::String(s) => s.hash(state),
+            Value::Bytes(b) => b.hash(state),
+            Value::Vector(v) => v.hash(state),
+            Value::Map(m) => m.hash(state),
+        }
+    }
+}
+
+impl Ord for Value {
+    /// Compares two values in a total order, consistent with `==`.
+    ///
+    /// Values of different types are ordered by type: null, booleans, numbers,
+    /// strings, byte strings, vectors, then maps. Values of the same type are
+    /// ordered as follows:
+    ///
+    /// - `false` is less than `true`.
+    /// - Numbers are ordered by [`Number::total_cmp`](super::Number::total_cmp).
+    /// - Strings and byte strings are ordered by their bytes.
+    /// - Vectors are ordered lexicographically by their elements.
+    /// - Maps are ordered lexicographically by their entries in key order,
+    ///   comparing keys before values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeSet;
+    /// use twic::Value;
+    ///
+    /// let set: BTreeSet<Value> = [
+    ///     "b:1;", ":1,2;", "x", "nan", "1.0", "-inf", "1", "true", "null", ":1;", "a:2;",
+    /// ]
+    /// .into_iter()
+    /// .map(|s| s.parse().unwrap())
+    /// .collect();
+    /// let sorted: Vec<String> = set.iter().map(|v| twic::to_string(v).unwrap()).collect();
+    /// assert_eq!(
+    ///     sorted,
+    ///     ["null", "true", "-inf", "1", "1.0", "nan", "x", ":1;", ":1,2;", "a:2;", "b:1;"],
+    /// );
+    /// ```
+    fn cmp(&self, other: &Value) -> Ordering {
+        match (self, other) {
+            (Value::Null, Value::Null) => Ordering::Equal,
+            (Value::Boolean(a), Value::Boolean(b)) => a.cmp(b),
+            (Value::Number(a), Value::Number(b)) => a.total_cmp(b),
+            (Value::String(a), Value::String(b)) => a.cmp(b),
+            (Value::Bytes(a), Value::Bytes(b)) => a.cmp(b),
+            (Value::Vector(a), Value::Vector(b)) => a.cmp(b),
+            (Value::Map(a), Value::Map(b)) => a.cmp(b),
+            _ => self.type_rank().cmp(&other.type_rank()),
+        }
+    }
+}
+
+impl PartialOrd for Value {
+    /// Compares two values in the total order of [`Ord`]. Unlike for
+    /// [`Number`](super::Number)s, `nan` is comparable with other numbers here.
+    fn partial_cmp(&self, other: &Value) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::{collections::BTreeSet, vec::Vec};
+    use core::{cmp::Ordering, hash::Hash};
+
+    use crate::{
+        de::parse_value,
+        value::{Number, Value, test_util::hashed},
+    };
+
+    fn hash(v: &Value) -> Vec<u8> {
+        hashed(|h| v.hash(h))
+    }
+
+    #[test]
+    fn test_total_order() {
+        let ordered = [
+            Value::Null,
+            Value::from(false),
+            Value::from(true),
+            Value::number(Number::Inf { negative: true }),
+            Value::from(-1),
+            Value::from(0),
+            Value::from(0.0),
+            Value::from(u64::MAX),
+            Value::number(Number::NaN),
+            Value::from(""),
+            Value::from("a"),
+            Value::from("b"),
+            Value::Bytes(b"\xff".to_vec()),
+            Value::vector_empty(),
+            Value::vector([Value::Null]),
+            Value::vector([Value::Null, Value::Null]),
+            Value::vector([Value::from(1)]),
+            Value::map_empty(),
+            Value::map_from([("a", 2)]),
+            Value::map_from([("a", 2), ("b", 0)]),
+            Value::map_from([("a", 3)]),
+            Value::map_from([("b", 1)]),
+        ];
+
+        for (i, a) in ordered.iter().enumerate() {
+            for (j, b) in ordered.iter().enumerate() {
+                assert_eq!(a.cmp(b), i.cmp(&j), "{:?} {:?}", a, b);
+                assert_eq!(a.partial_cmp(b), Some(i.cmp(&j)));
+                assert_eq!(a == b, i == j);
+            }
+        }
+    }
+
+    #[test]
+    fn test_consistency() {
+        // equal values compare and hash equally, whatever their spelling
+        for (a, b) in [
+            ("x:0x1F,y:nan;", "x:31,y:nan;"),
+            (":0.0;", ":-0.0;"),
+            ("+5", "05"),
+        ] {
+            let (a, b) = (parse_value(a).unwrap(), parse_value(b).unwrap());
+            assert_eq!(a, b);
+            assert_eq!(a.cmp(&b), Ordering::Equal);
+            assert_eq!(hash(&a), hash(&b));
+        }
+
+        let set: BTreeSet<Value> = ["a:1;", "a:0x1;", "a:1.0;", "a:1;"]
+            .into_iter()
+            .map(|s| parse_value(s).unwrap())
+            .collect();
+        assert_eq!(set.len(), 2);
+    }
+}
@@ -4,7 +4,7 @@ use alloc::{
     vec::Vec,
 };
 
-use super::{Number, Value};
+use super::{Number, NumberConversionError, Value};
 
 impl From<()> for Value {
     /// Converts a unit type to a Twic null value.
@@ -50,7 +50,45 @@ impl<T: Into<Number>> From<T> for Value {
     /// assert!(n.is_number());
     /// ```
     fn from(value: T) -> Self {
-        Value::Number(value.into())
+        Value::number(value)
+    }
+}
+
+impl TryFrom<i128> for Value {
+    type Error = NumberConversionError;
+
+    /// Converts an i128 to a Twic number value, failing if it is out of the
+    /// range of integer [`Number`]s. With the `bigint` feature, it never fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Value;
+    ///
+    /// assert_eq!(Value::try_from(-1i128), Ok(Value::from(-1)));
+    /// assert_eq!(Value::try_from(i128::MIN).is_err(), !cfg!(feature = "bigint"));
+    /// ```
+    fn try_from(value: i128) -> Result<Self, Self::Error> {
+        Number::try_from(value).map(Value::number)
+    }
+}
+
+impl TryFrom<u128> for Value {
+    type Error = NumberConversionError;
+
+    /// Converts a u128 to a Twic number value, failing if it is out of the
+    /// range of integer [`Number`]s. With the `bigint` feature, it never fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Value;
+    ///
+    /// assert_eq!(Value::try_from(42u128), Ok(Value::from(42)));
+    /// assert_eq!(Value::try_from(u128::MAX).is_err(), !cfg!(feature = "bigint"));
+    /// ```
+    fn try_from(value: u128) -> Result<Self, Self::Error> {
+        Number::try_from(value).map(Value::number)
     }
 }
 
@@ -0,0 +1,523 @@
+use alloc::{borrow::Cow, collections::btree_map, string::String, vec::Vec};
+use core::fmt;
+
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+    value::SeqDeserializer,
+};
+
+use super::{Map, Number, Value};
+#[cfg(any(feature = "bigint", feature = "decimal"))]
+use crate::de::parse_number_exact;
+use crate::de::{KeyDeserializer, ParseError, visit_f32, visit_f64, visit_number};
+
+/// Visits any number, keeping integers down to `-2^64` as integers.
+struct NumberVisitor;
+
+/// Parses a number passed as a map with the single key
+/// [`Number::SERDE_TOKEN`], after the key has been read.
+#[cfg(any(feature = "bigint", feature = "decimal"))]
+fn visit_token<'de, A: MapAccess<'de>>(mut map: A) -> Result<Number, A::Error> {
+    let literal: String = map.next_value()?;
+    // decimals are passed exactly, unlike in Twic text
+    parse_number_exact(&literal)
+        .ok_or_else(|| de::Error::invalid_value(de::Unexpected::Str(&literal), &NumberVisitor))
+}
+
+impl<'de> Visitor<'de> for NumberVisitor {
+    type Value = Number;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a number")
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Number, E> {
+        Ok(v.into())
+    }
+
+    fn visit_i128<E: de::Error>(self, v: i128) -> Result<Number, E> {
+        Number::try_from(v).map_err(|_| E::invalid_value(de::Unexpected::Other("integer"), &self))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Number, E> {
+        Ok(v.into())
+    }
+
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<Number, E> {
+        Number::try_from(v).map_err(|_| E::invalid_value(de::Unexpected::Other("integer"), &self))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Number, E> {
+        Ok(v.into())
+    }
+
+    #[cfg(any(feature = "bigint", feature = "decimal"))]
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Number, A::Error> {
+        match map.next_key::<String>()? {
+            Some(key) if key == Number::SERDE_TOKEN => visit_token(map),
+            _ => Err(de::Error::invalid_type(de::Unexpected::Map, &self)),
+        }
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Number {
+    /// Deserializes a number from any integer in the range of `Number`, or
+    /// any float. `NaN` and infinities become [`Number::NaN`] and
+    /// [`Number::Inf`].
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(NumberVisitor)
+    }
+}
+
+/// Visits any value.
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("any Twic value")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_some<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        de::Deserialize::deserialize(deserializer)
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Value, E> {
+        Ok(Value::Boolean(v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Value, E> {
+        NumberVisitor.visit_i64(v).map(Value::number)
+    }
+
+    fn visit_i128<E: de::Error>(self, v: i128) -> Result<Value, E> {
+        NumberVisitor.visit_i128(v).map(Value::number)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Value, E> {
+        NumberVisitor.visit_u64(v).map(Value::number)
+    }
+
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<Value, E> {
+        NumberVisitor.visit_u128(v).map(Value::number)
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Value, E> {
+        NumberVisitor.visit_f64(v).map(Value::number)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Value, E> {
+        Ok(Value::String(v.into()))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Value, E> {
+        Ok(Value::String(v))
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Value, E> {
+        Ok(Value::bytes(v))
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Value, E> {
+        Ok(Value::bytes(v))
+    }
+
+    fn visit_newtype_struct<D: de::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Value, D::Error> {
+        de::Deserialize::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut vector = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(value) = seq.next_element()? {
+            vector.push(value);
+        }
+        Ok(Value::Vector(vector))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let mut m = Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            #[cfg(any(feature = "bigint", feature = "decimal"))]
+            if m.is_empty() && key == Number::SERDE_TOKEN {
+                return visit_token(map).map(Value::number);
+            }
+            if m.contains_key(&key) {
+                return Err(de::Error::custom(alloc::format!(
+                    "duplicate map key {:?}",
+                    key
+                )));
+            }
+            let value = map.next_value()?;
+            m.insert(key, value);
+        }
+        Ok(Value::Map(m))
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Value {
+    /// Deserializes any value. Unit and `None` become [`Value::Null`], and
+    /// byte strings become vectors of numbers. Duplicate map keys are
+    /// rejected.
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+/// Converts a [`Value`] into a Rust value.
+///
+/// The value is mapped in the same way as by [`from_str`](crate::serde::from_str).
+///
+/// # Errors
+///
+/// Returns an error if the value does not match the structure expected by `T`.
+/// The error does not carry a meaningful [offset](ParseError::offset).
+///
+/// # Examples
+///
+/// ```
+/// use serde::Deserialize;
+/// use twic::Value;
+///
+/// #[derive(Deserialize, Debug, PartialEq)]
+/// enum Shape {
+///     Circle(f64),
+///     Rect { w: u32, h: u32 },
+/// }
+///
+/// let v = Value::map_from([("Rect", Value::map_from([("w", 2), ("h", 3)]))]);
+/// assert_eq!(twic::from_value::<Shape>(v).unwrap(), Shape::Rect { w: 2, h: 3 });
+/// assert!(twic::from_value::<Shape>(Value::from("Circle")).is_err());
+/// ```
+pub fn from_value<T: DeserializeOwned>(value: Value) -> Result<T, ParseError> {
+    T::deserialize(value)
+}
+
+impl IntoDeserializer<'_, ParseError> for Value {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Value {
+    type Error = ParseError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ParseError> {
+        match self {
+            Value::Null => visitor.visit_unit(),
+            Value::Boolean(b) => visitor.visit_bool(b),
+            Value::Number(n) => visit_number(n, visitor),
+            Value::String(s) => visitor.visit_string(s),
+            Value::Bytes(b) => visitor.visit_byte_buf(b),
+            Value::Vector(v) => {
+                let mut seq = SeqDeserializer::new(v.into_iter());
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            Value::Map(m) => {
+                let len = m.len();
+                let mut map = MapDeserializer {
+                    iter: m.into_iter(),
+                    value: None,
+                };
+                let value = visitor.visit_map(&mut map)?;
+                match map.iter.len() {
+                    0 => Ok(value),
+                    remaining => Err(de::Error::invalid_length(
+                        len - remaining,
+                        &"fewer entries in map",
+                    )),
+                }
+            }
+        }
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ParseError> {
+        match self {
+            Value::Number(n) => visit_f32(n, visitor),
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ParseError> {
+        match self {
+            Value::Number(n) => visit_f64(n, visitor),
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ParseError> {
+        match self {
+            Value::Null => visitor.visit_none(),
+            value => visitor.visit_some(value),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, ParseError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ParseError> {
+        match self {
+            Value::String(s) => visitor.visit_enum(s.into_deserializer()),
+            Value::Map(m) if m.len() == 1 => {
+                let (variant, value) = m.into_iter().next().unwrap();
+                visitor.visit_enum(EnumDeserializer { variant, value })
+            }
+            _ => Err(de::Error::custom("expected a string or a map for an enum")),
+        }
+    }
+
+    fn is_human_readable(&self) -> bool {
+        true
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+/// Accesses the entries of a [`Value::Map`].
+struct MapDeserializer {
+    iter: btree_map::IntoIter<String, Value>,
+    value: Option<Value>,
+}
+
+impl<'de> MapAccess<'de> for MapDeserializer {
+    type Error = ParseError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, ParseError> {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(KeyDeserializer::new(Cow::Owned(key)))
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, ParseError> {
+        let value = self
+            .value
+            .take()
+            .expect("`next_value_seed` is called after `next_key_seed`");
+        seed.deserialize(value)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+/// Accesses the variant of an enum written as a single-key map.
+struct EnumDeserializer {
+    variant: String,
+    value: Value,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumDeserializer {
+    type Error = ParseError;
+    type Variant = Value;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Value), ParseError> {
+        let variant = seed.deserialize(self.variant.into_deserializer())?;
+        Ok((variant, self.value))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Value {
+    type Error = ParseError;
+
+    fn unit_variant(self) -> Result<(), ParseError> {
+        de::Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, ParseError> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, ParseError> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ParseError> {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+    use serde::Deserialize;
+
+    use super::from_value;
+    use crate::{
+        serde::from_str,
+        value::{Number, Value},
+    };
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Rect(u32, u32),
+        Polygon { sides: u8 },
+    }
+
+    #[test]
+    fn test_numbers() {
+        for (s, n) in [
+            ("18446744073709551615", Number::PosInt(u64::MAX)),
+            ("-1", Number::NegInt(u64::MAX)),
+            ("-9223372036854775809", Number::NegInt((1 << 63) - 1)),
+            ("-18446744073709551616", Number::NegInt(0)),
+            ("-0.5", Number::Float(-0.5)),
+            ("-inf", Number::Inf { negative: true }),
+        ] {
+            assert_eq!(from_str::<Number>(s).unwrap(), n, "{}", s);
+            assert_eq!(
+                from_str::<Value>(s).unwrap(),
+                Value::number(n.clone()),
+                "{}",
+                s
+            );
+            assert_eq!(from_value::<Number>(Value::number(n.clone())).unwrap(), n);
+        }
+
+        assert!(from_str::<Number>("nan").unwrap().is_nan());
+        assert!(
+            from_value::<f64>(Value::number(Number::NaN))
+                .unwrap()
+                .is_nan()
+        );
+        assert_eq!(
+            from_value::<i128>(Value::number(Number::NegInt(0))).unwrap(),
+            -(1 << 64)
+        );
+        assert!(from_str::<Number>("x").is_err());
+
+        // decimals pass through serde exactly, or as the nearest float when one
+        // is requested
+        #[cfg(feature = "decimal")]
+        {
+            let n = Number::Decimal("-12.340".parse().unwrap());
+            assert_eq!(crate::serde::to_string(&n).unwrap(), "-12.34");
+            assert_eq!(
+                crate::value::to_value(&n).unwrap(),
+                Value::number(n.clone())
+            );
+            assert_eq!(from_str::<f64>("0.1").unwrap(), 0.1);
+            assert_eq!(from_str::<f32>("0.1").unwrap(), 0.1f32);
+            assert_eq!(from_str::<Option<f64>>("1e-3").unwrap(), Some(0.001));
+            assert_eq!(from_value::<f64>(Value::number(n)).unwrap(), -12.34);
+            assert_eq!(from_str::<Vec<(f64,)>>("::2.5;;").unwrap(), [(2.5,)]);
+        }
+    }
+
+    #[test]
+    fn test_values() {
+        let s = r#"name:twic,tags::a,"b c";,nested:x:null,y:1.5;,empty:;;"#;
+        let v = from_str::<Value>(s).unwrap();
+        assert_eq!(v, s.parse::<Value>().unwrap());
+        assert_eq!(
+            from_str::<Value>(&crate::serde::to_string(&v).unwrap()).unwrap(),
+            v
+        );
+        assert!(from_str::<Value>("a:1,a:2;").is_err());
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Config {
+            name: String,
+            tags: Vec<String>,
+            nested: BTreeMap<String, Option<f64>>,
+        }
+
+        let cfg = from_value::<Config>(v).unwrap();
+        assert_eq!(cfg.name, "twic");
+        assert_eq!(cfg.tags, ["a", "b c"]);
+        assert_eq!(cfg.nested["x"], None);
+        assert_eq!(cfg.nested["y"], Some(1.5));
+    }
+
+    #[test]
+    fn test_enums() {
+        let shape = |v: Value| from_value::<Shape>(v);
+
+        assert_eq!(shape(Value::from("Empty")).unwrap(), Shape::Empty);
+        assert_eq!(
+            shape(Value::map_from([("Empty", Value::Null)])).unwrap(),
+            Shape::Empty
+        );
+        assert_eq!(
+            shape(Value::map_from([("Circle", 1.5)])).unwrap(),
+            Shape::Circle(1.5)
+        );
+        assert_eq!(
+            shape(Value::map_from([("Rect", Value::vector_from([2, 3]))])).unwrap(),
+            Shape::Rect(2, 3)
+        );
+        assert_eq!(
+            shape(Value::map_from([(
+                "Polygon",
+                Value::map_from([("sides", 6)])
+            )]))
+            .unwrap(),
+            Shape::Polygon { sides: 6 }
+        );
+        assert!(shape(Value::from("Circle")).is_err());
+        assert!(shape(Value::map_empty()).is_err());
+        assert!(shape(Value::map_from([("Rect", Value::vector_from([2, 3, 4]))])).is_err());
+
+        assert_eq!(
+            from_value::<BTreeMap<u8, bool>>(Value::map_from([("1", true)])).unwrap(),
+            BTreeMap::from([(1, true)])
+        );
+    }
+}
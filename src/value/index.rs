@@ -3,6 +3,7 @@ use alloc::{borrow::ToOwned, string::String};
 use super::Value;
 
 /// Errors that can occur when indexing into a [`Value`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ValueIndexError {
     /// The value is not indexable (not a map or vector).
     NotIndexable,
@@ -159,7 +160,7 @@ impl<T: IndexInto> core::ops::Index<T> for Value {
     /// use twic::value::{Value, Map};
     ///
     /// let mut v = Value::vector_from([1, 2]);
-    /// assert_eq!(v[0], Value::Number(1.into()));
+    /// assert_eq!(v[0], Value::number(1));
     ///
     /// let mut m = Value::map_empty();
     /// m["key"] = Value::string("value");
@@ -1,16 +1,35 @@
+#[cfg(feature = "bigint")]
+mod bigint;
+mod cmp;
+#[cfg(feature = "decimal")]
+mod decimal;
+mod error;
 mod impls;
+mod ops;
+mod repr;
 mod utils;
 
+#[cfg(feature = "bigint")]
+pub use bigint::BigInt;
+#[cfg(feature = "decimal")]
+pub use decimal::Decimal;
+pub use error::{NumberConversionError, NumberConversionErrorKind};
+pub use repr::{Notation, NumberRepr, NumberReprs, Radix};
 use utils::{
     consts::*, f64_to_f32_lossless, f64_to_u64_no_sig_lossless, f64_to_u128_no_sig_lossless,
     from_inf, neg_i65_to_i128, u64_to_f32_lossless, u64_to_f64_lossless,
 };
+#[cfg(feature = "decimal")]
+use utils::{saturate_i128, saturate_u128};
 
 /// Represents a Twic number, which can be an integer, float (excluding NaN and
 /// Infinity), `nan`, and `[+/-]inf`.
 ///
 /// This enum is not expected to be constructed directly by specifying its
-/// variants but rather through conversions from Rust numeric types.
+/// variants but rather through conversions from Rust numeric types. It is
+/// non-exhaustive, as the `bigint` and `decimal` features add variants, and
+/// implements the same traits with or without them: in particular, it is not
+/// `Copy`.
 ///
 /// # Representation and Ranges
 ///
@@ -18,9 +37,15 @@ use utils::{
 /// - Integers from `-2^64` to `2^64 - 1`, i.e., the range of `i65` (if such a
 ///   type exists). Therefore all Rust integer types (except `i128` and `u128`)
 ///   can be safely converted to `Number` without loss of information.
+/// - With the `bigint` feature, integers of any size out of that range, as
+///   `Number::BigInt`s.
 /// - Floating-point numbers representable by `f64`, including special values
 ///   `NaN` and positive/negative infinity (though these are represented by
 ///   separate enum variants for clarity and convenience).
+/// - With the `decimal` feature, exact decimals `±mantissa × 10^exponent` with
+///   a `u128` mantissa and an `i16` exponent, as `Number::Decimal`s. Number
+///   literals with a fraction or an exponent are parsed into decimals only
+///   when enabled, see [`from_str_decimal`](crate::de::from_str_decimal).
 ///
 /// # Range Checking and Conversions
 ///
@@ -33,7 +58,8 @@ use utils::{
 ///   considered not to fit into floating-point types regardless of their value,
 ///   and vice versa.
 /// - `From::from` allows conversion from Rust numeric types to `Number`s. These
-///   conversions are always safe and lossless.
+///   conversions are always safe and lossless. `i128` and `u128` are converted
+///   with `TryFrom::try_from` instead, which fails if the value is out of range.
 /// - `get_<type>` methods attempt to get the value of the `Number` as the
 ///   specified Rust numeric type, if the value and target type are both
 ///   integers or both floats, and the value fits within the target type's
@@ -47,7 +73,37 @@ use utils::{
 ///   type, following Rust's [standard numeric casting `as` rules](
 ///   https://doc.rust-lang.org/stable/reference/expressions/operator-expr.html#r-expr.as.numeric)
 ///   and may result in loss of information.
-#[derive(Clone, Copy)]
+/// - `TryFrom<Number>` is implemented for all Rust numeric types. It converts
+///   like the `as_<type>_exact` methods, but returns a
+///   [`NumberConversionError`] telling why the conversion failed.
+///
+/// # Arithmetic
+///
+/// `Number` implements the arithmetic operators, along with `checked_*`,
+/// `wrapping_*` and `saturating_*` methods. Operations on two integers are
+/// exact, and their results are converted to the nearest float (or `None`,
+/// wrapped, or saturated) if out of the integer range. Operations involving a
+/// float, `nan` or `inf` are performed on `f64`s following IEEE 754.
+///
+/// With the `bigint` feature, operations involving a `Number::BigInt` are
+/// exact, and their results are never out of range. Operations on integers in
+/// the range of `[-2^64, 2^64 - 1]` behave the same with or without the
+/// feature. `wrapping_*` and `saturating_*` methods always work on that range.
+///
+/// With the `decimal` feature, operations on two decimals, or on a decimal and
+/// an integer, are exact and result in decimals. If the exact result does not
+/// fit in a decimal, e.g. `1.0 / 3.0`, they are performed on `f64`s instead.
+///
+/// # Comparison
+///
+/// Equality of `Number`s is structural: integers never equal floats, and `nan`
+/// equals `nan`. [`PartialOrd`] and [`Number::total_cmp`] order numbers by
+/// their exact mathematical values, placing integers before floats of the same
+/// value to stay consistent with equality, and decimals between them. Use
+/// [`Number::numeric_cmp`], [`Number::numeric_eq`] and [`Number::numeric_hash`]
+/// to compare and hash by value only, e.g. for range checks.
+#[derive(Clone)]
+#[non_exhaustive]
 pub enum Number {
     /// Represents a positive integer, including zero, in the range of
     /// `[0, 2^64 - 1]`.
@@ -78,6 +134,27 @@ pub enum Number {
         /// Indicates if the infinity is negative.
         negative: bool,
     },
+    /// Represents an integer out of the range of `[-2^64, 2^64 - 1]`.
+    ///
+    /// Available with the `bigint` feature. Integers in the range are always
+    /// represented by [`Number::PosInt`] or [`Number::NegInt`] instead.
+    #[cfg(feature = "bigint")]
+    BigInt(BigInt),
+    /// Represents an exact decimal, which may or may not have a fractional
+    /// part.
+    ///
+    /// Available with the `decimal` feature. Decimals are never equal to
+    /// integers or floats, even of the same value.
+    #[cfg(feature = "decimal")]
+    Decimal(Decimal),
+}
+
+impl Number {
+    /// The name of the newtype struct and map key passing numbers without a
+    /// counterpart in the serde data model through serde as literal strings:
+    /// big integers out of the range of `i128` and `u128`, and decimals.
+    #[cfg(all(feature = "serde", any(feature = "bigint", feature = "decimal")))]
+    pub(crate) const SERDE_TOKEN: &str = "$twic::private::Number";
 }
 
 /// Basic checks.
@@ -96,7 +173,12 @@ impl Number {
     /// assert!(!m.is_integer());
     /// ```
     pub const fn is_integer(&self) -> bool {
-        matches!(self, Number::PosInt(_) | Number::NegInt(_))
+        match self {
+            Number::PosInt(_) | Number::NegInt(_) => true,
+            #[cfg(feature = "bigint")]
+            Number::BigInt(_) => true,
+            _ => false,
+        }
     }
 
     /// Checks if the `Number` is a floating-point number, excluding NaN and
@@ -117,6 +199,24 @@ impl Number {
         matches!(self, Number::Float(_))
     }
 
+    /// Checks if the `Number` is a decimal.
+    ///
+    /// Available with the `decimal` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// let n = twic::de::from_str_decimal("3.14").unwrap().as_number().unwrap();
+    /// assert!(n.is_decimal() && !n.is_float());
+    /// assert!(!"3.14".parse::<Number>().unwrap().is_decimal());
+    /// ```
+    #[cfg(feature = "decimal")]
+    pub const fn is_decimal(&self) -> bool {
+        matches!(self, Number::Decimal(_))
+    }
+
     /// Checks if the `Number` is NaN.
     ///
     /// # Examples
@@ -205,6 +305,10 @@ impl Number {
             Number::Float(n) => n.is_sign_positive() && *n != 0.0,
             Number::NaN => false,
             Number::Inf { negative } => !*negative,
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => !n.is_negative(),
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => !d.is_negative() && !d.is_zero(),
         }
     }
 
@@ -234,6 +338,10 @@ impl Number {
             Number::Float(n) => n.is_sign_negative() && *n != 0.0,
             Number::NaN => false,
             Number::Inf { negative } => *negative,
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => n.is_negative(),
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => d.is_negative(),
         }
     }
 
@@ -255,6 +363,10 @@ impl Number {
             Number::Float(n) => *n == 0.0,
             Number::NaN => false,
             Number::Inf { .. } => false,
+            #[cfg(feature = "bigint")]
+            Number::BigInt(_) => false,
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => d.is_zero(),
         }
     }
 }
@@ -367,7 +479,8 @@ impl Number {
     }
 
     /// Checks if the `Number` is an integer and can be represented as an i128
-    /// without overflow. This is always true for integer `Number`s.
+    /// without overflow. This is always true for integer `Number`s, except
+    /// for big integers with the `bigint` feature.
     ///
     /// # Examples
     ///
@@ -381,7 +494,12 @@ impl Number {
     /// assert!(Number::NegInt(u64::MAX).fits_in_i128());
     /// ```
     pub const fn fits_in_i128(&self) -> bool {
-        matches!(self, Number::PosInt(_) | Number::NegInt(_))
+        match self {
+            Number::PosInt(_) | Number::NegInt(_) => true,
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => n.to_i128().is_some(),
+            _ => false,
+        }
     }
 
     /// Checks if the `Number` is an integer and can be represented as a u8
@@ -480,7 +598,8 @@ impl Number {
     }
 
     /// Checks if the `Number` is an integer and can be represented as a u128
-    /// without overflow. This is always true for positive integer `Number`s.
+    /// without overflow. This is always true for positive integer `Number`s,
+    /// except for big integers with the `bigint` feature.
     ///
     /// # Examples
     ///
@@ -492,7 +611,12 @@ impl Number {
     /// assert!(!Number::from(-1i64).fits_in_u128());
     /// ```
     pub const fn fits_in_u128(&self) -> bool {
-        matches!(self, Number::PosInt(_))
+        match self {
+            Number::PosInt(_) => true,
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => n.to_u128().is_some(),
+            _ => false,
+        }
     }
 
     /// Checks if the `Number` is a float and can be represented as an f32
@@ -640,7 +764,9 @@ impl Number {
         }
     }
 
-    /// Gets the `Number` as an i128 if it is an integer.
+    /// Gets the `Number` as an i128 if it is an integer and can be represented
+    /// as an i128 without overflow, which is always the case without the
+    /// `bigint` feature.
     ///
     /// # Examples
     ///
@@ -657,6 +783,8 @@ impl Number {
         match self {
             Number::PosInt(n) => Some(*n as i128),
             Number::NegInt(n) => Some(neg_i65_to_i128(*n)),
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => n.to_i128(),
             _ => None,
         }
     }
@@ -757,7 +885,9 @@ impl Number {
         }
     }
 
-    /// Gets the `Number` as a u128 if it is a positive integer.
+    /// Gets the `Number` as a u128 if it is a positive integer and can be
+    /// represented as a u128 without overflow, which is always the case
+    /// without the `bigint` feature.
     ///
     /// # Examples
     ///
@@ -771,6 +901,8 @@ impl Number {
     pub const fn get_u128(&self) -> Option<u128> {
         match self {
             Number::PosInt(n) => Some(*n as u128),
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => n.to_u128(),
             _ => None,
         }
     }
@@ -867,6 +999,11 @@ impl Number {
                     None
                 }
             }
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => match d.to_i128() {
+                Some(n) if n >= i8::MIN as i128 && n <= i8::MAX as i128 => Some(n as i8),
+                _ => None,
+            },
             _ => None,
         }
     }
@@ -913,6 +1050,11 @@ impl Number {
                     None
                 }
             }
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => match d.to_i128() {
+                Some(n) if n >= i16::MIN as i128 && n <= i16::MAX as i128 => Some(n as i16),
+                _ => None,
+            },
             _ => None,
         }
     }
@@ -958,6 +1100,11 @@ impl Number {
                     None
                 }
             }
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => match d.to_i128() {
+                Some(n) if n >= i32::MIN as i128 && n <= i32::MAX as i128 => Some(n as i32),
+                _ => None,
+            },
             _ => None,
         }
     }
@@ -1004,6 +1151,11 @@ impl Number {
                     None
                 }
             }
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => match d.to_i128() {
+                Some(n) if n >= i64::MIN as i128 && n <= i64::MAX as i128 => Some(n as i64),
+                _ => None,
+            },
             _ => None,
         }
     }
@@ -1045,6 +1197,11 @@ impl Number {
                     None
                 }
             }
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => match d.to_i128() {
+                Some(n) if n >= isize::MIN as i128 && n <= isize::MAX as i128 => Some(n as isize),
+                _ => None,
+            },
             _ => None,
         }
     }
@@ -1091,6 +1248,10 @@ impl Number {
                     None
                 }
             }
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => n.to_i128(),
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => d.to_i128(),
             _ => None,
         }
     }
@@ -1133,6 +1294,11 @@ impl Number {
                     None
                 }
             }
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => match d.to_u128() {
+                Some(n) if n <= u8::MAX as u128 => Some(n as u8),
+                _ => None,
+            },
             _ => None,
         }
     }
@@ -1175,6 +1341,11 @@ impl Number {
                     None
                 }
             }
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => match d.to_u128() {
+                Some(n) if n <= u16::MAX as u128 => Some(n as u16),
+                _ => None,
+            },
             _ => None,
         }
     }
@@ -1217,6 +1388,11 @@ impl Number {
                     None
                 }
             }
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => match d.to_u128() {
+                Some(n) if n <= u32::MAX as u128 => Some(n as u32),
+                _ => None,
+            },
             _ => None,
         }
     }
@@ -1257,6 +1433,11 @@ impl Number {
                     None
                 }
             }
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => match d.to_u128() {
+                Some(n) if n <= u64::MAX as u128 => Some(n as u64),
+                _ => None,
+            },
             _ => None,
         }
     }
@@ -1293,6 +1474,11 @@ impl Number {
                     None
                 }
             }
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => match d.to_u128() {
+                Some(n) if n <= usize::MAX as u128 => Some(n as usize),
+                _ => None,
+            },
             _ => None,
         }
     }
@@ -1337,6 +1523,10 @@ impl Number {
                     None
                 }
             }
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => n.to_u128(),
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => d.to_u128(),
             _ => None,
         }
     }
@@ -1370,6 +1560,10 @@ impl Number {
             Number::Float(n) => f64_to_f32_lossless(*n),
             Number::NaN => Some(f32::NAN),
             Number::Inf { negative } => Some(from_inf(*negative)),
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => n.to_f32_exact(),
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => d.to_f32_exact(),
         }
     }
 
@@ -1399,6 +1593,10 @@ impl Number {
             Number::Float(n) => Some(*n),
             Number::NaN => Some(f64::NAN),
             Number::Inf { negative } => Some(from_inf(*negative)),
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => n.to_f64_exact(),
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => d.to_f64_exact(),
         }
     }
 }
@@ -1426,6 +1624,12 @@ impl Number {
             Number::Float(n) => *n as i8,
             Number::NaN => 0,
             Number::Inf { negative } => from_inf(*negative),
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => n.wrapping_to_u128() as i8,
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => {
+                saturate_i128(d.saturating_to_i128(), i8::MIN as i128, i8::MAX as i128) as i8
+            }
         }
     }
 
@@ -1451,6 +1655,12 @@ impl Number {
             Number::Float(n) => *n as i16,
             Number::NaN => 0,
             Number::Inf { negative } => from_inf(*negative),
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => n.wrapping_to_u128() as i16,
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => {
+                saturate_i128(d.saturating_to_i128(), i16::MIN as i128, i16::MAX as i128) as i16
+            }
         }
     }
 
@@ -1475,6 +1685,12 @@ impl Number {
             Number::Float(n) => *n as i32,
             Number::NaN => 0,
             Number::Inf { negative } => from_inf(*negative),
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => n.wrapping_to_u128() as i32,
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => {
+                saturate_i128(d.saturating_to_i128(), i32::MIN as i128, i32::MAX as i128) as i32
+            }
         }
     }
 
@@ -1498,6 +1714,12 @@ impl Number {
             Number::Float(n) => *n as i64,
             Number::NaN => 0,
             Number::Inf { negative } => from_inf(*negative),
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => n.wrapping_to_u128() as i64,
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => {
+                saturate_i128(d.saturating_to_i128(), i64::MIN as i128, i64::MAX as i128) as i64
+            }
         }
     }
 
@@ -1521,6 +1743,14 @@ impl Number {
             Number::Float(n) => *n as isize,
             Number::NaN => 0,
             Number::Inf { negative } => from_inf(*negative),
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => n.wrapping_to_u128() as isize,
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => saturate_i128(
+                d.saturating_to_i128(),
+                isize::MIN as i128,
+                isize::MAX as i128,
+            ) as isize,
         }
     }
 
@@ -1546,6 +1776,10 @@ impl Number {
             Number::Float(n) => *n as i128,
             Number::NaN => 0,
             Number::Inf { negative } => from_inf(*negative),
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => n.wrapping_to_u128() as i128,
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => d.saturating_to_i128(),
         }
     }
 
@@ -1569,6 +1803,10 @@ impl Number {
             Number::Float(n) => *n as u8,
             Number::NaN => 0,
             Number::Inf { negative } => from_inf(*negative),
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => n.wrapping_to_u128() as u8,
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => saturate_u128(d.saturating_to_u128(), u8::MAX as u128) as u8,
         }
     }
 
@@ -1592,6 +1830,10 @@ impl Number {
             Number::Float(n) => *n as u16,
             Number::NaN => 0,
             Number::Inf { negative } => from_inf(*negative),
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => n.wrapping_to_u128() as u16,
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => saturate_u128(d.saturating_to_u128(), u16::MAX as u128) as u16,
         }
     }
 
@@ -1616,6 +1858,10 @@ impl Number {
             Number::Float(n) => *n as u32,
             Number::NaN => 0,
             Number::Inf { negative } => from_inf(*negative),
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => n.wrapping_to_u128() as u32,
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => saturate_u128(d.saturating_to_u128(), u32::MAX as u128) as u32,
         }
     }
 
@@ -1638,6 +1884,10 @@ impl Number {
             Number::Float(n) => *n as u64,
             Number::NaN => 0,
             Number::Inf { negative } => from_inf(*negative),
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => n.wrapping_to_u128() as u64,
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => saturate_u128(d.saturating_to_u128(), u64::MAX as u128) as u64,
         }
     }
 
@@ -1661,6 +1911,12 @@ impl Number {
             Number::Float(n) => *n as usize,
             Number::NaN => 0,
             Number::Inf { negative } => from_inf(*negative),
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => n.wrapping_to_u128() as usize,
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => {
+                saturate_u128(d.saturating_to_u128(), usize::MAX as u128) as usize
+            }
         }
     }
 
@@ -1686,6 +1942,10 @@ impl Number {
             Number::Float(n) => *n as u128,
             Number::NaN => 0,
             Number::Inf { negative } => from_inf(*negative),
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => n.wrapping_to_u128(),
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => d.saturating_to_u128(),
         }
     }
 
@@ -1710,6 +1970,10 @@ impl Number {
             Number::Float(n) => *n as f32,
             Number::NaN => f32::NAN,
             Number::Inf { negative } => from_inf(*negative),
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => n.to_f32(),
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => d.to_f32(),
         }
     }
 
@@ -1734,6 +1998,67 @@ impl Number {
             Number::Float(n) => *n,
             Number::NaN => f64::NAN,
             Number::Inf { negative } => from_inf(*negative),
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => n.to_f64(),
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => d.to_f64(),
+        }
+    }
+}
+
+/// Normalization.
+impl Number {
+    /// Returns the canonical form of the `Number`, which is an integer if the
+    /// value is an integer in the range of integer `Number`s, or the number
+    /// itself otherwise.
+    ///
+    /// Both `0.0` and `-0.0` become the integer `0`. With the `bigint` feature,
+    /// every float or decimal without a fractional part becomes an integer.
+    /// With the `decimal` feature, every other decimal with the exact value of
+    /// a float becomes that float. Numbers with the same mathematical value
+    /// have the same canonical form, so normalized numbers can be compared and
+    /// hashed structurally.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(Number::Float(8080.0).normalize(), Number::PosInt(8080));
+    /// assert_eq!(Number::Float(-1.0).normalize(), Number::NegInt(u64::MAX));
+    /// assert_eq!(Number::Float(-0.0).normalize(), Number::PosInt(0));
+    /// assert_eq!(Number::Float(0.1).normalize(), Number::Float(0.1));
+    /// #[cfg(feature = "decimal")]
+    /// assert_eq!(Number::Decimal("0.5".parse().unwrap()).normalize(), Number::Float(0.5));
+    /// ```
+    pub fn normalize(&self) -> Number {
+        if !self.is_integer()
+            && let Some(n) = self.as_i128_exact()
+            && let Ok(n) = Number::try_from(n)
+        {
+            return n;
+        }
+        // floats of at least 2^64 in magnitude are all integers
+        #[cfg(feature = "bigint")]
+        if let Number::Float(f) = *self
+            && f.abs() >= TWO_POW_64_F64
+        {
+            return Number::from(BigInt::from_f64(f));
+        }
+        #[cfg(all(feature = "bigint", feature = "decimal"))]
+        if let Number::Decimal(d) = self
+            && d.is_integer()
+        {
+            let n = BigInt::from(d.mantissa()).mul_pow10(d.exponent() as u32);
+            return Number::from(if d.is_negative() { -n } else { n });
+        }
+        // decimals with the exact value of a float are floats as well
+        #[cfg(feature = "decimal")]
+        if let Number::Decimal(d) = self
+            && Decimal::from_f64(d.to_f64()) == Some(*d)
+        {
+            return Number::Float(d.to_f64());
         }
+        self.clone()
     }
 }
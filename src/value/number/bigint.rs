@@ -0,0 +1,804 @@
+use alloc::{string::String, vec, vec::Vec};
+use core::{
+    cmp::Ordering,
+    fmt::{self, Write},
+    ops::{Add, Mul, Neg, Sub},
+};
+
+/// An arbitrary-precision integer, held by [`Number::BigInt`](super::Number)
+/// for integers out of the range `[-2^64, 2^64 - 1]`.
+///
+/// Available with the `bigint` feature. `BigInt`s are usually obtained by
+/// parsing Twic text, or by converting from `i128` or `u128`:
+///
+/// ```
+/// use twic::value::{BigInt, Number};
+///
+/// let n: Number = "340282366920938463463374607431768211456".parse().unwrap();
+/// assert!(matches!(n, Number::BigInt(_)));
+/// assert_eq!(BigInt::from(u128::MAX).to_string(), "340282366920938463463374607431768211455");
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    /// Never set for zero.
+    negative: bool,
+    /// The magnitude in little-endian 64-bit limbs, without leading zero limbs.
+    limbs: Vec<u64>,
+}
+
+impl BigInt {
+    fn from_parts(negative: bool, mut limbs: Vec<u64>) -> BigInt {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        BigInt {
+            negative: negative && !limbs.is_empty(),
+            limbs,
+        }
+    }
+
+    fn from_magnitude(negative: bool, magnitude: u128) -> BigInt {
+        BigInt::from_parts(negative, vec![magnitude as u64, (magnitude >> 64) as u64])
+    }
+
+    /// Parses the digits of an integer in radix 10 or 16. Returns `None` if
+    /// there is no digit or an invalid one.
+    ///
+    /// Hexadecimal digits are converted in linear time, and decimal ones in
+    /// quadratic time, so callers parsing untrusted input should bound the
+    /// number of decimal digits.
+    pub(crate) fn from_digits(negative: bool, digits: &str, radix: u32) -> Option<BigInt> {
+        if digits.is_empty() {
+            return None;
+        }
+
+        // each limb holds 16 hexadecimal digits, and each chunk of 19 decimal
+        // digits is added to the limbs multiplied by `10^19`
+        let chunk_len = if radix == 16 { 16 } else { 19 };
+        let chunk_value = |chunk: &[u8]| {
+            chunk.iter().try_fold(0u64, |acc, &b| {
+                Some(acc * radix as u64 + (b as char).to_digit(radix)? as u64)
+            })
+        };
+
+        let bytes = digits.as_bytes();
+        if radix == 16 {
+            let limbs = bytes
+                .rchunks(chunk_len)
+                .map(chunk_value)
+                .collect::<Option<_>>()?;
+            return Some(BigInt::from_parts(negative, limbs));
+        }
+
+        let mut limbs = Vec::with_capacity(bytes.len() / 19 + 1);
+        let head = bytes.len() % chunk_len;
+        let chunks = (head > 0).then(|| &bytes[..head]).into_iter();
+        for chunk in chunks.chain(bytes[head..].chunks(chunk_len)) {
+            let scale = 10u64.pow(chunk.len() as u32);
+            mul_add_limb(&mut limbs, scale, chunk_value(chunk)?);
+        }
+        Some(BigInt::from_parts(negative, limbs))
+    }
+
+    /// Multiplies the integer by `10^exponent`, in time linear in `exponent`
+    /// and the length of the result.
+    #[cfg(any(feature = "decimal", test))]
+    pub(crate) fn mul_pow10(mut self, exponent: u32) -> BigInt {
+        // each step multiplies by up to `10^19`, the largest power of ten in a
+        // limb
+        let mut rest = exponent;
+        while rest > 0 && !self.is_zero() {
+            let step = rest.min(19);
+            mul_add_limb(&mut self.limbs, 10u64.pow(step), 0);
+            rest -= step;
+        }
+        self
+    }
+
+    /// Converts a finite float to a `BigInt`, truncating towards zero.
+    pub(crate) fn from_f64(f: f64) -> BigInt {
+        let bits = f.to_bits();
+        let exponent = ((bits >> 52) & 0x7ff) as i64;
+        let mantissa = if exponent == 0 {
+            (bits & ((1 << 52) - 1)) << 1
+        } else {
+            (bits & ((1 << 52) - 1)) | (1 << 52)
+        };
+        // `f` is `mantissa * 2^shift`
+        let shift = exponent - 1075;
+        let negative = f.is_sign_negative();
+
+        if shift <= -64 {
+            BigInt::from_parts(negative, Vec::new())
+        } else if shift < 0 {
+            BigInt::from_parts(negative, vec![mantissa >> -shift])
+        } else {
+            let mut limbs = vec![0; shift as usize / 64];
+            let offset = shift % 64;
+            limbs.push(mantissa << offset);
+            if offset != 0 {
+                limbs.push(mantissa >> (64 - offset));
+            }
+            BigInt::from_parts(negative, limbs)
+        }
+    }
+
+    /// Checks if the integer is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::BigInt;
+    ///
+    /// assert!(BigInt::from(-1i128).is_negative());
+    /// assert!(!BigInt::from(0u128).is_negative());
+    /// ```
+    pub const fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Checks if the integer is zero.
+    pub const fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// Gets the magnitude as a `u128`, if it fits.
+    const fn magnitude_u128(&self) -> Option<u128> {
+        match self.limbs.as_slice() {
+            [] => Some(0),
+            [low] => Some(*low as u128),
+            [low, high] => Some(*low as u128 | (*high as u128) << 64),
+            _ => None,
+        }
+    }
+
+    /// Gets the integer as an `i128`, if it fits.
+    pub(crate) const fn to_i128(&self) -> Option<i128> {
+        match self.magnitude_u128() {
+            Some(m) if !self.negative && m <= i128::MAX as u128 => Some(m as i128),
+            Some(m) if self.negative && m <= i128::MIN as u128 => Some((m as i128).wrapping_neg()),
+            _ => None,
+        }
+    }
+
+    /// Gets the integer as a `u128`, if it fits.
+    pub(crate) const fn to_u128(&self) -> Option<u128> {
+        match self.magnitude_u128() {
+            Some(m) if !self.negative => Some(m),
+            _ => None,
+        }
+    }
+
+    /// Gets the lowest 128 bits of the integer in two's complement.
+    pub(crate) const fn wrapping_to_u128(&self) -> u128 {
+        let limbs = self.limbs.as_slice();
+        let mut low = 0;
+        if !limbs.is_empty() {
+            low |= limbs[0] as u128;
+        }
+        if limbs.len() > 1 {
+            low |= (limbs[1] as u128) << 64;
+        }
+        if self.negative {
+            low.wrapping_neg()
+        } else {
+            low
+        }
+    }
+
+    /// Gets the number of significant bits of the magnitude.
+    const fn bit_len(&self) -> u64 {
+        let limbs = self.limbs.as_slice();
+        match limbs.last() {
+            Some(last) => limbs.len() as u64 * 64 - last.leading_zeros() as u64,
+            None => 0,
+        }
+    }
+
+    /// Gets the number of trailing zero bits of the magnitude.
+    const fn trailing_zeros(&self) -> u64 {
+        let limbs = self.limbs.as_slice();
+        let mut i = 0;
+        while i < limbs.len() {
+            if limbs[i] != 0 {
+                return i as u64 * 64 + limbs[i].trailing_zeros() as u64;
+            }
+            i += 1;
+        }
+        0
+    }
+
+    /// Gets the 64 bits of the magnitude starting from bit `shift`, with the
+    /// lowest bit set if any lower bit is set, so that rounding the result to
+    /// fewer bits gives the same result as rounding the whole magnitude.
+    const fn top_bits(&self, shift: u64) -> u64 {
+        let limbs = self.limbs.as_slice();
+        let index = (shift / 64) as usize;
+        let offset = shift % 64;
+
+        let mut bits = limbs[index] >> offset;
+        if offset != 0 && index + 1 < limbs.len() {
+            bits |= limbs[index + 1] << (64 - offset);
+        }
+        if shift > self.trailing_zeros() {
+            bits |= 1;
+        }
+        bits
+    }
+
+    /// Converts the integer to the nearest `f64`, ties to even.
+    pub(crate) const fn to_f64(&self) -> f64 {
+        let len = self.bit_len();
+        let magnitude = if len <= 64 {
+            match self.magnitude_u128() {
+                Some(m) => m as f64,
+                None => 0.0,
+            }
+        } else {
+            let shift = len - 64;
+            let scale = if shift > 1023 {
+                f64::INFINITY
+            } else {
+                f64::from_bits((shift + 1023) << 52)
+            };
+            self.top_bits(shift) as f64 * scale
+        };
+        if self.negative { -magnitude } else { magnitude }
+    }
+
+    /// Converts the integer to the nearest `f32`, ties to even.
+    pub(crate) const fn to_f32(&self) -> f32 {
+        let len = self.bit_len();
+        let magnitude = if len <= 64 {
+            match self.magnitude_u128() {
+                Some(m) => m as f32,
+                None => 0.0,
+            }
+        } else {
+            let shift = len - 64;
+            let scale = if shift > 127 {
+                f32::INFINITY
+            } else {
+                f32::from_bits((shift as u32 + 127) << 23)
+            };
+            self.top_bits(shift) as f32 * scale
+        };
+        if self.negative { -magnitude } else { magnitude }
+    }
+
+    /// Converts the integer to an `f64`, if it can be represented exactly.
+    pub(crate) const fn to_f64_exact(&self) -> Option<f64> {
+        let len = self.bit_len();
+        if len <= 1024 && len - self.trailing_zeros() <= 53 {
+            Some(self.to_f64())
+        } else {
+            None
+        }
+    }
+
+    /// Converts the integer to an `f32`, if it can be represented exactly.
+    pub(crate) const fn to_f32_exact(&self) -> Option<f32> {
+        let len = self.bit_len();
+        if len <= 128 && len - self.trailing_zeros() <= 24 {
+            Some(self.to_f32())
+        } else {
+            None
+        }
+    }
+
+    /// Divides the integer by `rhs`, rounding towards zero, and returns the
+    /// quotient and the remainder. Returns `None` if `rhs` is zero.
+    pub fn checked_div_rem(&self, rhs: &BigInt) -> Option<(BigInt, BigInt)> {
+        if rhs.is_zero() {
+            return None;
+        }
+        let (quotient, remainder) = div_rem_magnitude(&self.limbs, &rhs.limbs);
+        Some((
+            BigInt::from_parts(self.negative != rhs.negative, quotient),
+            BigInt::from_parts(self.negative, remainder),
+        ))
+    }
+
+    /// Divides the integer by `rhs`, rounding towards zero. Returns `None` if
+    /// `rhs` is zero.
+    pub fn checked_div(self, rhs: BigInt) -> Option<BigInt> {
+        self.checked_div_rem(&rhs).map(|(quotient, _)| quotient)
+    }
+
+    /// Calculates the remainder of dividing the integer by `rhs`, which has
+    /// the same sign as the integer. Returns `None` if `rhs` is zero.
+    pub fn checked_rem(self, rhs: BigInt) -> Option<BigInt> {
+        self.checked_div_rem(&rhs).map(|(_, remainder)| remainder)
+    }
+}
+
+fn cmp_magnitude(a: &[u64], b: &[u64]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitude(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut result = Vec::with_capacity(a.len() + 1);
+    let mut carry = false;
+    for (i, &x) in a.iter().enumerate() {
+        let (sum, c1) = x.overflowing_add(b.get(i).copied().unwrap_or(0));
+        let (sum, c2) = sum.overflowing_add(carry as u64);
+        result.push(sum);
+        carry = c1 || c2;
+    }
+    if carry {
+        result.push(1);
+    }
+    result
+}
+
+/// Subtracts `b` from `a`, where `a >= b`.
+fn sub_magnitude(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut result = Vec::with_capacity(a.len());
+    let mut borrow = false;
+    for (i, &x) in a.iter().enumerate() {
+        let (diff, b1) = x.overflowing_sub(b.get(i).copied().unwrap_or(0));
+        let (diff, b2) = diff.overflowing_sub(borrow as u64);
+        result.push(diff);
+        borrow = b1 || b2;
+    }
+    result
+}
+
+/// Sets `limbs` to `limbs * scale + carry`.
+fn mul_add_limb(limbs: &mut Vec<u64>, scale: u64, carry: u64) {
+    let mut carry = carry as u128;
+    for limb in limbs.iter_mut() {
+        let v = *limb as u128 * scale as u128 + carry;
+        *limb = v as u64;
+        carry = v >> 64;
+    }
+    if carry != 0 {
+        limbs.push(carry as u64);
+    }
+}
+
+fn mul_magnitude(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut result = vec![0; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0;
+        for (j, &y) in b.iter().enumerate() {
+            let v = x as u128 * y as u128 + result[i + j] as u128 + carry;
+            result[i + j] = v as u64;
+            carry = v >> 64;
+        }
+        result[i + b.len()] = carry as u64;
+    }
+    result
+}
+
+/// Divides `a` by a single limb `b`, which must not be zero.
+fn div_rem_limb(a: &[u64], b: u64) -> (Vec<u64>, u64) {
+    let mut quotient = vec![0; a.len()];
+    let mut remainder = 0u128;
+    for (i, &x) in a.iter().enumerate().rev() {
+        let v = remainder << 64 | x as u128;
+        quotient[i] = (v / b as u128) as u64;
+        remainder = v % b as u128;
+    }
+    (quotient, remainder as u64)
+}
+
+/// Shifts `a` left by `shift` bits, less than 64, into one more limb.
+fn shl_magnitude(a: &[u64], shift: u32) -> Vec<u64> {
+    let mut result = Vec::with_capacity(a.len() + 1);
+    let mut carry = 0;
+    for &x in a {
+        result.push(x << shift | carry);
+        carry = if shift == 0 { 0 } else { x >> (64 - shift) };
+    }
+    result.push(carry);
+    result
+}
+
+/// Divides `a` by `b`, which must not be zero, with Knuth's long division
+/// (The Art of Computer Programming, Vol. 2, 4.3.1, Algorithm D), finding one
+/// limb of the quotient at a time.
+fn div_rem_magnitude(a: &[u64], b: &[u64]) -> (Vec<u64>, Vec<u64>) {
+    if let [b] = b {
+        let (quotient, remainder) = div_rem_limb(a, *b);
+        return (quotient, vec![remainder]);
+    }
+    if cmp_magnitude(a, b) == Ordering::Less {
+        return (Vec::new(), a.to_vec());
+    }
+
+    // normalizes the divisor so that its highest bit is set, which makes the
+    // estimates of the quotient limbs at most 2 too large
+    let shift = b[b.len() - 1].leading_zeros();
+    let mut b = shl_magnitude(b, shift);
+    b.pop();
+    let mut a = shl_magnitude(a, shift);
+    let n = b.len();
+    let (high, low) = (b[n - 1] as u128, b[n - 2] as u128);
+
+    let mut quotient = vec![0; a.len() - n];
+    for j in (0..quotient.len()).rev() {
+        // estimates the quotient limb from the top limbs
+        let top = (a[j + n] as u128) << 64 | a[j + n - 1] as u128;
+        let mut q = top / high;
+        let mut r = top % high;
+        while q > u64::MAX as u128 || q * low > (r << 64 | a[j + n - 2] as u128) {
+            q -= 1;
+            r += high;
+            if r > u64::MAX as u128 {
+                break;
+            }
+        }
+
+        // subtracts `q * b` from the current part of `a`
+        let mut carry = 0;
+        let mut borrow = false;
+        for i in 0..n {
+            let product = q * b[i] as u128 + carry;
+            carry = product >> 64;
+            let (diff, b1) = a[i + j].overflowing_sub(product as u64);
+            let (diff, b2) = diff.overflowing_sub(borrow as u64);
+            a[i + j] = diff;
+            borrow = b1 || b2;
+        }
+        let (diff, b1) = a[j + n].overflowing_sub(carry as u64);
+        let (diff, b2) = diff.overflowing_sub(borrow as u64);
+        a[j + n] = diff;
+
+        if b1 || b2 {
+            // the estimate was still 1 too large, rarely: adds `b` back
+            q -= 1;
+            let mut carry = false;
+            for i in 0..n {
+                let (sum, c1) = a[i + j].overflowing_add(b[i]);
+                let (sum, c2) = sum.overflowing_add(carry as u64);
+                a[i + j] = sum;
+                carry = c1 || c2;
+            }
+            a[j + n] = a[j + n].wrapping_add(carry as u64);
+        }
+        quotient[j] = q as u64;
+    }
+
+    // the remainder is in the lowest limbs, shifted by `shift`
+    a.truncate(n);
+    let remainder = if shift == 0 {
+        a
+    } else {
+        (0..n)
+            .map(|i| a[i] >> shift | a.get(i + 1).map_or(0, |x| x << (64 - shift)))
+            .collect()
+    };
+    (quotient, remainder)
+}
+
+impl From<i128> for BigInt {
+    fn from(value: i128) -> Self {
+        BigInt::from_magnitude(value < 0, value.unsigned_abs())
+    }
+}
+
+impl From<u128> for BigInt {
+    fn from(value: u128) -> Self {
+        BigInt::from_magnitude(false, value)
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(value: i64) -> Self {
+        BigInt::from(value as i128)
+    }
+}
+
+impl From<u64> for BigInt {
+    fn from(value: u64) -> Self {
+        BigInt::from(value as u128)
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_magnitude(&self.limbs, &other.limbs),
+            (true, true) => cmp_magnitude(&other.limbs, &self.limbs),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for BigInt {
+    type Output = BigInt;
+
+    fn add(self, rhs: BigInt) -> BigInt {
+        if self.negative == rhs.negative {
+            return BigInt::from_parts(self.negative, add_magnitude(&self.limbs, &rhs.limbs));
+        }
+        match cmp_magnitude(&self.limbs, &rhs.limbs) {
+            Ordering::Less => {
+                BigInt::from_parts(rhs.negative, sub_magnitude(&rhs.limbs, &self.limbs))
+            }
+            _ => BigInt::from_parts(self.negative, sub_magnitude(&self.limbs, &rhs.limbs)),
+        }
+    }
+}
+
+impl Sub for BigInt {
+    type Output = BigInt;
+
+    fn sub(self, rhs: BigInt) -> BigInt {
+        self + -rhs
+    }
+}
+
+impl Mul for BigInt {
+    type Output = BigInt;
+
+    fn mul(self, rhs: BigInt) -> BigInt {
+        BigInt::from_parts(
+            self.negative != rhs.negative,
+            mul_magnitude(&self.limbs, &rhs.limbs),
+        )
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        let negative = !self.negative;
+        BigInt::from_parts(negative, self.limbs)
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+
+        if self.is_zero() {
+            return f.write_str("0");
+        }
+
+        // split the magnitude into base-10^19 chunks, lowest first
+        let mut chunks = Vec::new();
+        let mut rest = self.limbs.clone();
+        while !rest.is_empty() {
+            let (quotient, remainder) = div_rem_limb(&rest, CHUNK);
+            chunks.push(remainder);
+            rest = quotient;
+            while rest.last() == Some(&0) {
+                rest.pop();
+            }
+        }
+
+        let mut digits = String::new();
+        let mut chunks = chunks.iter().rev();
+        if let Some(first) = chunks.next() {
+            write!(digits, "{}", first)?;
+        }
+        for chunk in chunks {
+            write!(digits, "{:019}", chunk)?;
+        }
+        f.pad_integral(!self.negative, "", &digits)
+    }
+}
+
+/// Formats the magnitude of a big integer in hexadecimal, with the sign of
+/// the integer and with `0x` for the alternate flag.
+macro_rules! impl_fmt_hex {
+    ($($trait:ident => $format:literal, $limb_format:literal),*) => {
+        $(
+            impl fmt::$trait for BigInt {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    let mut digits = String::new();
+                    let mut limbs = self.limbs.iter().rev();
+                    match limbs.next() {
+                        Some(first) => write!(digits, $format, first)?,
+                        None => digits.push('0'),
+                    }
+                    for limb in limbs {
+                        write!(digits, $limb_format, limb)?;
+                    }
+                    f.pad_integral(!self.negative, "0x", &digits)
+                }
+            }
+        )*
+    };
+}
+
+impl_fmt_hex! {
+    LowerHex => "{:x}", "{:016x}",
+    UpperHex => "{:X}", "{:016X}"
+}
+
+impl fmt::Debug for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::{string::ToString, vec::Vec};
+    use core::cmp::Ordering;
+
+    use super::{BigInt, cmp_magnitude};
+
+    fn big(s: &str) -> BigInt {
+        match s.strip_prefix('-') {
+            Some(digits) => BigInt::from_digits(true, digits, 10).unwrap(),
+            None => BigInt::from_digits(false, s, 10).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(big("0"), BigInt::from(0u128));
+        assert_eq!(big("-0"), BigInt::from(0u128));
+        assert_eq!(big("-0").to_string(), "0");
+        assert_eq!(big("000123").to_string(), "123");
+        assert_eq!(
+            big("-170141183460469231731687303715884105728"),
+            BigInt::from(i128::MIN)
+        );
+        assert_eq!(
+            BigInt::from_digits(false, "ffffffffffffffffffffffffffffffff", 16),
+            Some(BigInt::from(u128::MAX))
+        );
+        assert_eq!(BigInt::from_digits(false, "12a", 10), None);
+        assert_eq!(BigInt::from_digits(false, "", 10), None);
+
+        let s = "-123456789012345678901234567890123456789012345678901234567890";
+        assert_eq!(big(s).to_string(), s);
+        assert_eq!(alloc::format!("{:>70}", big(s)).len(), 70);
+        assert_eq!(
+            big("10000000000000000000000000000000000000").to_string(),
+            "10000000000000000000000000000000000000"
+        );
+
+        let hex = "-1000000000000000f0000000000000000";
+        assert_eq!(
+            alloc::format!("{:x}", BigInt::from_digits(true, &hex[1..], 16).unwrap()),
+            hex
+        );
+        assert_eq!(
+            alloc::format!("{:#X}", big("18446744073709551616")),
+            "0x10000000000000000"
+        );
+        assert_eq!(alloc::format!("{:08x}", big("-255")), "-00000ff");
+        assert_eq!(alloc::format!("{:x}", big("0")), "0");
+
+        assert_eq!(big("-123").mul_pow10(0), big("-123"));
+        assert_eq!(big("0").mul_pow10(1000), big("0"));
+        let digits = alloc::format!("-123{}", "0".repeat(1000));
+        assert_eq!(big("-123").mul_pow10(1000), big(&digits));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let values = [
+            i128::MIN / 3,
+            -(1 << 64) - 1,
+            -(1 << 64),
+            -12345678901234567890,
+            -1,
+            0,
+            1,
+            3,
+            1 << 63,
+            u64::MAX as i128,
+            1 << 64,
+            98765432109876543210987654321,
+            i128::MAX / 3,
+        ];
+        for a in values {
+            for b in values {
+                let (x, y) = (BigInt::from(a), BigInt::from(b));
+                assert_eq!(x.clone() + y.clone(), BigInt::from(a + b));
+                assert_eq!(x.clone() - y.clone(), BigInt::from(a - b));
+                assert_eq!(x.cmp(&y), a.cmp(&b));
+                if let Some(product) = a.checked_mul(b) {
+                    assert_eq!(x.clone() * y.clone(), BigInt::from(product));
+                }
+                if b != 0 {
+                    let (q, r) = x.checked_div_rem(&y).unwrap();
+                    assert_eq!((q, r), (BigInt::from(a / b), BigInt::from(a % b)));
+                } else {
+                    assert_eq!(x.checked_div_rem(&y), None);
+                }
+            }
+        }
+
+        let a = big("-123456789012345678901234567890123456789");
+        let b = big("987654321098765432109876543210");
+        let product = big("-121932631137021795226185032733744855963362292333223746380111126352690");
+        assert_eq!(a.clone() * b.clone(), product);
+        assert_eq!(product.clone().checked_div(b.clone()), Some(a.clone()));
+        assert_eq!(product.clone().checked_div(a), Some(b.clone()));
+        assert_eq!((product.clone() - big("5")).checked_rem(b), Some(big("-5")));
+        assert_eq!(-product.clone() + product, BigInt::from(0u128));
+
+        // multi-limb divisors, including limbs that make the estimates of the
+        // quotient too large
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            match seed % 4 {
+                0 => 0,
+                1 => u64::MAX,
+                2 => 1 << 63,
+                _ => seed,
+            }
+        };
+        for i in 0..500 {
+            let mut limbs = |len: usize| (0..len).map(|_| next()).collect::<Vec<_>>();
+            let b = BigInt::from_parts(i % 3 == 0, limbs(2 + i % 5));
+            let q = BigInt::from_parts(i % 2 == 0, limbs(1 + i % 7));
+            let r = BigInt::from_parts(false, limbs(1 + i % 5));
+            if b.is_zero() || cmp_magnitude(&r.limbs, &b.limbs) != Ordering::Less {
+                continue;
+            }
+            // the remainder has the sign of the dividend
+            let r = if q.is_negative() != b.is_negative() {
+                -r
+            } else {
+                r
+            };
+            let a = q.clone() * b.clone() + r.clone();
+            let q = if a.is_zero() { BigInt::from(0u128) } else { q };
+            assert_eq!(a.checked_div_rem(&b), Some((q, r)));
+        }
+    }
+
+    #[test]
+    fn test_float_conversions() {
+        let two_128 = BigInt::from(u128::MAX) + BigInt::from(1u128);
+        assert_eq!(two_128.to_f64(), 2f64.powi(128));
+        assert_eq!(two_128.to_f64_exact(), Some(2f64.powi(128)));
+        assert_eq!(two_128.to_f32_exact(), None);
+        assert_eq!(two_128.to_f32(), f32::INFINITY);
+        assert_eq!(BigInt::from(u128::MAX).to_f32(), f32::INFINITY);
+        assert_eq!(BigInt::from(u128::MAX).to_f64_exact(), None);
+        assert_eq!(
+            (-BigInt::from(1u128 << 127)).to_f32_exact(),
+            Some(-2f32.powi(127))
+        );
+
+        // halfway between two floats, ties to even
+        let halfway = BigInt::from((1u128 << 70) + (1 << 17));
+        assert_eq!(halfway.to_f64(), 2f64.powi(70));
+        let above = halfway + BigInt::from(1u128);
+        assert_eq!(above.to_f64(), 2f64.powi(70) + 2f64.powi(18));
+        for v in [u128::MAX, 12345678901234567890123456789, (1 << 100) + 1] {
+            assert_eq!(BigInt::from(v).to_f64(), v as f64);
+            assert_eq!(BigInt::from(v).to_f32(), v as f32);
+        }
+
+        let huge = big(&alloc::format!("1{}", "0".repeat(400)));
+        assert_eq!(huge.to_f64(), f64::INFINITY);
+        assert_eq!((-huge).to_f64(), f64::NEG_INFINITY);
+
+        assert_eq!(
+            BigInt::from_f64(1e30),
+            big("1000000000000000019884624838656")
+        );
+        assert_eq!(BigInt::from_f64(-2.5), BigInt::from(-2i128));
+        assert_eq!(BigInt::from_f64(0.5), BigInt::from(0u128));
+        assert_eq!(BigInt::from_f64(f64::MAX).to_f64(), f64::MAX);
+        assert_eq!(
+            BigInt::from_f64(-(2f64.powi(64))),
+            BigInt::from(-(1i128 << 64))
+        );
+    }
+}
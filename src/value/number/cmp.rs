@@ -0,0 +1,494 @@
+use core::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+
+#[cfg(feature = "bigint")]
+use super::BigInt;
+#[cfg(feature = "decimal")]
+use super::Decimal;
+use super::{Number, utils::consts::TWO_POW_64_F64};
+
+/// Compares an integer with a finite float exactly.
+fn cmp_int_float(int: i128, float: f64) -> Ordering {
+    // floats out of `[-2^64, 2^64)` are beyond all integer `Number`s, and the
+    // others are truncated exactly
+    if float >= TWO_POW_64_F64 {
+        return Ordering::Less;
+    }
+    if float < -TWO_POW_64_F64 {
+        return Ordering::Greater;
+    }
+
+    let truncated = float as i128;
+    int.cmp(&truncated).then_with(|| {
+        // the fractional part is exact, as `truncated` has fewer significant
+        // bits than `float`
+        let fraction = float - truncated as f64;
+        0.0.partial_cmp(&fraction).unwrap_or(Ordering::Equal)
+    })
+}
+
+impl Number {
+    /// Returns the rank of the number in [`total_cmp`](Number::total_cmp) for
+    /// numbers that are not compared by value.
+    const fn rank(&self) -> u8 {
+        match self {
+            Number::Inf { negative: true } => 0,
+            Number::PosInt(_) | Number::NegInt(_) | Number::Float(_) => 1,
+            #[cfg(feature = "bigint")]
+            Number::BigInt(_) => 1,
+            #[cfg(feature = "decimal")]
+            Number::Decimal(_) => 1,
+            Number::Inf { negative: false } => 2,
+            Number::NaN => 3,
+        }
+    }
+
+    /// Returns the order of numbers with the same value in
+    /// [`total_cmp`](Number::total_cmp): integers, decimals, then floats.
+    const fn kind(&self) -> u8 {
+        match self {
+            #[cfg(feature = "decimal")]
+            Number::Decimal(_) => 1,
+            Number::Float(_) => 2,
+            _ => 0,
+        }
+    }
+
+    /// Compares two numbers by their mathematical values, regardless of whether
+    /// they are integers or floats.
+    ///
+    /// Returns `None` if exactly one of the numbers is `nan`. As everywhere
+    /// else in Twic, `nan` is considered equal to `nan`. The comparison is
+    /// exact, without converting integers to floats.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(Number::from(1).numeric_cmp(&Number::from(1.0)), Some(Ordering::Equal));
+    /// assert_eq!(Number::from(3).numeric_cmp(&Number::from(2.5)), Some(Ordering::Greater));
+    /// assert_eq!(
+    ///     Number::from(u64::MAX).numeric_cmp(&Number::from(18446744073709551615.0)),
+    ///     Some(Ordering::Less),
+    /// );
+    /// assert_eq!(Number::NaN.numeric_cmp(&Number::from(1)), None);
+    /// ```
+    pub fn numeric_cmp(&self, other: &Number) -> Option<Ordering> {
+        match (self, other) {
+            (Number::NaN, Number::NaN) => Some(Ordering::Equal),
+            (Number::NaN, _) | (_, Number::NaN) => None,
+            _ => Some(self.cmp_finite(other)),
+        }
+    }
+
+    /// Compares two numbers that are not `nan` by their mathematical values.
+    fn cmp_finite(&self, other: &Number) -> Ordering {
+        #[cfg(feature = "decimal")]
+        if let Some(ordering) = self.cmp_decimal(other) {
+            return ordering;
+        }
+
+        #[cfg(feature = "bigint")]
+        if let Some(ordering) = self.cmp_big(other) {
+            return ordering;
+        }
+
+        match (self.get_i128(), other.get_i128(), self, other) {
+            (Some(a), Some(b), _, _) => a.cmp(&b),
+            (Some(a), None, _, Number::Float(b)) => cmp_int_float(a, *b),
+            (None, Some(b), Number::Float(a), _) => cmp_int_float(b, *a).reverse(),
+            (None, None, Number::Float(a), Number::Float(b)) => {
+                a.partial_cmp(b).unwrap_or(Ordering::Equal)
+            }
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+
+    /// Compares a big integer with a finite number, exactly.
+    #[cfg(feature = "bigint")]
+    fn cmp_big(&self, other: &Number) -> Option<Ordering> {
+        // big integers are beyond all other integer `Number`s, and floats of
+        // the same magnitude are integers, so comparing with a truncated float
+        // is exact
+        let sign = |n: &BigInt| {
+            if n.is_negative() {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        };
+        match (self, other) {
+            (Number::BigInt(a), Number::BigInt(b)) => Some(a.cmp(b)),
+            (Number::BigInt(a), Number::PosInt(_) | Number::NegInt(_)) => Some(sign(a)),
+            (Number::PosInt(_) | Number::NegInt(_), Number::BigInt(b)) => Some(sign(b).reverse()),
+            (Number::BigInt(a), Number::Float(b)) => Some(a.cmp(&BigInt::from_f64(*b))),
+            (Number::Float(a), Number::BigInt(b)) => Some(BigInt::from_f64(*a).cmp(b)),
+            _ => None,
+        }
+    }
+
+    /// Compares a decimal with a number that is not `nan`, exactly.
+    #[cfg(feature = "decimal")]
+    fn cmp_decimal(&self, other: &Number) -> Option<Ordering> {
+        let cmp = |a: &Decimal, b: &Number| match b {
+            Number::PosInt(_) | Number::NegInt(_) => a.cmp_i128(b.as_i128()),
+            Number::Float(f) => a.cmp_f64(*f),
+            Number::Decimal(b) => a.cmp(b),
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => a.cmp_big_int(n),
+            Number::Inf { negative } => {
+                if *negative {
+                    Ordering::Greater
+                } else {
+                    Ordering::Less
+                }
+            }
+            Number::NaN => Ordering::Less,
+        };
+        match (self, other) {
+            (Number::Decimal(a), _) => Some(cmp(a, other)),
+            (_, Number::Decimal(b)) => Some(cmp(b, self).reverse()),
+            _ => None,
+        }
+    }
+
+    /// Checks if two numbers have the same mathematical value, regardless of
+    /// whether they are integers or floats.
+    ///
+    /// Unlike `==`, an integer equals a float of the same value. As everywhere
+    /// else in Twic, `nan` equals `nan`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert!(Number::from(1).numeric_eq(&Number::from(1.0)));
+    /// assert!(Number::from(0).numeric_eq(&Number::from(-0.0)));
+    /// assert!(!Number::from(u64::MAX).numeric_eq(&Number::from(u64::MAX as f64)));
+    /// assert!(Number::NaN.numeric_eq(&Number::NaN));
+    /// ```
+    pub fn numeric_eq(&self, other: &Number) -> bool {
+        self.numeric_cmp(other) == Some(Ordering::Equal)
+    }
+
+    /// Feeds the number into the given [`Hasher`], consistently with
+    /// [`numeric_eq`](Number::numeric_eq): numbers with the same mathematical
+    /// value hash identically.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::hash::{BuildHasher, Hasher, RandomState};
+    /// use twic::value::Number;
+    ///
+    /// let s = RandomState::new();
+    /// let hash = |n: Number| {
+    ///     let mut h = s.build_hasher();
+    ///     n.numeric_hash(&mut h);
+    ///     h.finish()
+    /// };
+    /// assert_eq!(hash(Number::from(8080)), hash(Number::from(8080.0)));
+    /// ```
+    pub fn numeric_hash<H: Hasher>(&self, state: &mut H) {
+        self.normalize().hash(state);
+    }
+
+    /// Compares two numbers in a total order.
+    ///
+    /// Numbers are ordered by their mathematical values, from `-inf` to `inf`,
+    /// followed by `nan`. An integer is ordered before a decimal of the same
+    /// value, which is ordered before a float of the same value, and `0.0` and
+    /// `-0.0` are considered equal, so that the order is consistent with
+    /// [`PartialEq`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use twic::value::Number;
+    ///
+    /// let mut v = [
+    ///     Number::NaN,
+    ///     Number::from(1.0),
+    ///     Number::Inf { negative: true },
+    ///     Number::from(1),
+    ///     Number::from(-2.5),
+    ///     Number::Inf { negative: false },
+    /// ];
+    /// v.sort_by(Number::total_cmp);
+    /// assert_eq!(
+    ///     v,
+    ///     [
+    ///         Number::Inf { negative: true },
+    ///         Number::from(-2.5),
+    ///         Number::from(1),
+    ///         Number::from(1.0),
+    ///         Number::Inf { negative: false },
+    ///         Number::NaN,
+    ///     ]
+    /// );
+    /// assert_eq!(Number::from(0.0).total_cmp(&Number::from(-0.0)), Ordering::Equal);
+    /// ```
+    pub fn total_cmp(&self, other: &Number) -> Ordering {
+        match (self, other) {
+            (Number::NaN, _) | (_, Number::NaN) => self.rank().cmp(&other.rank()),
+            _ => self
+                .cmp_finite(other)
+                .then_with(|| self.kind().cmp(&other.kind())),
+        }
+    }
+}
+
+impl PartialOrd for Number {
+    /// Compares two numbers in the order of [`Number::total_cmp`], except that
+    /// `nan` is not comparable with other numbers.
+    ///
+    /// This order is consistent with [`PartialEq`], so an integer is less than
+    /// a float of the same value. Use [`Number::numeric_cmp`] to compare
+    /// numbers by their values only.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert!(Number::from(3) > Number::from(2.5));
+    /// assert!(Number::NegInt(0) < Number::from(-1.8446744073709552e19));
+    /// assert!(Number::from(1) < Number::from(1.0));
+    /// assert!(!(Number::NaN < Number::from(1)) && !(Number::NaN >= Number::from(1)));
+    /// ```
+    fn partial_cmp(&self, other: &Number) -> Option<Ordering> {
+        match (self, other) {
+            (Number::NaN, Number::NaN) => Some(Ordering::Equal),
+            (Number::NaN, _) | (_, Number::NaN) => None,
+            _ => Some(self.total_cmp(other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+    use core::cmp::Ordering;
+
+    use crate::value::{Number, test_util::hashed};
+
+    fn numeric_hash(n: &Number) -> Vec<u8> {
+        hashed(|h| n.numeric_hash(h))
+    }
+
+    #[test]
+    fn test_numeric_eq() {
+        let numbers = [
+            Number::from(0),
+            Number::from(0.0),
+            Number::from(-0.0),
+            Number::from(1),
+            Number::from(1.0),
+            Number::from(1.5),
+            Number::NegInt(0),
+            Number::from(-18446744073709551616.0),
+            Number::from(u64::MAX),
+            Number::from(18446744073709551616.0),
+            Number::from(1e30),
+            Number::NaN,
+            Number::Inf { negative: false },
+            Number::Inf { negative: true },
+        ];
+
+        for a in &numbers {
+            for b in &numbers {
+                let eq = a.numeric_eq(b);
+                assert_eq!(eq, a.numeric_cmp(b) == Some(Ordering::Equal));
+                assert_eq!(eq, a.normalize() == b.normalize(), "{:?} {:?}", a, b);
+                if a == b {
+                    assert!(eq, "{:?} {:?}", a, b);
+                }
+                if eq {
+                    assert_eq!(numeric_hash(a), numeric_hash(b), "{:?} {:?}", a, b);
+                }
+            }
+        }
+
+        assert!(Number::from(1).numeric_eq(&Number::from(1.0)));
+        assert!(!Number::from(u64::MAX).numeric_eq(&Number::from(18446744073709551616.0)));
+        assert_ne!(
+            numeric_hash(&Number::from(1)),
+            numeric_hash(&Number::from(1.5))
+        );
+    }
+
+    #[test]
+    fn test_numeric_cmp() {
+        let cmp = |a: Number, b: Number| a.numeric_cmp(&b).unwrap();
+        let two_64 = 18446744073709551616.0;
+
+        assert_eq!(
+            cmp(Number::from(u64::MAX), Number::from(two_64)),
+            Ordering::Less
+        );
+        assert_eq!(
+            cmp(Number::NegInt(0), Number::from(-two_64)),
+            Ordering::Equal
+        );
+        assert_eq!(
+            cmp(Number::NegInt(1), Number::from(-two_64)),
+            Ordering::Greater
+        );
+        assert_eq!(
+            cmp(Number::NegInt(0), Number::from(-two_64 * 2.0)),
+            Ordering::Greater
+        );
+        assert_eq!(
+            cmp(Number::from(1u64 << 53), Number::from(9007199254740992.0)),
+            Ordering::Equal
+        );
+        assert_eq!(
+            cmp(
+                Number::from((1u64 << 53) + 1),
+                Number::from(9007199254740992.0)
+            ),
+            Ordering::Greater
+        );
+        assert_eq!(cmp(Number::from(2), Number::from(2.5)), Ordering::Less);
+        assert_eq!(cmp(Number::from(-2), Number::from(-2.5)), Ordering::Greater);
+        assert_eq!(cmp(Number::from(-2), Number::from(-1.5)), Ordering::Less);
+        assert_eq!(cmp(Number::from(0), Number::from(-0.0)), Ordering::Equal);
+        assert_eq!(
+            cmp(Number::from(0), Number::from(-1e-300)),
+            Ordering::Greater
+        );
+        assert_eq!(cmp(Number::from(1.5), Number::from(1)), Ordering::Greater);
+        assert_eq!(
+            cmp(Number::from(f64::MAX), Number::Inf { negative: false }),
+            Ordering::Less
+        );
+        assert_eq!(
+            cmp(Number::NegInt(0), Number::Inf { negative: true }),
+            Ordering::Greater
+        );
+        assert_eq!(cmp(Number::NaN, Number::NaN), Ordering::Equal);
+        assert_eq!(Number::from(1).numeric_cmp(&Number::NaN), None);
+    }
+
+    #[test]
+    fn test_total_cmp() {
+        let ordered = [
+            Number::Inf { negative: true },
+            Number::from(-1e300),
+            Number::NegInt(0),
+            Number::from(-18446744073709551616.0),
+            Number::from(-1),
+            Number::from(-0.5),
+            Number::from(0),
+            Number::from(0.0),
+            Number::from(u64::MAX),
+            Number::from(18446744073709551616.0),
+            Number::Inf { negative: false },
+            Number::NaN,
+        ];
+
+        for (i, a) in ordered.iter().enumerate() {
+            for (j, b) in ordered.iter().enumerate() {
+                assert_eq!(a.total_cmp(b), i.cmp(&j), "{:?} {:?}", a, b);
+                assert_eq!(a.total_cmp(b) == Ordering::Equal, a == b);
+            }
+        }
+
+        assert_eq!(
+            Number::from(-0.0).total_cmp(&Number::from(0.0)),
+            Ordering::Equal
+        );
+        assert_eq!(
+            Number::from(-0.0).partial_cmp(&Number::from(0)),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            Number::NaN.partial_cmp(&Number::Inf { negative: false }),
+            None
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "decimal")]
+    fn test_decimals() {
+        let d = |s: &str| Number::Decimal(s.parse().unwrap());
+        let cmp = |a: &Number, b: &Number| a.numeric_cmp(b).unwrap();
+
+        assert_eq!(cmp(&d("0.1"), &Number::from(0.1)), Ordering::Less);
+        assert_eq!(cmp(&d("0.5"), &Number::from(0.5)), Ordering::Equal);
+        assert_eq!(cmp(&d("-2.0"), &Number::from(-2)), Ordering::Equal);
+        assert_eq!(cmp(&d("-2.5"), &Number::from(-2)), Ordering::Less);
+        assert_eq!(
+            cmp(&d("18446744073709551615.5"), &Number::from(u64::MAX)),
+            Ordering::Greater
+        );
+        assert_eq!(cmp(&d("1e400"), &Number::from(f64::MAX)), Ordering::Greater);
+        assert_eq!(
+            cmp(&d("1e400"), &Number::Inf { negative: false }),
+            Ordering::Less
+        );
+        assert_eq!(cmp(&d("-0.0"), &Number::from(0)), Ordering::Equal);
+        assert_eq!(d("1.5").numeric_cmp(&Number::NaN), None);
+
+        for (a, b) in [
+            (d("0.5"), Number::from(0.5)),
+            (d("3.0"), Number::from(3)),
+            (d("1e22"), Number::from(1e22)),
+        ] {
+            assert!(a.numeric_eq(&b), "{:?} {:?}", a, b);
+            assert_eq!(a.normalize(), b.normalize());
+            assert_eq!(numeric_hash(&a), numeric_hash(&b));
+        }
+        assert!(!d("0.1").numeric_eq(&Number::from(0.1)));
+
+        // integers, then decimals, then floats for equal values
+        assert_eq!(d("1.0").total_cmp(&Number::from(1)), Ordering::Greater);
+        assert_eq!(d("1.0").total_cmp(&Number::from(1.0)), Ordering::Less);
+        assert_eq!(d("0.1").total_cmp(&Number::from(0.1)), Ordering::Less);
+    }
+
+    #[test]
+    #[cfg(feature = "bigint")]
+    fn test_big_integers() {
+        let big = |s: &str| s.parse::<Number>().unwrap();
+        let cmp = |a: &Number, b: &Number| a.numeric_cmp(b).unwrap();
+        let two_64 = big("18446744073709551616");
+        let e30 = big("1000000000000000019884624838656");
+
+        assert_eq!(
+            cmp(&two_64, &Number::from(18446744073709551616.0)),
+            Ordering::Equal
+        );
+        assert_eq!(cmp(&two_64, &Number::from(u64::MAX)), Ordering::Greater);
+        assert_eq!(
+            cmp(&big("-18446744073709551617"), &Number::NegInt(0)),
+            Ordering::Less
+        );
+        assert_eq!(cmp(&e30, &Number::from(1e30)), Ordering::Equal);
+        assert_eq!(
+            cmp(&(e30.clone() + Number::from(1)), &Number::from(1e30)),
+            Ordering::Greater
+        );
+        assert_eq!(cmp(&-e30.clone(), &Number::from(-1.5)), Ordering::Less);
+        assert_eq!(cmp(&e30, &Number::Inf { negative: false }), Ordering::Less);
+        assert_eq!(
+            cmp(&-e30.clone(), &Number::Inf { negative: true }),
+            Ordering::Greater
+        );
+        assert_eq!(cmp(&-e30.clone(), &two_64), Ordering::Less);
+        assert_eq!(
+            two_64.total_cmp(&Number::from(18446744073709551616.0)),
+            Ordering::Less
+        );
+
+        assert_eq!(Number::from(1e30).normalize(), e30);
+        assert!(e30.numeric_eq(&Number::from(1e30)));
+        assert_eq!(numeric_hash(&e30), numeric_hash(&Number::from(1e30)));
+    }
+}
@@ -0,0 +1,787 @@
+use alloc::{format, string::String};
+use core::{
+    cmp::Ordering,
+    fmt::{self, Write},
+    hash::{Hash, Hasher},
+    ops::Neg,
+};
+
+#[cfg(feature = "bigint")]
+use alloc::string::ToString;
+
+#[cfg(feature = "bigint")]
+use super::BigInt;
+
+/// An exact decimal number `±mantissa × 10^exponent`, held by
+/// [`Number::Decimal`](super::Number) for number literals with a fraction or
+/// an exponent.
+///
+/// Available with the `decimal` feature. `Decimal`s are obtained by parsing
+/// Twic text with [`from_str_decimal`](crate::de::from_str_decimal) or
+/// [`Reader::decimals`](crate::de::Reader::decimals), or by parsing a literal
+/// into a `Decimal`, and keep the written value exactly as long as its
+/// significant digits fit in a `u128`:
+///
+/// ```
+/// use twic::value::{Decimal, Number};
+///
+/// let d: Decimal = "0.10".parse().unwrap();
+/// assert_eq!(d, Decimal::new(1, -1).unwrap());
+/// let sum = Number::Decimal(d) + Number::Decimal("0.2".parse().unwrap());
+/// assert_eq!(sum.to_string(), "0.3");
+/// ```
+///
+/// Equality, hashing and ordering are by value, except that `0.0` and `-0.0`
+/// are distinguished by [`Display`](fmt::Display) only, like floats.
+#[derive(Clone, Copy)]
+pub struct Decimal {
+    /// May be set for zero, which is `-0.0` then.
+    negative: bool,
+    /// Without trailing zeros.
+    mantissa: u128,
+    /// Always zero for zero.
+    exponent: i16,
+    /// The nearest `f64`, computed on creation so that conversions can be
+    /// `const`.
+    float: f64,
+    /// The nearest `f32`, which rounding `float` could miss by double rounding.
+    float32: f32,
+}
+
+/// The significant digits of a value, compared as `±0.digits × 10^point`.
+struct Significand<'a> {
+    negative: bool,
+    /// Without leading or trailing zeros, and empty for zero.
+    digits: &'a [u8],
+    point: i64,
+}
+
+impl Significand<'_> {
+    const fn sign(&self) -> i8 {
+        match (self.digits.is_empty(), self.negative) {
+            (true, _) => 0,
+            (false, true) => -1,
+            (false, false) => 1,
+        }
+    }
+
+    fn cmp(&self, other: &Significand<'_>) -> Ordering {
+        let sign = self.sign();
+        if sign != other.sign() || sign == 0 {
+            return sign.cmp(&other.sign());
+        }
+        let magnitude = self
+            .point
+            .cmp(&other.point)
+            .then_with(|| self.digits.cmp(other.digits));
+        if sign < 0 {
+            magnitude.reverse()
+        } else {
+            magnitude
+        }
+    }
+}
+
+/// Writes the decimal digits of `n` at the end of `buf`, returning them.
+fn write_digits(n: u128, buf: &mut [u8; 39]) -> &[u8] {
+    let mut i = buf.len();
+    let mut n = n;
+    loop {
+        i -= 1;
+        buf[i] = b'0' + (n % 10) as u8;
+        n /= 10;
+        if n == 0 {
+            return &buf[i..];
+        }
+    }
+}
+
+/// The literal of the magnitude of a decimal, `<mantissa>e<exponent>`, written
+/// on the stack: a `u128` has at most 39 digits and an `i16` at most 6 chars.
+struct Literal {
+    bytes: [u8; 46],
+    len: usize,
+}
+
+impl Default for Literal {
+    fn default() -> Self {
+        Literal {
+            bytes: [0; 46],
+            len: 0,
+        }
+    }
+}
+
+impl Literal {
+    fn as_str(&self) -> &str {
+        // only `str`s are written
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or_default()
+    }
+}
+
+impl Write for Literal {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        let dest = self.bytes.get_mut(self.len..end).ok_or(fmt::Error)?;
+        dest.copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Creates the significand of an integer from its decimal digits.
+fn integer_significand(negative: bool, digits: &[u8]) -> Significand<'_> {
+    let significant = digits.len() - digits.iter().rev().take_while(|&&d| d == b'0').count();
+    Significand {
+        negative,
+        digits: &digits[..significant],
+        point: digits.len() as i64,
+    }
+}
+
+/// Gets the parts of the exact decimal value of a finite float, if its
+/// significant digits fit in a `u128`.
+const fn float_parts(f: f64) -> Option<(bool, u128, i16)> {
+    if !f.is_finite() {
+        return None;
+    }
+
+    let bits = f.to_bits();
+    let negative = bits >> 63 != 0;
+    let biased = ((bits >> 52) & 0x7ff) as i32;
+    let fraction = bits & ((1 << 52) - 1);
+    // `f` is `mantissa * 2^shift`
+    let (mut mantissa, mut shift) = if biased == 0 {
+        (fraction, -1074)
+    } else {
+        (fraction | 1 << 52, biased - 1075)
+    };
+    if mantissa == 0 {
+        return Some((negative, 0, 0));
+    }
+    shift += mantissa.trailing_zeros() as i32;
+    mantissa >>= mantissa.trailing_zeros();
+
+    if shift >= 0 {
+        if 64 - mantissa.leading_zeros() as i32 + shift > 128 {
+            return None;
+        }
+        let mut n = (mantissa as u128) << shift;
+        let mut exponent = 0;
+        while n.is_multiple_of(10) {
+            n /= 10;
+            exponent += 1;
+        }
+        Some((negative, n, exponent))
+    } else {
+        // `mantissa * 2^-k` is `mantissa * 5^k * 10^-k`, without trailing
+        // zeros as `mantissa` is odd
+        let k = -shift as u32;
+        match 5u128.checked_pow(k) {
+            Some(p) => match (mantissa as u128).checked_mul(p) {
+                Some(n) => Some((negative, n, -(k as i16))),
+                None => None,
+            },
+            None => None,
+        }
+    }
+}
+
+impl Decimal {
+    /// Creates a decimal from its parts, removing trailing zeros from the
+    /// mantissa. Returns `None` if the exponent is out of the range of `i16`.
+    pub(crate) fn from_parts(negative: bool, mantissa: u128, exponent: i64) -> Option<Decimal> {
+        let (mut mantissa, mut exponent) = (mantissa, exponent);
+        if mantissa == 0 {
+            exponent = 0;
+        }
+        while mantissa != 0 && mantissa.is_multiple_of(10) {
+            mantissa /= 10;
+            exponent += 1;
+        }
+        let exponent = i16::try_from(exponent).ok()?;
+
+        // the literal fits in a buffer on the stack
+        let mut literal = Literal::default();
+        let _ = write!(literal, "{}e{}", mantissa, exponent);
+        let (float, float32) = match literal.as_str().parse::<f64>() {
+            Ok(f) => (f, literal.as_str().parse().unwrap_or(f32::NAN)),
+            Err(_) => (f64::NAN, f32::NAN),
+        };
+        Some(Decimal {
+            negative,
+            mantissa,
+            exponent,
+            float: if negative { -float } else { float },
+            float32: if negative { -float32 } else { float32 },
+        })
+    }
+
+    /// Converts a finite float to a `Decimal`, if its exact value has
+    /// significant digits fitting in a `u128`.
+    pub(crate) fn from_f64(f: f64) -> Option<Decimal> {
+        let (negative, mantissa, exponent) = float_parts(f)?;
+        Decimal::from_parts(negative, mantissa, exponent as i64)
+    }
+
+    /// Creates the decimal `mantissa × 10^exponent`. Returns `None` if the
+    /// exponent goes out of the range of `i16` after removing trailing zeros
+    /// from the mantissa.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Decimal;
+    ///
+    /// let d = Decimal::new(-1500, -3).unwrap();
+    /// assert_eq!((d.mantissa(), d.exponent()), (15, -1));
+    /// assert_eq!(d.to_string(), "-1.5");
+    /// assert_eq!(Decimal::new(10, i16::MAX), None);
+    /// ```
+    pub fn new(mantissa: i128, exponent: i16) -> Option<Decimal> {
+        Decimal::from_parts(mantissa < 0, mantissa.unsigned_abs(), exponent as i64)
+    }
+
+    /// Gets the magnitude of the mantissa, which has no trailing zeros.
+    pub const fn mantissa(&self) -> u128 {
+        self.mantissa
+    }
+
+    /// Gets the exponent, which is zero for zero.
+    pub const fn exponent(&self) -> i16 {
+        self.exponent
+    }
+
+    /// Checks if the decimal is negative, excluding `-0.0`.
+    pub const fn is_negative(&self) -> bool {
+        self.negative && self.mantissa != 0
+    }
+
+    /// Checks if the decimal is zero, either `0.0` or `-0.0`.
+    pub const fn is_zero(&self) -> bool {
+        self.mantissa == 0
+    }
+
+    /// Checks if the decimal has no fractional part.
+    pub const fn is_integer(&self) -> bool {
+        self.exponent >= 0
+    }
+
+    /// Converts the decimal to the nearest `f64`, ties to even.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Decimal;
+    ///
+    /// assert_eq!(Decimal::new(1, -1).unwrap().to_f64(), 0.1);
+    /// assert_eq!(Decimal::new(1, 400).unwrap().to_f64(), f64::INFINITY);
+    /// ```
+    pub const fn to_f64(self) -> f64 {
+        self.float
+    }
+
+    /// Converts the decimal to the nearest `f32`, ties to even.
+    pub const fn to_f32(self) -> f32 {
+        self.float32
+    }
+
+    /// Converts the decimal to an `f64`, if it can be represented exactly.
+    pub(crate) const fn to_f64_exact(self) -> Option<f64> {
+        match float_parts(self.float) {
+            Some((_, mantissa, exponent))
+                if mantissa == self.mantissa && exponent == self.exponent =>
+            {
+                Some(self.float)
+            }
+            _ => None,
+        }
+    }
+
+    /// Converts the decimal to an `f32`, if it can be represented exactly.
+    pub(crate) const fn to_f32_exact(self) -> Option<f32> {
+        match float_parts(self.float32 as f64) {
+            Some((_, mantissa, exponent))
+                if mantissa == self.mantissa && exponent == self.exponent =>
+            {
+                Some(self.float32)
+            }
+            _ => None,
+        }
+    }
+
+    /// Gets the magnitude of the integer part, or `None` if it does not fit in
+    /// a `u128`.
+    const fn trunc_magnitude(&self) -> Option<u128> {
+        if self.exponent >= 0 {
+            match 10u128.checked_pow(self.exponent as u32) {
+                Some(p) => self.mantissa.checked_mul(p),
+                None => None,
+            }
+        } else {
+            match 10u128.checked_pow(-(self.exponent as i32) as u32) {
+                Some(p) => Some(self.mantissa / p),
+                None => Some(0),
+            }
+        }
+    }
+
+    /// Gets the decimal as an `i128`, if it is an integer that fits.
+    pub(crate) const fn to_i128(self) -> Option<i128> {
+        if !self.is_integer() {
+            return None;
+        }
+        match self.trunc_magnitude() {
+            Some(m) if !self.negative && m <= i128::MAX as u128 => Some(m as i128),
+            Some(m) if self.negative && m <= i128::MIN as u128 => Some((m as i128).wrapping_neg()),
+            _ => None,
+        }
+    }
+
+    /// Gets the decimal as a `u128`, if it is an integer that fits.
+    pub(crate) const fn to_u128(self) -> Option<u128> {
+        match self.trunc_magnitude() {
+            Some(m) if self.is_integer() && (!self.negative || m == 0) => Some(m),
+            _ => None,
+        }
+    }
+
+    /// Truncates the decimal towards zero, saturating at the bounds of `i128`.
+    pub(crate) const fn saturating_to_i128(&self) -> i128 {
+        match self.trunc_magnitude() {
+            Some(m) if !self.negative && m <= i128::MAX as u128 => m as i128,
+            Some(m) if self.negative && m <= i128::MIN as u128 => (m as i128).wrapping_neg(),
+            _ if self.negative => i128::MIN,
+            _ => i128::MAX,
+        }
+    }
+
+    /// Truncates the decimal towards zero, saturating at the bounds of `u128`.
+    pub(crate) const fn saturating_to_u128(&self) -> u128 {
+        match self.trunc_magnitude() {
+            _ if self.negative => 0,
+            Some(m) => m,
+            None => u128::MAX,
+        }
+    }
+
+    /// Gets the significand of the decimal, with digits written to `buf`.
+    fn significand<'a>(&self, buf: &'a mut [u8; 39]) -> Significand<'a> {
+        let digits: &[u8] = if self.mantissa == 0 {
+            &[]
+        } else {
+            write_digits(self.mantissa, buf)
+        };
+        Significand {
+            negative: self.negative,
+            digits,
+            point: digits.len() as i64 + self.exponent as i64,
+        }
+    }
+
+    /// Compares the decimal with an integer exactly.
+    pub(crate) fn cmp_i128(&self, n: i128) -> Ordering {
+        let (mut a, mut b) = ([0; 39], [0; 39]);
+        let digits = write_digits(n.unsigned_abs(), &mut b);
+        self.significand(&mut a)
+            .cmp(&integer_significand(n < 0, digits))
+    }
+
+    /// Compares the decimal with a big integer exactly.
+    #[cfg(feature = "bigint")]
+    pub(crate) fn cmp_big_int(&self, n: &BigInt) -> Ordering {
+        let mut a = [0; 39];
+        let s = n.to_string();
+        let digits = s.trim_start_matches('-').as_bytes();
+        self.significand(&mut a)
+            .cmp(&integer_significand(n.is_negative(), digits))
+    }
+
+    /// Compares the decimal with a finite float exactly.
+    pub(crate) fn cmp_f64(&self, f: f64) -> Ordering {
+        // rounding is monotonic, so the nearest floats of two values are
+        // ordered the same way as the values unless they are equal
+        match self.float.partial_cmp(&f) {
+            Some(Ordering::Equal) | None => {}
+            Some(ordering) => return ordering,
+        }
+
+        // every float has a terminating decimal expansion of at most 767
+        // significant digits, which `{:e}` writes exactly
+        let s = format!("{:.767e}", f.abs());
+        let (digits, exponent) = s.split_once('e').unwrap_or((s.as_str(), "0"));
+        let digits = digits.replace('.', "");
+        let digits = digits.trim_end_matches('0').as_bytes();
+        let point = exponent.parse::<i64>().unwrap_or(0) + 1;
+
+        let mut a = [0; 39];
+        self.significand(&mut a).cmp(&Significand {
+            negative: f.is_sign_negative(),
+            digits,
+            point,
+        })
+    }
+
+    /// Aligns the mantissas of two decimals to the smaller exponent. Returns
+    /// `None` if a mantissa overflows.
+    fn align(&self, other: &Decimal) -> Option<(u128, u128, i64)> {
+        let exponent = self.exponent.min(other.exponent);
+        let scale = |d: &Decimal| match d.mantissa {
+            0 => Some(0),
+            m => m.checked_mul(10u128.checked_pow((d.exponent as i32 - exponent as i32) as u32)?),
+        };
+        Some((scale(self)?, scale(other)?, exponent as i64))
+    }
+
+    /// Adds two decimals exactly, returning `None` if the result does not fit.
+    pub(crate) fn checked_add(self, rhs: Decimal) -> Option<Decimal> {
+        let (a, b, exponent) = self.align(&rhs)?;
+        let (negative, mantissa) = if self.negative == rhs.negative {
+            (self.negative, a.checked_add(b)?)
+        } else if a >= b {
+            // `x - x` is `0.0`, as for floats
+            (self.negative && a != b, a - b)
+        } else {
+            (rhs.negative, b - a)
+        };
+        Decimal::from_parts(negative, mantissa, exponent)
+    }
+
+    /// Subtracts `rhs` from the decimal exactly, returning `None` if the
+    /// result does not fit.
+    pub(crate) fn checked_sub(self, rhs: Decimal) -> Option<Decimal> {
+        self.checked_add(-rhs)
+    }
+
+    /// Multiplies two decimals exactly, returning `None` if the result does not
+    /// fit.
+    pub(crate) fn checked_mul(self, rhs: Decimal) -> Option<Decimal> {
+        Decimal::from_parts(
+            self.negative != rhs.negative,
+            self.mantissa.checked_mul(rhs.mantissa)?,
+            self.exponent as i64 + rhs.exponent as i64,
+        )
+    }
+
+    /// Divides the decimal by `rhs` exactly, returning `None` if `rhs` is zero,
+    /// or if the quotient does not terminate or fit.
+    pub(crate) fn checked_div(self, rhs: Decimal) -> Option<Decimal> {
+        if rhs.mantissa == 0 {
+            return None;
+        }
+        let (mut mantissa, mut exponent) =
+            (self.mantissa, self.exponent as i64 - rhs.exponent as i64);
+        // scales the dividend until the quotient terminates, which it does
+        // before overflowing if it is short enough
+        while mantissa % rhs.mantissa != 0 {
+            mantissa = mantissa.checked_mul(10)?;
+            exponent -= 1;
+        }
+        Decimal::from_parts(
+            self.negative != rhs.negative,
+            mantissa / rhs.mantissa,
+            exponent,
+        )
+    }
+
+    /// Calculates the remainder of dividing the decimal by `rhs` exactly, which
+    /// has the same sign as the decimal. Returns `None` if `rhs` is zero, or if
+    /// the remainder does not fit.
+    pub(crate) fn checked_rem(self, rhs: Decimal) -> Option<Decimal> {
+        if rhs.mantissa == 0 {
+            return None;
+        }
+        let (a, b, exponent) = self.align(&rhs)?;
+        Decimal::from_parts(self.negative, a % b, exponent)
+    }
+}
+
+impl From<i128> for Decimal {
+    fn from(value: i128) -> Self {
+        // integers have fewer than 40 trailing zeros
+        Decimal::from_parts(value < 0, value.unsigned_abs(), 0).unwrap()
+    }
+}
+
+impl From<u128> for Decimal {
+    fn from(value: u128) -> Self {
+        Decimal::from_parts(false, value, 0).unwrap()
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(value: i64) -> Self {
+        Decimal::from(value as i128)
+    }
+}
+
+impl From<u64> for Decimal {
+    fn from(value: u64) -> Self {
+        Decimal::from(value as u128)
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Decimal) -> bool {
+        self.mantissa == other.mantissa
+            && self.exponent == other.exponent
+            && (self.negative == other.negative || self.mantissa == 0)
+    }
+}
+
+impl Eq for Decimal {}
+
+impl Hash for Decimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.is_negative().hash(state);
+        self.mantissa.hash(state);
+        self.exponent.hash(state);
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Decimal) -> Ordering {
+        let (mut a, mut b) = ([0; 39], [0; 39]);
+        self.significand(&mut a).cmp(&other.significand(&mut b))
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Decimal) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for Decimal {
+    type Output = Decimal;
+
+    fn neg(self) -> Decimal {
+        Decimal {
+            negative: !self.negative,
+            float: -self.float,
+            float32: -self.float32,
+            ..self
+        }
+    }
+}
+
+impl Decimal {
+    /// Checks if the sign of the decimal is negative, including for `-0.0`.
+    pub(crate) const fn is_sign_negative(self) -> bool {
+        self.negative
+    }
+
+    /// Checks if the decimal is written in scientific notation by default,
+    /// which is for magnitudes below `1e-4` or from `1e16` on, like floats.
+    pub(crate) fn is_scientific(self) -> bool {
+        !(-4..16).contains(&self.scientific_exponent())
+    }
+
+    /// The exponent of the first significant digit in scientific notation.
+    fn scientific_exponent(self) -> i64 {
+        let digits = self.mantissa.checked_ilog10().map_or(1, |d| d + 1);
+        digits as i64 + self.exponent as i64 - 1
+    }
+
+    /// Writes the magnitude of the decimal with all its significant digits,
+    /// always with a `.` or an exponent, in scientific notation if `scientific`
+    /// is set and in fixed notation otherwise.
+    pub(crate) fn write_magnitude(self, s: &mut String, scientific: bool) -> fmt::Result {
+        let mut buf = [0; 39];
+        let digits = write_digits(self.mantissa, &mut buf);
+        let digits = core::str::from_utf8(digits).map_err(|_| fmt::Error)?;
+        let exponent = self.scientific_exponent();
+
+        if scientific {
+            let (first, rest) = digits.split_at(1);
+            s.push_str(first);
+            if !rest.is_empty() {
+                s.push('.');
+                s.push_str(rest);
+            }
+            write!(s, "e{}", exponent)?;
+        } else if self.exponent >= 0 {
+            s.push_str(digits);
+            s.extend(core::iter::repeat_n('0', self.exponent as usize));
+            s.push_str(".0");
+        } else if exponent >= 0 {
+            let (int, fraction) = digits.split_at(exponent as usize + 1);
+            write!(s, "{}.{}", int, fraction)?;
+        } else {
+            s.push_str("0.");
+            s.extend(core::iter::repeat_n('0', (-exponent - 1) as usize));
+            s.push_str(digits);
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Decimal {
+    /// Formats the decimal as a Twic number literal with all its significant
+    /// digits, always with a `.` or an exponent so that it parses back to the
+    /// same decimal. Like floats, it uses scientific notation for magnitudes
+    /// below `1e-4` or from `1e16` on.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = String::new();
+        self.write_magnitude(&mut s, self.is_scientific())?;
+        f.pad_integral(!self.negative, "", &s)
+    }
+}
+
+impl fmt::LowerExp for Decimal {
+    /// Formats the decimal in scientific notation with all its significant
+    /// digits, e.g. `1.5e3`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = String::new();
+        self.write_magnitude(&mut s, true)?;
+        f.pad_integral(!self.negative, "", &s)
+    }
+}
+
+impl fmt::UpperExp for Decimal {
+    /// Formats the decimal in scientific notation with all its significant
+    /// digits, e.g. `1.5E3`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = String::new();
+        self.write_magnitude(&mut s, true)?;
+        f.pad_integral(!self.negative, "", &s.replace('e', "E"))
+    }
+}
+
+impl fmt::Debug for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+    use core::cmp::Ordering;
+
+    use super::Decimal;
+
+    fn dec(mantissa: i128, exponent: i16) -> Decimal {
+        Decimal::new(mantissa, exponent).unwrap()
+    }
+
+    #[test]
+    fn test_display() {
+        for (d, expected) in [
+            (dec(0, 5), "0.0"),
+            (-dec(0, 0), "-0.0"),
+            (dec(1, -1), "0.1"),
+            (dec(-15, -1), "-1.5"),
+            (dec(100, 0), "100.0"),
+            (dec(1, 3), "1000.0"),
+            (dec(1, 16), "1e16"),
+            (dec(123, 14), "1.23e16"),
+            (dec(9999, 12), "9999000000000000.0"),
+            (dec(1, -4), "0.0001"),
+            (dec(15, -6), "1.5e-5"),
+            (dec(1234567, -3), "1234.567"),
+            (
+                dec(i128::MAX, -40),
+                "0.0170141183460469231731687303715884105727",
+            ),
+            (
+                dec(i128::MAX, -45),
+                "1.70141183460469231731687303715884105727e-7",
+            ),
+            (dec(1, i16::MIN), "1e-32768"),
+        ] {
+            assert_eq!(d.to_string(), expected);
+        }
+        assert_eq!(alloc::format!("{:>6}", dec(15, -1)), "   1.5");
+        assert_eq!(alloc::format!("{:e}", dec(15, 2)), "1.5e3");
+        assert_eq!(alloc::format!("{:E}", dec(-1, -5)), "-1E-5");
+        assert_eq!(alloc::format!("{:e}", dec(0, 0)), "0e0");
+    }
+
+    #[test]
+    fn test_float_conversions() {
+        assert_eq!(dec(1, -1).to_f64(), 0.1);
+        assert_eq!(dec(1, -1).to_f32(), 0.1f32);
+        assert_eq!(dec(1, -1).to_f64_exact(), None);
+        assert_eq!(dec(-25, -2).to_f64_exact(), Some(-0.25));
+        assert_eq!(dec(-25, -2).to_f32_exact(), Some(-0.25));
+        assert_eq!(dec(1, 22).to_f64_exact(), Some(1e22));
+        assert_eq!(dec(1, 23).to_f64_exact(), None);
+        assert_eq!(dec(1, 400).to_f64(), f64::INFINITY);
+        assert_eq!(dec(1, 400).to_f64_exact(), None);
+        assert!((-dec(0, 0)).to_f64_exact().unwrap().is_sign_negative());
+
+        // double rounding through `f64` would round this up
+        let d = Decimal::from_parts(false, 1000000059604644775390625001, -27).unwrap();
+        assert_eq!(d.to_f32(), 1.0000001);
+        let d = Decimal::from_parts(false, 100000005960464477539062499, -26).unwrap();
+        assert_eq!(d.to_f32(), 1.0);
+
+        assert_eq!(Decimal::from_f64(0.5), Some(dec(5, -1)));
+        assert_eq!(Decimal::from_f64(-1e22), Some(dec(-1, 22)));
+        assert_eq!(Decimal::from_f64(0.1), None);
+        assert_eq!(Decimal::from_f64(1e300), None);
+        assert_eq!(
+            Decimal::from_f64(2f64.powi(-30)),
+            Some(dec(931322574615478515625, -30))
+        );
+    }
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(
+            dec(15, 37).to_u128(),
+            Some(150000000000000000000000000000000000000)
+        );
+        assert_eq!(dec(1, 39).to_u128(), None);
+        assert_eq!(dec(-1, 3).to_i128(), Some(-1000));
+        assert_eq!(dec(-1, 3).to_u128(), None);
+        assert_eq!(dec(15, -1).to_i128(), None);
+        assert_eq!(dec(15, -1).saturating_to_i128(), 1);
+        assert_eq!(dec(-15, -1).saturating_to_i128(), -1);
+        assert_eq!(dec(-15, -1).saturating_to_u128(), 0);
+        assert_eq!(dec(1, 40).saturating_to_i128(), i128::MAX);
+        assert_eq!(dec(-1, 40).saturating_to_i128(), i128::MIN);
+        assert_eq!(dec(1, -100).saturating_to_i128(), 0);
+        assert_eq!(Decimal::from(-1000i64), dec(-1, 3));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(dec(1, -1).checked_add(dec(2, -1)), Some(dec(3, -1)));
+        assert_eq!(dec(1, -1).checked_sub(dec(1, -1)), Some(dec(0, 0)));
+        assert!(!dec(1, -1).checked_sub(dec(1, -1)).unwrap().negative);
+        assert_eq!(dec(1, 0).checked_sub(dec(25, -1)), Some(dec(-15, -1)));
+        assert_eq!(dec(-15, -1).checked_mul(dec(-2, 1)), Some(dec(3, 1)));
+        assert_eq!(dec(1, 0).checked_div(dec(8, 0)), Some(dec(125, -3)));
+        assert_eq!(dec(1, 0).checked_div(dec(3, 0)), None);
+        assert_eq!(dec(1, 0).checked_div(dec(0, 0)), None);
+        assert_eq!(dec(-75, -1).checked_rem(dec(2, 0)), Some(dec(-15, -1)));
+        assert_eq!(dec(1, 0).checked_rem(dec(0, 0)), None);
+        assert_eq!(dec(1, 30).checked_add(dec(1, -30)), None);
+        assert_eq!(dec(i128::MAX, 0).checked_mul(dec(i128::MAX, 0)), None);
+    }
+
+    #[test]
+    fn test_cmp() {
+        assert_eq!(dec(1, -1).cmp(&dec(-1, 5)), Ordering::Greater);
+        assert_eq!(dec(-1, -1).cmp(&dec(-1, 5)), Ordering::Greater);
+        assert_eq!(dec(15, -1).cmp(&dec(151, -2)), Ordering::Less);
+        assert_eq!(dec(0, 0), -dec(0, 0));
+        assert_eq!(dec(0, 0).cmp(&-dec(0, 0)), Ordering::Equal);
+
+        assert_eq!(dec(1, 3).cmp_i128(1000), Ordering::Equal);
+        assert_eq!(dec(1, 3).cmp_i128(1001), Ordering::Less);
+        assert_eq!(dec(-5, -1).cmp_i128(0), Ordering::Less);
+        assert_eq!(dec(-5, -1).cmp_i128(-1), Ordering::Greater);
+        assert_eq!(dec(0, 0).cmp_i128(0), Ordering::Equal);
+
+        assert_eq!(dec(5, -1).cmp_f64(0.5), Ordering::Equal);
+        assert_eq!(dec(1, -1).cmp_f64(0.1), Ordering::Less);
+        assert_eq!(dec(-1, -1).cmp_f64(-0.1), Ordering::Greater);
+        assert_eq!(dec(3, -1).cmp_f64(0.1 + 0.2), Ordering::Less);
+        assert_eq!(dec(1, 400).cmp_f64(f64::MAX), Ordering::Greater);
+        assert_eq!((-dec(0, 0)).cmp_f64(0.0), Ordering::Equal);
+        assert_eq!(dec(5, -324).cmp_f64(5e-324), Ordering::Greater);
+    }
+}
@@ -0,0 +1,63 @@
+use core::fmt;
+
+/// The reason a conversion between a [`Number`](super::Number) and a Rust
+/// numeric type failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberConversionErrorKind {
+    /// The number is `nan`, but the target is an integer type.
+    WrongKind,
+    /// The number is out of the range of the target type.
+    Overflow,
+    /// The number is in the range of the target type, but cannot be
+    /// represented exactly, e.g. a float with a fractional part converted to
+    /// an integer type.
+    PrecisionLoss,
+}
+
+impl fmt::Display for NumberConversionErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumberConversionErrorKind::WrongKind => write!(f, "nan is not an integer"),
+            NumberConversionErrorKind::Overflow => write!(f, "number out of range"),
+            NumberConversionErrorKind::PrecisionLoss => {
+                write!(f, "number cannot be represented exactly")
+            }
+        }
+    }
+}
+
+/// An error that occurred while converting a [`Number`](super::Number) from or
+/// into a Rust numeric type with [`TryFrom`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberConversionError {
+    kind: NumberConversionErrorKind,
+}
+
+impl NumberConversionError {
+    /// Creates a new conversion error of the given kind.
+    pub(crate) fn new(kind: NumberConversionErrorKind) -> Self {
+        NumberConversionError { kind }
+    }
+
+    /// Returns the kind of the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::{Number, NumberConversionErrorKind};
+    ///
+    /// let err = u8::try_from(Number::from(2.5)).unwrap_err();
+    /// assert_eq!(err.kind(), NumberConversionErrorKind::PrecisionLoss);
+    /// ```
+    pub fn kind(&self) -> NumberConversionErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for NumberConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.kind.fmt(f)
+    }
+}
+
+impl core::error::Error for NumberConversionError {}
@@ -1,7 +1,11 @@
 use core::fmt;
 use core::hash::Hash;
 
-use super::Number;
+#[cfg(feature = "bigint")]
+use super::BigInt;
+#[cfg(feature = "decimal")]
+use super::Decimal;
+use super::{Number, NumberConversionError, NumberConversionErrorKind};
 
 impl fmt::Debug for Number {
     /// Formats the Twic number for debugging purposes.
@@ -29,6 +33,10 @@ impl fmt::Debug for Number {
                     write!(f, "+Inf")
                 }
             }
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => write!(f, "Integer({})", n),
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => write!(f, "Decimal({})", d),
         }
     }
 }
@@ -44,11 +52,17 @@ impl PartialEq for Number {
             (Number::Float(a), Number::Float(b)) => a == b,
             (Number::NaN, Number::NaN) => true,
             (Number::Inf { negative: a }, Number::Inf { negative: b }) => a == b,
+            #[cfg(feature = "bigint")]
+            (Number::BigInt(a), Number::BigInt(b)) => a == b,
+            #[cfg(feature = "decimal")]
+            (Number::Decimal(a), Number::Decimal(b)) => a == b,
             _ => false,
         }
     }
 }
 
+impl Eq for Number {}
+
 impl Hash for Number {
     /// Hashes the Twic number.
     ///
@@ -80,6 +94,14 @@ impl Hash for Number {
                 .to_bits()
                 .hash(state);
             }
+            #[cfg(feature = "bigint")]
+            Number::BigInt(n) => {
+                n.hash(state);
+            }
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => {
+                d.hash(state);
+            }
         }
     }
 }
@@ -202,3 +224,270 @@ impl_partial_eq_number! {
     f32 => as_f32_exact,
     f64 => as_f64_exact,
 }
+
+#[cfg(feature = "bigint")]
+impl From<BigInt> for Number {
+    fn from(value: BigInt) -> Self {
+        match value.to_i128() {
+            Some(n) if (0..=u64::MAX as i128).contains(&n) => Number::PosInt(n as u64),
+            Some(n) if (-(1 << 64)..0).contains(&n) => Number::NegInt(n as u64),
+            _ => Number::BigInt(value),
+        }
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl From<Decimal> for Number {
+    fn from(value: Decimal) -> Self {
+        Number::Decimal(value)
+    }
+}
+
+/// Converts an integer out of the range of `[-2^64, 2^64 - 1]`.
+#[cfg(not(feature = "bigint"))]
+fn from_wide_int<T>(_: T) -> Result<Number, NumberConversionError> {
+    Err(NumberConversionError::new(
+        NumberConversionErrorKind::Overflow,
+    ))
+}
+
+/// Converts an integer out of the range of `[-2^64, 2^64 - 1]`.
+#[cfg(feature = "bigint")]
+fn from_wide_int<T: Into<BigInt>>(value: T) -> Result<Number, NumberConversionError> {
+    Ok(Number::BigInt(value.into()))
+}
+
+impl TryFrom<i128> for Number {
+    type Error = NumberConversionError;
+
+    /// Converts an i128 to a Twic number, failing if it is out of the range of
+    /// integer `Number`s. With the `bigint` feature, it never fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(Number::try_from(-(1i128 << 64)), Ok(Number::NegInt(0)));
+    /// assert_eq!(
+    ///     Number::try_from(1i128 << 64).is_err(),
+    ///     !cfg!(feature = "bigint")
+    /// );
+    /// ```
+    fn try_from(value: i128) -> Result<Self, Self::Error> {
+        if value >= 0 {
+            Number::try_from(value as u128)
+        } else if value >= -(1 << 64) {
+            Ok(Number::NegInt(value as u64))
+        } else {
+            from_wide_int(value)
+        }
+    }
+}
+
+impl TryFrom<u128> for Number {
+    type Error = NumberConversionError;
+
+    /// Converts a u128 to a Twic number, failing if it is out of the range of
+    /// integer `Number`s. With the `bigint` feature, it never fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(Number::try_from(u64::MAX as u128), Ok(Number::PosInt(u64::MAX)));
+    /// assert_eq!(
+    ///     Number::try_from(u128::MAX).is_err(),
+    ///     !cfg!(feature = "bigint")
+    /// );
+    /// ```
+    fn try_from(value: u128) -> Result<Self, Self::Error> {
+        match u64::try_from(value) {
+            Ok(n) => Ok(Number::PosInt(n)),
+            Err(_) => from_wide_int(value),
+        }
+    }
+}
+
+impl Number {
+    /// Returns the reason the number cannot be converted to an integer type
+    /// exactly.
+    fn integer_conversion_error(&self) -> NumberConversionError {
+        let kind = match self {
+            Number::NaN => NumberConversionErrorKind::WrongKind,
+            // floats of at least 2^53 in magnitude are all integers
+            Number::Float(f) if f.abs() < 9007199254740992.0 && (*f as i64) as f64 != *f => {
+                NumberConversionErrorKind::PrecisionLoss
+            }
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) if !d.is_integer() => NumberConversionErrorKind::PrecisionLoss,
+            _ => NumberConversionErrorKind::Overflow,
+        };
+        NumberConversionError::new(kind)
+    }
+
+    /// Returns the reason the number cannot be converted to an `f32` exactly.
+    fn f32_conversion_error(&self) -> NumberConversionError {
+        let overflow = match self {
+            // floats just above `f32::MAX` round down to it rather than to
+            // infinity, but are out of range all the same
+            Number::Float(f) => f.abs() > f32::MAX as f64,
+            n => n.as_f32().is_infinite(),
+        };
+        float_conversion_error(overflow)
+    }
+
+    /// Returns the reason the number cannot be converted to an `f64` exactly.
+    fn f64_conversion_error(&self) -> NumberConversionError {
+        float_conversion_error(self.as_f64().is_infinite())
+    }
+}
+
+/// Returns the reason a number cannot be converted to a float type exactly,
+/// given whether it is out of the range of the type.
+fn float_conversion_error(overflow: bool) -> NumberConversionError {
+    NumberConversionError::new(if overflow {
+        NumberConversionErrorKind::Overflow
+    } else {
+        NumberConversionErrorKind::PrecisionLoss
+    })
+}
+
+macro_rules! impl_try_from_number {
+    ($($t:ty => $method:ident, $error:ident),* $(,)?) => {
+        $(
+            impl TryFrom<Number> for $t {
+                type Error = NumberConversionError;
+
+                /// Converts a Twic number to a primitive number if it can be
+                /// represented without loss of information.
+                fn try_from(value: Number) -> Result<Self, Self::Error> {
+                    value.$method().ok_or_else(|| value.$error())
+                }
+            }
+        )*
+    }
+}
+
+impl_try_from_number! {
+    i8 => as_i8_exact, integer_conversion_error,
+    i16 => as_i16_exact, integer_conversion_error,
+    i32 => as_i32_exact, integer_conversion_error,
+    i64 => as_i64_exact, integer_conversion_error,
+    isize => as_isize_exact, integer_conversion_error,
+    i128 => as_i128_exact, integer_conversion_error,
+    u8 => as_u8_exact, integer_conversion_error,
+    u16 => as_u16_exact, integer_conversion_error,
+    u32 => as_u32_exact, integer_conversion_error,
+    u64 => as_u64_exact, integer_conversion_error,
+    usize => as_usize_exact, integer_conversion_error,
+    u128 => as_u128_exact, integer_conversion_error,
+    f32 => as_f32_exact, f32_conversion_error,
+    f64 => as_f64_exact, f64_conversion_error,
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "bigint")]
+    use alloc::format;
+
+    use crate::value::{Number, NumberConversionErrorKind};
+
+    #[test]
+    fn test_try_from() {
+        use NumberConversionErrorKind::*;
+
+        let two_64 = 1i128 << 64;
+        assert_eq!(Number::try_from(two_64 - 1), Ok(Number::PosInt(u64::MAX)));
+        assert_eq!(Number::try_from(-two_64), Ok(Number::NegInt(0)));
+        assert_eq!(Number::try_from(-1i128), Ok(Number::NegInt(u64::MAX)));
+        #[cfg(not(feature = "bigint"))]
+        {
+            assert_eq!(Number::try_from(two_64).unwrap_err().kind(), Overflow);
+            assert_eq!(Number::try_from(-two_64 - 1).unwrap_err().kind(), Overflow);
+            assert_eq!(
+                Number::try_from(two_64 as u128).unwrap_err().kind(),
+                Overflow
+            );
+        }
+        #[cfg(feature = "bigint")]
+        for big in [two_64, -two_64 - 1, i128::MIN, i128::MAX] {
+            let n = Number::try_from(big).unwrap();
+            assert!(matches!(n, Number::BigInt(_)));
+            assert_eq!(i128::try_from(n), Ok(big));
+        }
+
+        let kind = |r: Result<u8, _>| r.map_err(|e: crate::value::NumberConversionError| e.kind());
+        assert_eq!(kind(u8::try_from(Number::from(255))), Ok(255));
+        assert_eq!(kind(u8::try_from(Number::from(255.0))), Ok(255));
+        assert_eq!(kind(u8::try_from(Number::from(256))), Err(Overflow));
+        assert_eq!(kind(u8::try_from(Number::from(-1))), Err(Overflow));
+        assert_eq!(kind(u8::try_from(Number::from(0.5))), Err(PrecisionLoss));
+        assert_eq!(kind(u8::try_from(Number::from(1e300))), Err(Overflow));
+        assert_eq!(kind(u8::try_from(Number::NaN)), Err(WrongKind));
+        assert_eq!(
+            kind(u8::try_from(Number::Inf { negative: false })),
+            Err(Overflow)
+        );
+
+        assert_eq!(i128::try_from(Number::NegInt(0)), Ok(-two_64));
+        assert_eq!(
+            u128::try_from(Number::from(1e30)),
+            Ok(1000000000000000019884624838656)
+        );
+        assert_eq!(
+            f64::try_from(Number::from(1u64 << 53)),
+            Ok(9007199254740992.0)
+        );
+        assert_eq!(
+            f64::try_from(Number::from((1u64 << 53) + 1))
+                .unwrap_err()
+                .kind(),
+            PrecisionLoss
+        );
+        assert_eq!(f32::try_from(Number::from(0.5)), Ok(0.5));
+        assert_eq!(
+            f32::try_from(Number::from(0.1)).unwrap_err().kind(),
+            PrecisionLoss
+        );
+        assert_eq!(
+            f32::try_from(Number::from(1e300)).unwrap_err().kind(),
+            Overflow
+        );
+        assert!(f32::try_from(Number::NaN).unwrap().is_nan());
+
+        #[cfg(feature = "bigint")]
+        {
+            let big = |s: &str| s.parse::<Number>().unwrap();
+            let beyond_f64 = big(&format!("1{}", "0".repeat(400)));
+            assert_eq!(
+                f64::try_from(beyond_f64.clone()).unwrap_err().kind(),
+                Overflow
+            );
+            assert_eq!(f32::try_from(beyond_f64).unwrap_err().kind(), Overflow);
+            let beyond_f32 = big(&format!("1{}1", "0".repeat(39)));
+            assert_eq!(
+                f32::try_from(beyond_f32.clone()).unwrap_err().kind(),
+                Overflow
+            );
+            assert_eq!(f64::try_from(beyond_f32).unwrap_err().kind(), PrecisionLoss);
+        }
+        #[cfg(feature = "decimal")]
+        {
+            let decimal = |s: &str| Number::Decimal(s.parse().unwrap());
+            assert_eq!(
+                f64::try_from(decimal("1.5e400")).unwrap_err().kind(),
+                Overflow
+            );
+            assert_eq!(
+                f32::try_from(decimal("1.5e40")).unwrap_err().kind(),
+                Overflow
+            );
+            assert_eq!(
+                f64::try_from(decimal("1.5e40")).unwrap_err().kind(),
+                PrecisionLoss
+            );
+        }
+    }
+}
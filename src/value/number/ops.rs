@@ -0,0 +1,1046 @@
+use core::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+
+#[cfg(feature = "bigint")]
+use super::BigInt;
+#[cfg(feature = "decimal")]
+use super::Decimal;
+use super::Number;
+#[cfg(not(feature = "bigint"))]
+use super::utils::consts::TWO_POW_128_F64;
+
+/// The smallest integer `Number`, `-2^64`.
+const MIN: i128 = -(1 << 64);
+/// The largest integer `Number`, `2^64 - 1`.
+const MAX: i128 = (1 << 64) - 1;
+
+/// The type integer operations are performed on. `i128` holds all integer
+/// `Number`s and their sums and differences.
+#[cfg(not(feature = "bigint"))]
+type Int = i128;
+
+/// The type integer operations are performed on with the `bigint` feature.
+///
+/// Operations on integers in the range of `[-2^64, 2^64 - 1]` behave as
+/// without the feature, so their results still go out of range, while those
+/// involving a big integer are exact. Values are kept in an `i128` while they
+/// fit, so only operations whose results overflow it allocate.
+#[cfg(feature = "bigint")]
+#[derive(Clone)]
+struct Int {
+    value: IntValue,
+    /// Set if an operand was a `Number::BigInt`.
+    big: bool,
+}
+
+/// The value of an [`Int`].
+#[cfg(feature = "bigint")]
+#[derive(Clone)]
+enum IntValue {
+    Small(i128),
+    /// An integer that does not fit in `i128`.
+    Big(BigInt),
+}
+
+#[cfg(feature = "bigint")]
+impl IntValue {
+    fn from_big(n: BigInt) -> IntValue {
+        match n.to_i128() {
+            Some(n) => IntValue::Small(n),
+            None => IntValue::Big(n),
+        }
+    }
+
+    fn into_big(self) -> BigInt {
+        match self {
+            IntValue::Small(n) => BigInt::from(n),
+            IntValue::Big(n) => n,
+        }
+    }
+
+    fn to_f64(&self) -> f64 {
+        match self {
+            IntValue::Small(n) => *n as f64,
+            IntValue::Big(n) => n.to_f64(),
+        }
+    }
+}
+
+/// Creates an integer `Number` from an `i128` known to be in `[MIN, MAX]`.
+const fn from_i65(n: i128) -> Number {
+    if n >= 0 {
+        Number::PosInt(n as u64)
+    } else {
+        Number::NegInt(n as u64)
+    }
+}
+
+/// Creates an integer `Number`, returning `None` if `n` is out of range.
+const fn from_i128(n: i128) -> Option<Number> {
+    if MIN <= n && n <= MAX {
+        Some(from_i65(n))
+    } else {
+        None
+    }
+}
+
+/// Creates an integer `Number`, returning `None` if `n` is out of range.
+#[cfg(not(feature = "bigint"))]
+const fn int(n: i128) -> Option<Number> {
+    from_i128(n)
+}
+
+/// Creates an integer `Number`, or the nearest float if `n` is out of range.
+#[cfg(not(feature = "bigint"))]
+fn int_or_float(n: i128) -> Number {
+    int(n).unwrap_or(Number::Float(n as f64))
+}
+
+/// Wraps `n` around the range of integer `Number`s.
+#[cfg(not(feature = "bigint"))]
+const fn wrap(n: i128) -> Number {
+    // sign-extends the lowest 65 bits
+    from_i65((n << 63) >> 63)
+}
+
+/// Clamps `n` to the range of integer `Number`s.
+#[cfg(not(feature = "bigint"))]
+fn saturate(n: i128) -> Number {
+    from_i65(n.clamp(MIN, MAX))
+}
+
+/// Multiplies two integers, returning the product, or the nearest float if it
+/// does not fit in `i128`.
+#[cfg(not(feature = "bigint"))]
+fn mul_exact(a: i128, b: i128) -> Result<i128, f64> {
+    a.checked_mul(b).ok_or_else(|| {
+        // the magnitudes are at most 2^64, so only `2^64 * 2^64` overflows
+        let magnitude = a
+            .unsigned_abs()
+            .checked_mul(b.unsigned_abs())
+            .map_or(TWO_POW_128_F64, |m| m as f64);
+        if (a < 0) != (b < 0) {
+            -magnitude
+        } else {
+            magnitude
+        }
+    })
+}
+
+/// Multiplies two integers, wrapping around `i128`, which keeps the lowest 65
+/// bits of the product.
+#[cfg(not(feature = "bigint"))]
+fn wrapping_mul(a: i128, b: i128) -> i128 {
+    a.wrapping_mul(b)
+}
+
+/// Multiplies two integers, saturating at the bounds of `i128`.
+#[cfg(not(feature = "bigint"))]
+fn saturating_mul(a: i128, b: i128) -> i128 {
+    a.saturating_mul(b)
+}
+
+#[cfg(feature = "bigint")]
+impl Int {
+    /// Combines two operands with an operation on `i128`s, falling back to
+    /// the operation on big integers if either does not fit or the result
+    /// overflows.
+    fn combine(
+        self,
+        rhs: Int,
+        small_op: fn(i128, i128) -> Option<i128>,
+        big_op: fn(BigInt, BigInt) -> Option<BigInt>,
+    ) -> Option<Int> {
+        let big = self.big || rhs.big;
+        let value = match (&self.value, &rhs.value) {
+            (IntValue::Small(a), IntValue::Small(b)) if let Some(n) = small_op(*a, *b) => {
+                IntValue::Small(n)
+            }
+            _ => IntValue::from_big(big_op(self.value.into_big(), rhs.value.into_big())?),
+        };
+        Some(Int { value, big })
+    }
+
+    fn checked_div(self, rhs: Int) -> Option<Int> {
+        self.combine(rhs, i128::checked_div, BigInt::checked_div)
+    }
+
+    fn checked_rem(self, rhs: Int) -> Option<Int> {
+        self.combine(rhs, i128::checked_rem, BigInt::checked_rem)
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl Add for Int {
+    type Output = Int;
+
+    fn add(self, rhs: Int) -> Int {
+        self.combine(rhs, i128::checked_add, |a, b| Some(a + b))
+            .unwrap()
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl Sub for Int {
+    type Output = Int;
+
+    fn sub(self, rhs: Int) -> Int {
+        self.combine(rhs, i128::checked_sub, |a, b| Some(a - b))
+            .unwrap()
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl Mul for Int {
+    type Output = Int;
+
+    fn mul(self, rhs: Int) -> Int {
+        self.combine(rhs, i128::checked_mul, |a, b| Some(a * b))
+            .unwrap()
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl Neg for Int {
+    type Output = Int;
+
+    fn neg(self) -> Int {
+        let value = match self.value {
+            IntValue::Small(n) if let Some(n) = n.checked_neg() => IntValue::Small(n),
+            value => IntValue::from_big(-value.into_big()),
+        };
+        Int {
+            value,
+            big: self.big,
+        }
+    }
+}
+
+/// Creates an integer `Number`, returning `None` if `n` is out of range and no
+/// operand was a big integer.
+#[cfg(feature = "bigint")]
+fn int(n: Int) -> Option<Number> {
+    match n.value {
+        IntValue::Small(v) if let Some(number) = from_i128(v) => Some(number),
+        value if n.big => Some(Number::BigInt(value.into_big())),
+        _ => None,
+    }
+}
+
+/// Creates an integer `Number`, or the nearest float if `n` is out of range
+/// and no operand was a big integer.
+#[cfg(feature = "bigint")]
+fn int_or_float(n: Int) -> Number {
+    let f = n.value.to_f64();
+    int(n).unwrap_or(Number::Float(f))
+}
+
+/// Wraps `n` around the range of `[-2^64, 2^64 - 1]`.
+#[cfg(feature = "bigint")]
+fn wrap(n: Int) -> Number {
+    let low = match n.value {
+        IntValue::Small(v) => v,
+        IntValue::Big(v) => v.wrapping_to_u128() as i128,
+    };
+    // sign-extends the lowest 65 bits
+    from_i65((low << 63) >> 63)
+}
+
+/// Clamps `n` to the range of `[-2^64, 2^64 - 1]`.
+#[cfg(feature = "bigint")]
+fn saturate(n: Int) -> Number {
+    match n.value {
+        IntValue::Small(v) => from_i65(v.clamp(MIN, MAX)),
+        IntValue::Big(v) if v.is_negative() => from_i65(MIN),
+        IntValue::Big(_) => from_i65(MAX),
+    }
+}
+
+/// Multiplies two integers exactly.
+#[cfg(feature = "bigint")]
+fn mul_exact(a: Int, b: Int) -> Result<Int, f64> {
+    Ok(a * b)
+}
+
+/// Multiplies two integers exactly.
+#[cfg(feature = "bigint")]
+fn wrapping_mul(a: Int, b: Int) -> Int {
+    a * b
+}
+
+/// Multiplies two integers exactly.
+#[cfg(feature = "bigint")]
+fn saturating_mul(a: Int, b: Int) -> Int {
+    a * b
+}
+
+/// The type operations involving a non-integer are performed on.
+#[cfg(not(feature = "decimal"))]
+type Real = f64;
+
+/// The type operations involving a non-integer are performed on: decimals are
+/// kept exact as long as the result fits, and everything else is an `f64`.
+#[cfg(feature = "decimal")]
+#[derive(Clone, Copy)]
+enum Real {
+    Float(f64),
+    Decimal(Decimal),
+}
+
+#[cfg(feature = "decimal")]
+impl Real {
+    const fn to_f64(self) -> f64 {
+        match self {
+            Real::Float(f) => f,
+            Real::Decimal(d) => d.to_f64(),
+        }
+    }
+
+    /// Applies an operation on decimals if both operands are decimals, falling
+    /// back to `f64`s if either is not or the exact result does not fit.
+    fn apply(
+        self,
+        rhs: Real,
+        decimal_op: fn(Decimal, Decimal) -> Option<Decimal>,
+        float_op: fn(f64, f64) -> f64,
+    ) -> Real {
+        if let (Real::Decimal(a), Real::Decimal(b)) = (self, rhs)
+            && let Some(d) = decimal_op(a, b)
+        {
+            return Real::Decimal(d);
+        }
+        Real::Float(float_op(self.to_f64(), rhs.to_f64()))
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl From<Real> for Number {
+    fn from(value: Real) -> Self {
+        match value {
+            Real::Float(f) => Number::from(f),
+            Real::Decimal(d) => Number::Decimal(d),
+        }
+    }
+}
+
+#[cfg(feature = "decimal")]
+macro_rules! impl_real_op {
+    ($($trait:ident, $method:ident => $decimal_op:ident),* $(,)?) => {
+        $(
+            impl $trait for Real {
+                type Output = Real;
+
+                fn $method(self, rhs: Real) -> Real {
+                    self.apply(rhs, Decimal::$decimal_op, f64::$method)
+                }
+            }
+        )*
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl_real_op! {
+    Add, add => checked_add,
+    Sub, sub => checked_sub,
+    Mul, mul => checked_mul,
+    Div, div => checked_div,
+    Rem, rem => checked_rem,
+}
+
+#[cfg(feature = "decimal")]
+impl Neg for Real {
+    type Output = Real;
+
+    fn neg(self) -> Real {
+        match self {
+            Real::Float(f) => Real::Float(-f),
+            Real::Decimal(d) => Real::Decimal(-d),
+        }
+    }
+}
+
+/// Divides two integers, panicking with Rust's message if `b` is zero.
+fn div(a: Int, b: Int) -> Int {
+    match a.checked_div(b) {
+        Some(quotient) => quotient,
+        None => panic!("attempt to divide by zero"),
+    }
+}
+
+impl Number {
+    /// Gets the value of an integer `Number` as an [`Int`].
+    #[cfg(not(feature = "bigint"))]
+    const fn get_int(&self) -> Option<Int> {
+        self.get_i128()
+    }
+
+    /// Gets the value of an integer `Number` as an [`Int`].
+    #[cfg(feature = "bigint")]
+    fn get_int(&self) -> Option<Int> {
+        match self {
+            Number::BigInt(n) => Some(Int {
+                value: IntValue::from_big(n.clone()),
+                big: true,
+            }),
+            _ => self.get_i128().map(|n| Int {
+                value: IntValue::Small(n),
+                big: false,
+            }),
+        }
+    }
+
+    /// Gets the value of the `Number` as a [`Real`].
+    #[cfg(not(feature = "decimal"))]
+    const fn get_real(&self, _decimal: bool) -> Real {
+        self.as_f64()
+    }
+
+    /// Gets the value of the `Number` as a [`Real`], converting integers to
+    /// decimals if `decimal` is set.
+    #[cfg(feature = "decimal")]
+    fn get_real(&self, decimal: bool) -> Real {
+        match self {
+            Number::Decimal(d) => Real::Decimal(*d),
+            _ if decimal
+                && self.is_integer()
+                && let Some(n) = self.as_i128_exact() =>
+            {
+                Real::Decimal(Decimal::from(n))
+            }
+            _ => Real::Float(self.as_f64()),
+        }
+    }
+
+    /// Checks if operations on the `Number` are performed on decimals.
+    const fn has_decimal(&self) -> bool {
+        #[cfg(feature = "decimal")]
+        return self.is_decimal();
+        #[cfg(not(feature = "decimal"))]
+        return false;
+    }
+
+    /// Applies a binary operation, on [`Int`]s if both operands are integers,
+    /// or on [`Real`]s otherwise.
+    fn binary<T>(
+        self,
+        rhs: Number,
+        int_op: impl FnOnce(Int, Int) -> T,
+        float_op: impl FnOnce(Real, Real) -> Real,
+    ) -> T
+    where
+        T: From<Number>,
+    {
+        match (self.get_int(), rhs.get_int()) {
+            (Some(a), Some(b)) => int_op(a, b),
+            _ => {
+                let decimal = self.has_decimal() || rhs.has_decimal();
+                Number::from(float_op(self.get_real(decimal), rhs.get_real(decimal))).into()
+            }
+        }
+    }
+
+    /// Negates the number with `int_op` if it is an integer, or as a [`Real`]
+    /// otherwise.
+    fn negate<T>(self, int_op: impl FnOnce(Int) -> T) -> T
+    where
+        T: From<Number>,
+    {
+        match self.get_int() {
+            Some(n) => int_op(n),
+            None => Number::from(-self.get_real(false)).into(),
+        }
+    }
+}
+
+/// Checked arithmetic.
+impl Number {
+    /// Adds two numbers, returning `None` if both are integers and the sum is
+    /// out of the range of integer `Number`s.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(Number::from(u64::MAX).checked_add(Number::from(-1)), Some(Number::from(u64::MAX - 1)));
+    /// assert_eq!(Number::from(u64::MAX).checked_add(Number::from(1)), None);
+    /// assert_eq!(Number::from(1.5).checked_add(Number::from(1)), Some(Number::from(2.5)));
+    /// ```
+    pub fn checked_add(self, rhs: Number) -> Option<Number> {
+        self.binary(rhs, |a, b| int(a + b), |a, b| a + b)
+    }
+
+    /// Subtracts `rhs` from `self`, returning `None` if both are integers and
+    /// the difference is out of the range of integer `Number`s.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(Number::from(0).checked_sub(Number::from(u64::MAX)), Some(Number::NegInt(1)));
+    /// assert_eq!(Number::NegInt(0).checked_sub(Number::from(1)), None);
+    /// ```
+    pub fn checked_sub(self, rhs: Number) -> Option<Number> {
+        self.binary(rhs, |a, b| int(a - b), |a, b| a - b)
+    }
+
+    /// Multiplies two numbers, returning `None` if both are integers and the
+    /// product is out of the range of integer `Number`s.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(Number::from(-1).checked_mul(Number::from(u64::MAX)), Some(Number::NegInt(1)));
+    /// assert_eq!(Number::from(u64::MAX).checked_mul(Number::from(2)), None);
+    /// ```
+    pub fn checked_mul(self, rhs: Number) -> Option<Number> {
+        self.binary(rhs, |a, b| mul_exact(a, b).ok().and_then(int), |a, b| a * b)
+    }
+
+    /// Divides `self` by `rhs`, returning `None` if both are integers and
+    /// `rhs` is zero or the quotient is out of the range of integer `Number`s.
+    ///
+    /// Integer division truncates towards zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(Number::from(-7).checked_div(Number::from(2)), Some(Number::from(-3)));
+    /// assert_eq!(Number::from(1).checked_div(Number::from(0)), None);
+    /// assert_eq!(Number::NegInt(0).checked_div(Number::from(-1)), None);
+    /// assert_eq!(Number::from(1).checked_div(Number::from(0.0)), Some(Number::Inf { negative: false }));
+    /// ```
+    pub fn checked_div(self, rhs: Number) -> Option<Number> {
+        self.binary(rhs, |a, b| a.checked_div(b).and_then(int), |a, b| a / b)
+    }
+
+    /// Computes the remainder of dividing `self` by `rhs`, returning `None` if
+    /// both are integers and `rhs` is zero.
+    ///
+    /// The remainder has the same sign as `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(Number::from(-7).checked_rem(Number::from(2)), Some(Number::from(-1)));
+    /// assert_eq!(Number::from(1).checked_rem(Number::from(0)), None);
+    /// ```
+    pub fn checked_rem(self, rhs: Number) -> Option<Number> {
+        self.binary(rhs, |a, b| a.checked_rem(b).and_then(int), |a, b| a % b)
+    }
+
+    /// Negates the number, returning `None` if it is the integer `-2^64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(Number::from(u64::MAX).checked_neg(), Some(Number::NegInt(1)));
+    /// assert_eq!(Number::NegInt(0).checked_neg(), None);
+    /// ```
+    pub fn checked_neg(self) -> Option<Number> {
+        self.negate(|n| int(-n))
+    }
+}
+
+/// Wrapping arithmetic.
+impl Number {
+    /// Adds two numbers, wrapping around the range of integer `Number`s if
+    /// both are integers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(Number::from(u64::MAX).wrapping_add(Number::from(1)), Number::NegInt(0));
+    /// ```
+    pub fn wrapping_add(self, rhs: Number) -> Number {
+        self.binary(rhs, |a, b| wrap(a + b), |a, b| a + b)
+    }
+
+    /// Subtracts `rhs` from `self`, wrapping around the range of integer
+    /// `Number`s if both are integers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(Number::NegInt(0).wrapping_sub(Number::from(1)), Number::from(u64::MAX));
+    /// ```
+    pub fn wrapping_sub(self, rhs: Number) -> Number {
+        self.binary(rhs, |a, b| wrap(a - b), |a, b| a - b)
+    }
+
+    /// Multiplies two numbers, wrapping around the range of integer `Number`s
+    /// if both are integers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(Number::from(u64::MAX).wrapping_mul(Number::from(2)), Number::NegInt(u64::MAX - 1));
+    /// ```
+    pub fn wrapping_mul(self, rhs: Number) -> Number {
+        self.binary(rhs, |a, b| wrap(wrapping_mul(a, b)), |a, b| a * b)
+    }
+
+    /// Divides `self` by `rhs`, wrapping around the range of integer `Number`s
+    /// if both are integers. The only integer division that wraps is `-2^64 /
+    /// -1`, which results in `-2^64`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if both are integers and `rhs` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(Number::NegInt(0).wrapping_div(Number::from(-1)), Number::NegInt(0));
+    /// ```
+    pub fn wrapping_div(self, rhs: Number) -> Number {
+        self.binary(rhs, |a, b| wrap(div(a, b)), |a, b| a / b)
+    }
+
+    /// Negates the number, wrapping around the range of integer `Number`s if
+    /// it is an integer. The only integer that wraps is `-2^64`, which results
+    /// in itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(Number::NegInt(0).wrapping_neg(), Number::NegInt(0));
+    /// ```
+    pub fn wrapping_neg(self) -> Number {
+        self.negate(|n| wrap(-n))
+    }
+}
+
+/// Saturating arithmetic.
+impl Number {
+    /// Adds two numbers, saturating at the bounds of integer `Number`s if both
+    /// are integers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(Number::from(u64::MAX).saturating_add(Number::from(1)), Number::from(u64::MAX));
+    /// ```
+    pub fn saturating_add(self, rhs: Number) -> Number {
+        self.binary(rhs, |a, b| saturate(a + b), |a, b| a + b)
+    }
+
+    /// Subtracts `rhs` from `self`, saturating at the bounds of integer
+    /// `Number`s if both are integers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(Number::NegInt(0).saturating_sub(Number::from(1)), Number::NegInt(0));
+    /// ```
+    pub fn saturating_sub(self, rhs: Number) -> Number {
+        self.binary(rhs, |a, b| saturate(a - b), |a, b| a - b)
+    }
+
+    /// Multiplies two numbers, saturating at the bounds of integer `Number`s
+    /// if both are integers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(Number::NegInt(0).saturating_mul(Number::NegInt(0)), Number::from(u64::MAX));
+    /// ```
+    pub fn saturating_mul(self, rhs: Number) -> Number {
+        self.binary(rhs, |a, b| saturate(saturating_mul(a, b)), |a, b| a * b)
+    }
+
+    /// Divides `self` by `rhs`, saturating at the bounds of integer `Number`s
+    /// if both are integers. The only integer division that saturates is
+    /// `-2^64 / -1`, which results in `2^64 - 1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if both are integers and `rhs` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(Number::NegInt(0).saturating_div(Number::from(-1)), Number::from(u64::MAX));
+    /// ```
+    pub fn saturating_div(self, rhs: Number) -> Number {
+        self.binary(rhs, |a, b| saturate(div(a, b)), |a, b| a / b)
+    }
+
+    /// Negates the number, saturating at the bounds of integer `Number`s if it
+    /// is an integer. The only integer that saturates is `-2^64`, which results
+    /// in `2^64 - 1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(Number::NegInt(0).saturating_neg(), Number::from(u64::MAX));
+    /// ```
+    pub fn saturating_neg(self) -> Number {
+        self.negate(|n| saturate(-n))
+    }
+}
+
+impl Add for Number {
+    type Output = Number;
+
+    /// Adds two numbers. The sum of two integers is exact, and is converted to
+    /// the nearest float if it is out of the range of integer `Number`s.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(Number::from(1) + Number::from(2), Number::from(3));
+    /// assert_eq!(Number::from(u64::MAX) + Number::from(1), Number::from(18446744073709551616.0));
+    /// assert_eq!(Number::from(1) + Number::from(0.5), Number::from(1.5));
+    /// assert!((Number::Inf { negative: true } + Number::Inf { negative: false }).is_nan());
+    /// ```
+    fn add(self, rhs: Number) -> Number {
+        self.binary(rhs, |a, b| int_or_float(a + b), |a, b| a + b)
+    }
+}
+
+impl Sub for Number {
+    type Output = Number;
+
+    /// Subtracts `rhs` from `self`. The difference of two integers is exact,
+    /// and is converted to the nearest float if it is out of the range of
+    /// integer `Number`s.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(Number::from(1) - Number::from(2), Number::from(-1));
+    /// assert_eq!(Number::NegInt(0) - Number::from(1), Number::from(-18446744073709551617.0));
+    /// ```
+    fn sub(self, rhs: Number) -> Number {
+        self.binary(rhs, |a, b| int_or_float(a - b), |a, b| a - b)
+    }
+}
+
+impl Mul for Number {
+    type Output = Number;
+
+    /// Multiplies two numbers. The product of two integers is exact, and is
+    /// converted to the nearest float if it is out of the range of integer
+    /// `Number`s.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(Number::from(30) * Number::from(-3), Number::from(-90));
+    /// assert_eq!(Number::from(u64::MAX) * Number::from(u64::MAX), Number::from(3.402823669209385e38));
+    /// ```
+    fn mul(self, rhs: Number) -> Number {
+        self.binary(
+            rhs,
+            |a, b| mul_exact(a, b).map_or_else(Number::from, int_or_float),
+            |a, b| a * b,
+        )
+    }
+}
+
+impl Div for Number {
+    type Output = Number;
+
+    /// Divides `self` by `rhs`. Integer division truncates towards zero, and
+    /// the only quotient of two integers out of the range of integer `Number`s,
+    /// `-2^64 / -1`, is converted to a float.
+    ///
+    /// # Panics
+    ///
+    /// Panics if both are integers and `rhs` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(Number::from(7) / Number::from(2), Number::from(3));
+    /// assert_eq!(Number::from(7) / Number::from(2.0), Number::from(3.5));
+    /// assert_eq!(Number::from(1.0) / Number::from(0), Number::Inf { negative: false });
+    /// ```
+    fn div(self, rhs: Number) -> Number {
+        self.binary(rhs, |a, b| int_or_float(div(a, b)), |a, b| a / b)
+    }
+}
+
+impl Rem for Number {
+    type Output = Number;
+
+    /// Computes the remainder of dividing `self` by `rhs`, which has the same
+    /// sign as `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if both are integers and `rhs` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(Number::from(-7) % Number::from(3), Number::from(-1));
+    /// assert_eq!(Number::from(7.5) % Number::from(2), Number::from(1.5));
+    /// ```
+    fn rem(self, rhs: Number) -> Number {
+        self.binary(
+            rhs,
+            |a, b| match a.checked_rem(b) {
+                // the remainder is smaller than the divisor in magnitude
+                Some(r) => int_or_float(r),
+                None => panic!("attempt to calculate the remainder with a divisor of zero"),
+            },
+            |a, b| a % b,
+        )
+    }
+}
+
+impl Neg for Number {
+    type Output = Number;
+
+    /// Negates the number. The negation of `-2^64` is converted to a float.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::Number;
+    ///
+    /// assert_eq!(-Number::from(1), Number::from(-1));
+    /// assert_eq!(-Number::NegInt(0), Number::from(18446744073709551616.0));
+    /// assert_eq!(-Number::Inf { negative: false }, Number::Inf { negative: true });
+    /// ```
+    fn neg(self) -> Number {
+        self.negate(|n| int_or_float(-n))
+    }
+}
+
+macro_rules! impl_op_assign {
+    ($($trait:ident, $method:ident => $op:tt),* $(,)?) => {
+        $(
+            impl $trait for Number {
+                fn $method(&mut self, rhs: Number) {
+                    *self = self.clone() $op rhs;
+                }
+            }
+        )*
+    }
+}
+
+impl_op_assign! {
+    AddAssign, add_assign => +,
+    SubAssign, sub_assign => -,
+    MulAssign, mul_assign => *,
+    DivAssign, div_assign => /,
+    RemAssign, rem_assign => %,
+}
+
+#[cfg(test)]
+mod test {
+    use super::{MAX, MIN};
+    use crate::value::Number;
+
+    fn n(i: i128) -> Number {
+        Number::try_from(i).unwrap()
+    }
+
+    #[test]
+    fn test_integers() {
+        // the same with or without the `bigint` feature
+        let bounds = [MIN, MIN + 1, -1, 0, 1, MAX - 1, MAX];
+        for a in bounds {
+            for b in bounds {
+                let (x, y) = (n(a), n(b));
+                assert_eq!(x.clone().checked_add(y.clone()), super::from_i128(a + b));
+                assert_eq!(x.clone().checked_sub(y.clone()), super::from_i128(a - b));
+                assert_eq!(
+                    x.clone().saturating_add(y.clone()),
+                    n((a + b).clamp(MIN, MAX))
+                );
+                assert_eq!(
+                    x.clone().saturating_sub(y.clone()),
+                    n((a - b).clamp(MIN, MAX))
+                );
+
+                let wrapped = (a + b).rem_euclid(1 << 65);
+                let wrapped = if wrapped > MAX {
+                    wrapped - (1 << 65)
+                } else {
+                    wrapped
+                };
+                assert_eq!(x.clone().wrapping_add(y.clone()), n(wrapped));
+
+                if let Some(sum) = x.clone().checked_add(y.clone()) {
+                    assert_eq!(x.clone() + y.clone(), sum);
+                } else {
+                    assert_eq!(x.clone() + y.clone(), Number::Float((a + b) as f64));
+                }
+
+                if b != 0 {
+                    let quotient = super::from_i128(a / b).unwrap_or(Number::Float((a / b) as f64));
+                    assert_eq!(x.clone() / y.clone(), quotient);
+                    assert_eq!(x % y, n(a % b));
+                }
+            }
+        }
+
+        assert_eq!(n(MIN) * n(MIN), Number::Float(2f64.powi(128)));
+        assert_eq!(n(MIN) * n(MAX), Number::Float(-(2f64.powi(128))));
+        assert_eq!(n(MIN).checked_mul(n(-1)), None);
+        assert_eq!(n(MIN).checked_mul(n(MIN)), None);
+        assert_eq!(n(MIN).saturating_mul(n(MIN)), n(MAX));
+        assert_eq!(n(MIN).saturating_mul(n(MAX)), n(MIN));
+        assert_eq!(n(MIN).wrapping_mul(n(-1)), n(MIN));
+        assert_eq!(n(MAX).wrapping_mul(n(MAX)), n(1));
+        assert_eq!(n(-3) * n(4), n(-12));
+        assert_eq!(n(MIN).checked_rem(n(-1)), Some(n(0)));
+        assert_eq!(n(MIN).checked_div(n(-1)), None);
+        assert_eq!(n(MIN) / n(-1), Number::Float(2f64.powi(64)));
+        assert_eq!(n(MIN).checked_div(n(0)), None);
+        assert_eq!(n(MIN).checked_rem(n(0)), None);
+        assert_eq!(n(MIN).checked_neg(), None);
+        assert_eq!(-n(MIN), Number::Float(2f64.powi(64)));
+        assert_eq!(n(MAX).checked_neg(), Some(n(-MAX)));
+        assert_eq!(n(MAX).wrapping_neg(), n(-MAX));
+        assert_eq!(n(0).saturating_neg(), n(0));
+
+        let mut x = n(10);
+        x *= n(3);
+        x -= n(5);
+        x /= n(2);
+        x %= n(5);
+        x += n(1);
+        assert_eq!(x, n(3));
+    }
+
+    #[test]
+    #[cfg(feature = "bigint")]
+    fn test_big_integers() {
+        let big = |s: &str| s.parse::<Number>().unwrap();
+        let two_64 = big("18446744073709551616");
+        let two_128 = big("340282366920938463463374607431768211456");
+
+        // operations involving a big integer are exact
+        assert!(matches!(two_64, Number::BigInt(_)));
+        assert_eq!(two_64.clone() * two_64.clone(), two_128);
+        assert_eq!(
+            two_64.clone().checked_mul(two_64.clone()),
+            Some(two_128.clone())
+        );
+        assert_eq!(two_64.clone() + n(MAX), big("36893488147419103231"));
+        assert_eq!(
+            n(MIN).checked_sub(two_64.clone()),
+            Some(big("-36893488147419103232"))
+        );
+        assert!(matches!(two_64.clone() - n(1), Number::PosInt(u64::MAX)));
+        assert_eq!(-two_64.clone(), n(MIN));
+        assert_eq!(two_128.clone() / two_64.clone(), two_64);
+        assert_eq!((two_128.clone() + n(5)) % two_64.clone(), n(5));
+        assert_eq!(-two_128.clone() % n(7), n(-4));
+        assert_eq!(two_128.clone().checked_div(n(0)), None);
+        assert_eq!(two_128.clone().wrapping_add(n(1)), n(1));
+        assert_eq!(two_64.clone().wrapping_add(n(0)), n(MIN));
+        assert_eq!(two_128.clone().saturating_sub(n(1)), n(MAX));
+        assert_eq!((-two_128.clone()).saturating_add(n(1)), n(MIN));
+        assert_eq!(
+            two_128.clone() * Number::Float(0.5),
+            Number::Float(2f64.powi(127))
+        );
+
+        let mut x = two_128.clone();
+        x -= two_128;
+        assert_eq!(x, n(0));
+    }
+
+    #[test]
+    fn test_floats() {
+        const NAN: Number = Number::NaN;
+        const INF: Number = Number::Inf { negative: false };
+        const NEG_INF: Number = Number::Inf { negative: true };
+
+        assert_eq!(n(1) + Number::Float(0.5), Number::Float(1.5));
+        assert_eq!(
+            Number::Float(0.5).checked_mul(n(MIN)),
+            Some(n(MIN / 2).as_f64().into())
+        );
+        assert_eq!(Number::Float(1e308) * Number::Float(10.0), INF);
+        assert_eq!(n(-1) / Number::Float(0.0), NEG_INF);
+        assert_eq!(n(1).checked_div(Number::Float(-0.0)), Some(NEG_INF));
+        assert_eq!(INF - INF, NAN);
+        assert_eq!(NAN + n(1), NAN);
+        assert_eq!(Number::Float(1.0) % Number::Float(0.0), NAN);
+        assert_eq!(INF.saturating_add(n(1)), INF);
+        assert_eq!(NEG_INF.wrapping_neg(), INF);
+        assert_eq!(-NAN, NAN);
+        assert!(matches!(-Number::Float(0.0), Number::Float(f) if f.is_sign_negative()));
+        assert!(matches!(n(0) * Number::Float(-1.0), Number::Float(f) if f.is_sign_negative()));
+        assert!(matches!(-n(0), Number::PosInt(0)));
+    }
+
+    #[test]
+    #[cfg(feature = "decimal")]
+    fn test_decimals() {
+        let d = |s: &str| Number::Decimal(s.parse().unwrap());
+
+        assert_eq!(d("0.1") + d("0.2"), d("0.3"));
+        assert_eq!(d("1.10") - n(1), d("0.1"));
+        assert_eq!(n(3) * d("0.1"), d("0.3"));
+        assert_eq!(d("1.5") / d("0.5"), d("3.0"));
+        assert_eq!(d("7.5") % n(-2), d("1.5"));
+        assert_eq!(-d("0.1"), d("-0.1"));
+        assert_eq!(d("2.5").checked_mul(d("-2.0")), Some(d("-5.0")));
+        // inexact results fall back to floats
+        assert_eq!(n(1) / d("3.0"), Number::Float(1.0 / 3.0));
+        assert_eq!(d("0.1") + Number::Float(0.2), Number::Float(0.1 + 0.2));
+        assert_eq!(d("1e32767") * d("10.0"), Number::Inf { negative: false });
+        assert_eq!(d("-1.0") / d("0.0"), Number::Inf { negative: true });
+        assert!(matches!(n(1) + n(2), Number::PosInt(3)));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn test_div_by_zero() {
+        let _ = n(1) / n(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to calculate the remainder with a divisor of zero")]
+    fn test_rem_by_zero() {
+        let _ = n(1) % n(0);
+    }
+}
@@ -0,0 +1,158 @@
+use alloc::collections::BTreeMap;
+
+use crate::value::{Number, Path};
+
+/// The spelling of a number literal in Twic text.
+///
+/// [`from_str_with_reprs`](crate::de::from_str_with_reprs) records the
+/// spelling of every number literal that is not written the way the number is
+/// written by default in a [`NumberReprs`] table, next to the parsed value, and
+/// [`PrettyFormatter::number_reprs`](crate::ser::PrettyFormatter::number_reprs)
+/// writes numbers with the spellings of such a table. Each field applies only
+/// to the kinds of numbers it makes sense for, and is ignored for the others: a
+/// number normalized from `1e3` to the integer `1000` is still written `1000`.
+///
+/// # Examples
+///
+/// ```
+/// use twic::{
+///     Number, Path, Value,
+///     ser::PrettyFormatter,
+///     value::{NumberRepr, Radix},
+/// };
+///
+/// let (mut v, mut reprs) = twic::de::from_str_with_reprs("mask:0xFF00,id:007,step:+1.5E-3;").unwrap();
+/// assert_eq!(v["mask"], 65280);
+/// assert_eq!(twic::to_string(&v).unwrap(), "id:7,mask:65280,step:0.0015;");
+///
+/// let repr = NumberRepr {
+///     radix: Radix::Hexadecimal,
+///     min_digits: 4,
+///     ..NumberRepr::default()
+/// };
+/// v.as_map_mut().unwrap().insert("flags".into(), Value::from(10));
+/// reprs.insert("flags".parse::<Path>().unwrap(), Number::from(10), repr);
+/// assert_eq!(
+///     PrettyFormatter::new().number_reprs(reprs).to_string(&v).unwrap(),
+///     "flags: 0x000a, id: 007, mask: 0xFF00, step: +1.5E-3;",
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct NumberRepr {
+    /// The radix of integers.
+    pub radix: Radix,
+    /// The minimum number of digits of integers, reached with leading zeros.
+    /// The `0x` prefix of hexadecimal integers is not counted.
+    pub min_digits: usize,
+    /// Whether numbers that are not negative, except `nan`, are written with
+    /// an explicit `+`.
+    pub plus: bool,
+    /// The notation of floats and decimals.
+    pub notation: Notation,
+    /// Whether hexadecimal digits and the exponent marker are written in
+    /// uppercase.
+    pub uppercase: bool,
+    /// Whether exponents that are not negative are written with an explicit
+    /// `+`.
+    pub exponent_plus: bool,
+}
+
+/// The radix of an integer literal, see [`NumberRepr::radix`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Radix {
+    /// Decimal digits, e.g. `255`.
+    #[default]
+    Decimal,
+    /// Hexadecimal digits after a `0x` prefix, e.g. `0xff`.
+    Hexadecimal,
+}
+
+/// The notation of a float or decimal literal, see [`NumberRepr::notation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Notation {
+    /// Fixed notation for magnitudes from `1e-4` up to `1e16`, and scientific
+    /// notation otherwise, as numbers are written by default.
+    #[default]
+    Auto,
+    /// Fixed notation, e.g. `1500.0` or `0.00001`.
+    Fixed,
+    /// Scientific notation with one digit before the decimal point, e.g.
+    /// `1.5e3` or `1e-5`.
+    Scientific,
+}
+
+/// The spellings of the numbers of a [`Value`](crate::Value), by path, as
+/// recorded by [`from_str_with_reprs`](crate::de::from_str_with_reprs).
+///
+/// Each spelling is kept with the number it was recorded for, and applies to
+/// the number at its path only while that number is identical to it. Numbers
+/// changed after parsing, or moved to another path, e.g. by removing an
+/// element of a vector, are written the default way instead of taking the
+/// spelling of the number that was there.
+///
+/// # Examples
+///
+/// ```
+/// use twic::{Number, Path, value::Radix};
+///
+/// let (mut v, reprs) = twic::de::from_str_with_reprs("ids::0x1,0x2;;").unwrap();
+/// let path: Path = "ids[0]".parse().unwrap();
+/// assert_eq!(reprs.get(&path, &Number::from(1)).unwrap().radix, Radix::Hexadecimal);
+///
+/// // `2` moves to the path of `1`, and is no longer spelled `0x1`
+/// v["ids"].as_vector_mut().unwrap().remove(0);
+/// assert_eq!(reprs.get(&path, &v["ids"][0].as_number().unwrap()), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct NumberReprs {
+    reprs: BTreeMap<Path, (Number, NumberRepr)>,
+}
+
+impl NumberReprs {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        NumberReprs {
+            reprs: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the number of spellings in the table.
+    pub fn len(&self) -> usize {
+        self.reprs.len()
+    }
+
+    /// Checks if the table has no spellings.
+    pub fn is_empty(&self) -> bool {
+        self.reprs.is_empty()
+    }
+
+    /// Returns the spelling of `number` at `path`, if one is recorded there
+    /// for an identical number.
+    pub fn get(&self, path: &Path, number: &Number) -> Option<&NumberRepr> {
+        match self.reprs.get(path) {
+            Some((recorded, repr)) if recorded == number => Some(repr),
+            _ => None,
+        }
+    }
+
+    /// Sets the spelling of `number` at `path`, returning the spelling that
+    /// was recorded there before, if any.
+    pub fn insert(&mut self, path: Path, number: Number, repr: NumberRepr) -> Option<NumberRepr> {
+        self.reprs
+            .insert(path, (number, repr))
+            .map(|(_, repr)| repr)
+    }
+
+    /// Removes the spelling at `path`, returning it if there was one.
+    pub fn remove(&mut self, path: &Path) -> Option<NumberRepr> {
+        self.reprs.remove(path).map(|(_, repr)| repr)
+    }
+
+    /// Iterates over the paths, numbers and spellings of the table, in order
+    /// of their paths.
+    pub fn iter(&self) -> impl Iterator<Item = (&Path, &Number, &NumberRepr)> {
+        self.reprs
+            .iter()
+            .map(|(path, (number, repr))| (path, number, repr))
+    }
+}
@@ -176,6 +176,9 @@ pub mod consts {
     pub const TWO_POW_64_F32: f32 = 18446744073709551616.0;
     /// 2 to the power of 64 as f64.
     pub const TWO_POW_64_F64: f64 = 18446744073709551616.0;
+    /// 2 to the power of 128 as f64.
+    #[cfg_attr(feature = "bigint", allow(dead_code))]
+    pub const TWO_POW_128_F64: f64 = 340282366920938463463374607431768211456.0;
 }
 
 /// Trait for number types that can be created from an infinite value.
@@ -219,3 +222,21 @@ pub const fn neg_i65_to_i128(repr: u64) -> i128 {
 
     repr as i128 | I128_HIGH_64_BITS
 }
+
+/// Clamps an i128 to `[min, max]`, for saturating casts.
+#[cfg(feature = "decimal")]
+pub const fn saturate_i128(n: i128, min: i128, max: i128) -> i128 {
+    if n < min {
+        min
+    } else if n > max {
+        max
+    } else {
+        n
+    }
+}
+
+/// Clamps a u128 to `[0, max]`, for saturating casts.
+#[cfg(feature = "decimal")]
+pub const fn saturate_u128(n: u128, max: u128) -> u128 {
+    if n > max { max } else { n }
+}
@@ -0,0 +1,528 @@
+use alloc::{string::String, vec::Vec};
+use core::{fmt, str::FromStr};
+
+use super::{IndexInto, IndexMutResult, IndexResult, Value, ValueIndexError};
+
+/// A step of a [`Path`]: a map key or a vector index.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PathSegment {
+    /// A key of a map.
+    Key(String),
+    /// An index of a vector.
+    Index(usize),
+    /// Any index of a vector, written `[*]`, for paths matching every element
+    /// of a vector, such as those given to
+    /// [`PrettyFormatter::number_repr_at`](crate::ser::PrettyFormatter::number_repr_at).
+    /// It does not lead to a single value, so indexing with it fails with
+    /// [`ValueIndexError::IncompatibleIndexType`](super::ValueIndexError::IncompatibleIndexType).
+    AnyIndex,
+}
+
+impl PathSegment {
+    /// Returns the empty container this segment indexes into.
+    fn empty_container(&self) -> Value {
+        match self {
+            PathSegment::Key(_) => Value::map_empty(),
+            PathSegment::Index(_) | PathSegment::AnyIndex => Value::vector_empty(),
+        }
+    }
+}
+
+impl From<&str> for PathSegment {
+    fn from(key: &str) -> Self {
+        PathSegment::Key(key.into())
+    }
+}
+
+impl From<String> for PathSegment {
+    fn from(key: String) -> Self {
+        PathSegment::Key(key)
+    }
+}
+
+impl From<usize> for PathSegment {
+    fn from(index: usize) -> Self {
+        PathSegment::Index(index)
+    }
+}
+
+impl IndexInto for PathSegment {
+    fn index_into<'a>(&self, value: &'a Value) -> IndexResult<'a> {
+        match self {
+            PathSegment::Key(key) => key.index_into(value),
+            PathSegment::Index(index) => index.index_into(value),
+            PathSegment::AnyIndex => Err(ValueIndexError::IncompatibleIndexType),
+        }
+    }
+
+    fn index_into_mut<'a>(&self, value: &'a mut Value) -> IndexMutResult<'a> {
+        match self {
+            PathSegment::Key(key) => key.index_into_mut(value),
+            PathSegment::Index(index) => index.index_into_mut(value),
+            PathSegment::AnyIndex => Err(ValueIndexError::IncompatibleIndexType),
+        }
+    }
+
+    fn index_into_or_insert<'a>(&self, value: &'a mut Value) -> IndexMutResult<'a> {
+        match self {
+            PathSegment::Key(key) => key.index_into_or_insert(value),
+            PathSegment::Index(index) => index.index_into_or_insert(value),
+            PathSegment::AnyIndex => Err(ValueIndexError::IncompatibleIndexType),
+        }
+    }
+}
+
+/// A sequence of map keys and vector indices leading to a nested [`Value`].
+///
+/// Paths are parsed from text of the form `profile.users[1]`:
+///
+/// - Map keys are separated by `.`, and vector indices are written as decimal
+///   digits in brackets, such as `[0]`, with no `.` before them. `[*]` stands
+///   for any index, see [`PathSegment::AnyIndex`].
+/// - Map keys containing `.`, `[`, `]` or `"`, and the empty key, are written
+///   in double quotes, where `\"` and `\\` stand for `"` and `\`.
+/// - The empty string is the empty path, leading to the value itself.
+///
+/// A `Path` implements [`IndexInto`], so it can be used with [`Value::get`],
+/// [`Value::get_mut`], [`Value::get_or_insert`] and the `[]` operator to reach
+/// the nested value in one step. When inserting, `null` values along the path,
+/// including those just inserted, are replaced with an empty map or vector as
+/// needed by the next segment.
+///
+/// # Examples
+///
+/// ```
+/// use twic::{Path, Value};
+///
+/// let mut v: Value = "profile:users::alice,bob;,\"a.b\":1;;".parse().unwrap();
+/// let path: Path = "profile.users[1]".parse().unwrap();
+/// assert_eq!(v[&path], "bob");
+/// assert_eq!(v.pointer("profile.\"a.b\""), Some(&Value::from(1)));
+///
+/// v[&"profile.limits.max[2]".parse::<Path>().unwrap()] = Value::from(10);
+/// assert_eq!(twic::to_string(&v["profile"]["limits"]).unwrap(), "max::null,null,10;;");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Path {
+    segments: Vec<PathSegment>,
+}
+
+impl Path {
+    /// Creates an empty path, leading to the value itself.
+    pub fn new() -> Self {
+        Path::default()
+    }
+
+    /// Parses a path. See [`Path`] for the syntax.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text is not a valid path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::value::{Path, PathParseErrorKind, PathSegment};
+    ///
+    /// let path = Path::parse("users[0].\"full.name\"").unwrap();
+    /// assert_eq!(
+    ///     path.segments(),
+    ///     [PathSegment::from("users"), PathSegment::from(0), PathSegment::from("full.name")],
+    /// );
+    ///
+    /// let err = Path::parse("users..name").unwrap_err();
+    /// assert_eq!((err.kind(), err.offset()), (PathParseErrorKind::EmptyKey, 6));
+    /// ```
+    pub fn parse(s: &str) -> Result<Path, PathParseError> {
+        let mut parser = PathParser {
+            input: s,
+            offset: 0,
+        };
+        let mut path = Path::new();
+
+        while let Some(c) = parser.peek() {
+            let segment = match c {
+                '[' => parser.index()?,
+                '.' if path.segments.is_empty() => {
+                    return Err(parser.error(PathParseErrorKind::EmptyKey));
+                }
+                '.' => {
+                    parser.offset += 1;
+                    parser.key()?
+                }
+                _ if path.segments.is_empty() => parser.key()?,
+                _ => return Err(parser.error(PathParseErrorKind::UnexpectedChar)),
+            };
+            path.segments.push(segment);
+        }
+
+        Ok(path)
+    }
+
+    /// Returns the segments of the path.
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+
+    /// Appends a segment to the path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use twic::Path;
+    ///
+    /// let mut path = Path::new();
+    /// path.push("servers");
+    /// path.push(0);
+    /// path.push("host name");
+    /// assert_eq!(path.to_string(), "servers[0].host name");
+    /// ```
+    pub fn push<S: Into<PathSegment>>(&mut self, segment: S) {
+        self.segments.push(segment.into());
+    }
+
+    /// Removes the last segment of the path and returns it, or `None` if the
+    /// path is empty.
+    pub fn pop(&mut self) -> Option<PathSegment> {
+        self.segments.pop()
+    }
+}
+
+impl FromStr for Path {
+    type Err = PathParseError;
+
+    fn from_str(s: &str) -> Result<Path, PathParseError> {
+        Path::parse(s)
+    }
+}
+
+impl<S: Into<PathSegment>> FromIterator<S> for Path {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        Path {
+            segments: iter.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl fmt::Display for Path {
+    /// Formats the path in the syntax it is parsed from, quoting keys only
+    /// when necessary.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.segments.iter().enumerate() {
+            match segment {
+                PathSegment::Index(index) => write!(f, "[{}]", index)?,
+                PathSegment::AnyIndex => f.write_str("[*]")?,
+                PathSegment::Key(key) => {
+                    if i > 0 {
+                        f.write_str(".")?;
+                    }
+                    if key.is_empty() || key.contains(['.', '[', ']', '"']) {
+                        f.write_str("\"")?;
+                        for c in key.chars() {
+                            if c == '"' || c == '\\' {
+                                f.write_str("\\")?;
+                            }
+                            write!(f, "{}", c)?;
+                        }
+                        f.write_str("\"")?;
+                    } else {
+                        f.write_str(key)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl IndexInto for Path {
+    fn index_into<'a>(&self, value: &'a Value) -> IndexResult<'a> {
+        self.segments
+            .iter()
+            .try_fold(value, |value, segment| segment.index_into(value))
+    }
+
+    fn index_into_mut<'a>(&self, mut value: &'a mut Value) -> IndexMutResult<'a> {
+        for segment in &self.segments {
+            value = segment.index_into_mut(value)?;
+        }
+        Ok(value)
+    }
+
+    fn index_into_or_insert<'a>(&self, mut value: &'a mut Value) -> IndexMutResult<'a> {
+        for segment in &self.segments {
+            if value.is_null() {
+                *value = segment.empty_container();
+            }
+            value = segment.index_into_or_insert(value)?;
+        }
+        Ok(value)
+    }
+}
+
+/// A cursor over the text of a [`Path`] being parsed.
+struct PathParser<'a> {
+    input: &'a str,
+    offset: usize,
+}
+
+impl PathParser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.offset..].chars().next()
+    }
+
+    fn error(&self, kind: PathParseErrorKind) -> PathParseError {
+        PathParseError {
+            kind,
+            offset: self.offset,
+        }
+    }
+
+    /// Parses a quoted or unquoted key.
+    fn key(&mut self) -> Result<PathSegment, PathParseError> {
+        if self.peek() == Some('"') {
+            return self.quoted_key();
+        }
+
+        let rest = &self.input[self.offset..];
+        let len = rest.find(['.', '[', ']', '"']).unwrap_or(rest.len());
+        if len == 0 {
+            return Err(match rest.chars().next() {
+                None | Some('.' | '[') => self.error(PathParseErrorKind::EmptyKey),
+                Some(_) => self.error(PathParseErrorKind::UnexpectedChar),
+            });
+        }
+        self.offset += len;
+        Ok(PathSegment::Key(rest[..len].into()))
+    }
+
+    /// Parses a key in double quotes.
+    fn quoted_key(&mut self) -> Result<PathSegment, PathParseError> {
+        let start = self.offset;
+        self.offset += 1;
+        let mut key = String::new();
+        loop {
+            let Some(c) = self.peek() else {
+                return Err(PathParseError {
+                    kind: PathParseErrorKind::UnterminatedQuote,
+                    offset: start,
+                });
+            };
+            self.offset += c.len_utf8();
+            match c {
+                '"' => return Ok(PathSegment::Key(key)),
+                '\\' => match self.peek() {
+                    Some(c @ ('"' | '\\')) => {
+                        self.offset += 1;
+                        key.push(c);
+                    }
+                    _ => {
+                        self.offset -= 1;
+                        return Err(self.error(PathParseErrorKind::InvalidEscape));
+                    }
+                },
+                c => key.push(c),
+            }
+        }
+    }
+
+    /// Parses an index or `*` in brackets.
+    fn index(&mut self) -> Result<PathSegment, PathParseError> {
+        let start = self.offset;
+        let rest = &self.input[start + 1..];
+        if rest.starts_with("*]") {
+            self.offset += 3;
+            return Ok(PathSegment::AnyIndex);
+        }
+        let len = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let invalid = PathParseError {
+            kind: PathParseErrorKind::InvalidIndex,
+            offset: start,
+        };
+        if len == 0 || !rest[len..].starts_with(']') {
+            return Err(invalid);
+        }
+        let index = rest[..len].parse().map_err(|_| invalid)?;
+        self.offset += len + 2;
+        Ok(PathSegment::Index(index))
+    }
+}
+
+/// The reason parsing a [`Path`] failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathParseErrorKind {
+    /// A key is empty without being quoted, as in `a..b` or `a.`.
+    EmptyKey,
+    /// An index is neither `*` nor made of decimal digits closed by `]`, or
+    /// does not fit in a `usize`.
+    InvalidIndex,
+    /// A quoted key contains a `\` not followed by `"` or `\`.
+    InvalidEscape,
+    /// A quoted key is missing its closing quote.
+    UnterminatedQuote,
+    /// A character is not allowed here, such as a `]` or `"` in an unquoted
+    /// key, or a key following an index without a `.`.
+    UnexpectedChar,
+}
+
+impl fmt::Display for PathParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathParseErrorKind::EmptyKey => write!(f, "empty key"),
+            PathParseErrorKind::InvalidIndex => write!(f, "invalid index"),
+            PathParseErrorKind::InvalidEscape => write!(f, "invalid escape sequence"),
+            PathParseErrorKind::UnterminatedQuote => write!(f, "unterminated quoted key"),
+            PathParseErrorKind::UnexpectedChar => write!(f, "unexpected character"),
+        }
+    }
+}
+
+/// An error that occurred while parsing a [`Path`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PathParseError {
+    kind: PathParseErrorKind,
+    offset: usize,
+}
+
+impl PathParseError {
+    /// Returns the kind of the error.
+    pub fn kind(&self) -> PathParseErrorKind {
+        self.kind
+    }
+
+    /// Returns the byte offset of the error in the parsed text.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl fmt::Display for PathParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at offset {} of path", self.kind, self.offset)
+    }
+}
+
+impl core::error::Error for PathParseError {}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+
+    use super::{Path, PathParseErrorKind, PathSegment};
+    use crate::value::{IndexInto, Value, ValueIndexError};
+
+    #[test]
+    fn test_parse() {
+        for (s, segments) in [
+            ("", &[][..]),
+            ("a", &[PathSegment::from("a")]),
+            ("[3]", &[PathSegment::from(3)]),
+            (
+                "a b.c[0][12]",
+                &[
+                    PathSegment::from("a b"),
+                    PathSegment::from("c"),
+                    PathSegment::from(0),
+                    PathSegment::from(12),
+                ],
+            ),
+            (
+                r#""".x."y.[\"z\\]""#,
+                &[
+                    PathSegment::from(""),
+                    PathSegment::from("x"),
+                    PathSegment::from(r#"y.["z\]"#),
+                ],
+            ),
+            ("é.ü", &[PathSegment::from("é"), PathSegment::from("ü")]),
+            (
+                "a[*].b[2]",
+                &[
+                    PathSegment::from("a"),
+                    PathSegment::AnyIndex,
+                    PathSegment::from("b"),
+                    PathSegment::from(2),
+                ],
+            ),
+        ] {
+            let path = Path::parse(s).unwrap();
+            assert_eq!(path.segments(), segments, "{}", s);
+            assert_eq!(path.to_string(), s);
+            assert_eq!(path.to_string().parse::<Path>().unwrap(), path);
+        }
+
+        assert_eq!(Path::parse("a[007]").unwrap().to_string(), "a[7]");
+        assert_eq!(Path::parse("\"a\".\"b\"").unwrap().to_string(), "a.b");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for (s, kind, offset) in [
+            (".a", PathParseErrorKind::EmptyKey, 0),
+            ("a.", PathParseErrorKind::EmptyKey, 2),
+            ("a..b", PathParseErrorKind::EmptyKey, 2),
+            ("a.[0]", PathParseErrorKind::EmptyKey, 2),
+            ("a[", PathParseErrorKind::InvalidIndex, 1),
+            ("a[]", PathParseErrorKind::InvalidIndex, 1),
+            ("a[-1]", PathParseErrorKind::InvalidIndex, 1),
+            ("a[1", PathParseErrorKind::InvalidIndex, 1),
+            ("a[*", PathParseErrorKind::InvalidIndex, 1),
+            ("a[**]", PathParseErrorKind::InvalidIndex, 1),
+            (
+                "a[99999999999999999999999]",
+                PathParseErrorKind::InvalidIndex,
+                1,
+            ),
+            ("a]", PathParseErrorKind::UnexpectedChar, 1),
+            ("a\"b\"", PathParseErrorKind::UnexpectedChar, 1),
+            ("[0]a", PathParseErrorKind::UnexpectedChar, 3),
+            ("\"a\"b", PathParseErrorKind::UnexpectedChar, 3),
+            ("a.]", PathParseErrorKind::UnexpectedChar, 2),
+            ("a.\"b", PathParseErrorKind::UnterminatedQuote, 2),
+            ("\"a\\n\"", PathParseErrorKind::InvalidEscape, 2),
+        ] {
+            let err = Path::parse(s).unwrap_err();
+            assert_eq!((err.kind(), err.offset()), (kind, offset), "{}", s);
+        }
+    }
+
+    #[test]
+    fn test_index() {
+        let mut v: Value = "a:b::1,c:2;;;,x.y:3;".parse().unwrap();
+        let path = |s: &str| s.parse::<Path>().unwrap();
+
+        assert_eq!(v.get(path("")), Some(&v.clone()));
+        assert_eq!(v.get(path("a.b[1].c")), Some(&Value::from(2)));
+        assert_eq!(v.get(path("\"x.y\"")), Some(&Value::from(3)));
+        assert_eq!(v.get(path("a.b[2]")), None);
+        assert_eq!(
+            path("a.b.c").index_into(&v),
+            Err(ValueIndexError::IncompatibleIndexType)
+        );
+        assert_eq!(
+            path("a.b[0][0]").index_into(&v),
+            Err(ValueIndexError::NotIndexable)
+        );
+        assert_eq!(
+            path("a.b[*]").index_into(&v),
+            Err(ValueIndexError::IncompatibleIndexType)
+        );
+
+        *v.get_mut(path("a.b[0]")).unwrap() = Value::from(4);
+        assert_eq!(v[path("a.b[0]")], 4);
+        assert!(v.get_mut(path("a.z")).is_none());
+
+        v[path("a.z[1].w")] = Value::from(5);
+        assert_eq!(crate::to_string(&v["a"]["z"]).unwrap(), ":null,w:5;;");
+        assert_eq!(
+            path("a.b[0].w").index_into_or_insert(&mut v),
+            Err(ValueIndexError::NotIndexable)
+        );
+
+        let mut v = Value::Null;
+        *v.get_or_insert(path("[1].x")).unwrap() = Value::from(true);
+        assert_eq!(crate::to_string(&v).unwrap(), ":null,x:true;;");
+    }
+}
@@ -0,0 +1,565 @@
+#[cfg(any(feature = "bigint", feature = "decimal"))]
+use alloc::string::ToString;
+use alloc::{string::String, vec::Vec};
+
+use serde::ser::{self, Serialize};
+
+use super::{Map, Number, Value};
+#[cfg(any(feature = "bigint", feature = "decimal"))]
+use crate::de::parse_number_exact;
+use crate::ser::{SerializeError, SerializeErrorKind, write_number};
+
+impl Serialize for Number {
+    /// Serializes the number as a `u64`, `i64`, `i128` or `f64`, whichever is
+    /// the narrowest type holding it exactly. `NaN` and infinities are
+    /// serialized as the corresponding `f64` values.
+    ///
+    /// With the `bigint` feature, integers out of the range of `i128` and
+    /// `u128` are serialized as decimal strings in a private newtype struct,
+    /// which is written as a number by Twic serializers. So are decimals with
+    /// the `decimal` feature, keeping their exact value.
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match *self {
+            Number::PosInt(n) => serializer.serialize_u64(n),
+            Number::NegInt(_) => match self.get_i64() {
+                Some(n) => serializer.serialize_i64(n),
+                None => serializer.serialize_i128(self.as_i128()),
+            },
+            Number::Float(f) => serializer.serialize_f64(f),
+            Number::NaN => serializer.serialize_f64(f64::NAN),
+            Number::Inf { negative } => serializer.serialize_f64(if negative {
+                f64::NEG_INFINITY
+            } else {
+                f64::INFINITY
+            }),
+            #[cfg(feature = "bigint")]
+            Number::BigInt(ref n) => match (n.to_i128(), n.to_u128()) {
+                (Some(v), _) => serializer.serialize_i128(v),
+                (_, Some(v)) => serializer.serialize_u128(v),
+                // other formats see the decimal string
+                _ => serializer.serialize_newtype_struct(Number::SERDE_TOKEN, &n.to_string()),
+            },
+            #[cfg(feature = "decimal")]
+            Number::Decimal(d) => {
+                serializer.serialize_newtype_struct(Number::SERDE_TOKEN, &d.to_string())
+            }
+        }
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::Null => serializer.serialize_unit(),
+            Value::Boolean(b) => serializer.serialize_bool(*b),
+            Value::Number(n) => n.serialize(serializer),
+            Value::String(s) => serializer.serialize_str(s),
+            Value::Bytes(b) => serializer.serialize_bytes(b),
+            Value::Vector(v) => serializer.collect_seq(v),
+            Value::Map(m) => serializer.collect_map(m),
+        }
+    }
+}
+
+/// Converts a Rust value into a [`Value`].
+///
+/// The value is mapped in the same way as by [`to_string`](crate::serde::to_string),
+/// except that a vector may start with an empty map.
+///
+/// # Errors
+///
+/// Returns an error if the value contains a map key that is not a string, a
+/// character or an integer, an integer outside the range of integer
+/// [`Number`]s, or if the `Serialize` implementation fails.
+///
+/// # Examples
+///
+/// ```
+/// use serde::Serialize;
+/// use twic::Value;
+///
+/// #[derive(Serialize)]
+/// struct Config {
+///     name: String,
+///     offset: i128,
+/// }
+///
+/// let cfg = Config { name: "twic".into(), offset: -(1 << 64) };
+/// let v = twic::to_value(&cfg).unwrap();
+/// assert_eq!(v["name"], "twic");
+/// assert_eq!(v["offset"].as_number().unwrap().get_i128(), Some(-(1 << 64)));
+/// ```
+pub fn to_value<T: Serialize + ?Sized>(value: &T) -> Result<Value, SerializeError> {
+    value.serialize(Serializer)
+}
+
+/// A serde [`Serializer`](ser::Serializer) building [`Value`]s.
+struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    type SerializeSeq = SerializeVector;
+    type SerializeTuple = SerializeVector;
+    type SerializeTupleStruct = SerializeVector;
+    type SerializeTupleVariant = SerializeVector;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeMap;
+    type SerializeStructVariant = SerializeMap;
+
+    fn serialize_bool(self, v: bool) -> Result<Value, SerializeError> {
+        Ok(Value::Boolean(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Value, SerializeError> {
+        Ok(Value::number(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value, SerializeError> {
+        Ok(Value::number(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value, SerializeError> {
+        Ok(Value::number(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value, SerializeError> {
+        Ok(Value::number(v))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Value, SerializeError> {
+        Value::try_from(v).map_err(|_| SerializeError::new(SerializeErrorKind::IntegerOutOfRange))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Value, SerializeError> {
+        Ok(Value::number(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value, SerializeError> {
+        Ok(Value::number(v))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value, SerializeError> {
+        Ok(Value::number(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Value, SerializeError> {
+        Ok(Value::number(v))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Value, SerializeError> {
+        Value::try_from(v).map_err(|_| SerializeError::new(SerializeErrorKind::IntegerOutOfRange))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value, SerializeError> {
+        Ok(Value::number(f64::from(v)))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value, SerializeError> {
+        Ok(Value::number(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value, SerializeError> {
+        Ok(Value::String(v.into()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value, SerializeError> {
+        Ok(Value::String(v.into()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value, SerializeError> {
+        Ok(Value::bytes(v))
+    }
+
+    fn serialize_none(self) -> Result<Value, SerializeError> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value, SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value, SerializeError> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, SerializeError> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Value, SerializeError> {
+        Ok(Value::String(variant.into()))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Value, SerializeError> {
+        let value = value.serialize(self)?;
+        #[cfg(any(feature = "bigint", feature = "decimal"))]
+        if _name == Number::SERDE_TOKEN
+            && let Some(n) = value.as_str().and_then(parse_number_exact)
+        {
+            return Ok(Value::number(n));
+        }
+        Ok(value)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value, SerializeError> {
+        let mut map = Map::new();
+        map.insert(variant.into(), value.serialize(self)?);
+        Ok(Value::Map(map))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeVector, SerializeError> {
+        Ok(SerializeVector {
+            variant: None,
+            vector: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeVector, SerializeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeVector, SerializeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVector, SerializeError> {
+        Ok(SerializeVector {
+            variant: Some(variant),
+            vector: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeMap, SerializeError> {
+        Ok(SerializeMap {
+            variant: None,
+            map: Map::new(),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeMap, SerializeError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<SerializeMap, SerializeError> {
+        Ok(SerializeMap {
+            variant: Some(variant),
+            map: Map::new(),
+            key: None,
+        })
+    }
+}
+
+/// Wraps the value of a tuple or struct variant in a single-key map, if
+/// `variant` is given.
+fn wrap_variant(variant: Option<&'static str>, value: Value) -> Value {
+    match variant {
+        Some(variant) => {
+            let mut map = Map::new();
+            map.insert(variant.into(), value);
+            Value::Map(map)
+        }
+        None => value,
+    }
+}
+
+/// Builds a [`Value::Vector`], optionally wrapped in a variant map.
+struct SerializeVector {
+    variant: Option<&'static str>,
+    vector: Vec<Value>,
+}
+
+impl SerializeVector {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        self.vector.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Value, SerializeError> {
+        Ok(wrap_variant(self.variant, Value::Vector(self.vector)))
+    }
+}
+
+impl ser::SerializeSeq for SerializeVector {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, SerializeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SerializeVector {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, SerializeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeVector {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, SerializeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeVector {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, SerializeError> {
+        self.finish()
+    }
+}
+
+/// Builds a [`Value::Map`], optionally wrapped in a variant map.
+struct SerializeMap {
+    variant: Option<&'static str>,
+    map: Map,
+    /// The key whose value is to be serialized next.
+    key: Option<String>,
+}
+
+impl SerializeMap {
+    fn finish(self) -> Result<Value, SerializeError> {
+        Ok(wrap_variant(self.variant, Value::Map(self.map)))
+    }
+}
+
+/// Converts a serialized map key into a string. Strings, characters and unit
+/// variants are serialized as strings, and integers as numbers.
+fn map_key(key: Value) -> Result<String, SerializeError> {
+    match key {
+        Value::String(s) => Ok(s),
+        Value::Number(n) if n.is_integer() => {
+            let mut s = String::new();
+            write_number(&mut s, &n)?;
+            Ok(s)
+        }
+        _ => Err(SerializeError::new(SerializeErrorKind::KeyMustBeString)),
+    }
+}
+
+impl ser::SerializeMap for SerializeMap {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerializeError> {
+        self.key = Some(map_key(key.serialize(Serializer)?)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        let key = self
+            .key
+            .take()
+            .expect("`serialize_value` is called after `serialize_key`");
+        self.map.insert(key, value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, SerializeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for SerializeMap {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.map.insert(key.into(), value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, SerializeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeMap {
+    type Ok = Value;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.map.insert(key.into(), value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, SerializeError> {
+        self.finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::collections::BTreeMap;
+
+    use serde::Serialize;
+
+    use super::to_value;
+    use crate::{
+        ser::SerializeErrorKind,
+        value::{Number, Value},
+    };
+
+    #[derive(Serialize)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Rect(u32, u32),
+        Polygon { sides: u8 },
+    }
+
+    #[test]
+    fn test_numbers() {
+        for n in [
+            Number::PosInt(u64::MAX),
+            Number::NegInt(u64::MAX),
+            Number::NegInt(1 << 63),
+            Number::NegInt((1 << 63) - 1),
+            Number::NegInt(0),
+            Number::Float(-0.5),
+            Number::NaN,
+            Number::Inf { negative: true },
+        ] {
+            let v = to_value(&n).unwrap();
+            let m = v.as_number().unwrap();
+            assert_eq!(core::mem::discriminant(&m), core::mem::discriminant(&n));
+            assert!(m.is_nan() && n.is_nan() || m == n);
+        }
+
+        assert_eq!(
+            crate::serde::to_string(&Value::number(Number::NegInt(0))).unwrap(),
+            "-18446744073709551616"
+        );
+        #[cfg(not(feature = "bigint"))]
+        assert_eq!(
+            to_value(&(1u128 << 64)).unwrap_err().kind(),
+            &SerializeErrorKind::IntegerOutOfRange
+        );
+        #[cfg(feature = "bigint")]
+        {
+            assert_eq!(
+                to_value(&u128::MAX)
+                    .unwrap()
+                    .as_number()
+                    .unwrap()
+                    .get_u128(),
+                Some(u128::MAX)
+            );
+
+            // integers beyond 128 bits pass through serde as a private token
+            let s = "n:-1234567890123456789012345678901234567890123;";
+            let v: Value = crate::serde::from_str(s).unwrap();
+            assert!(matches!(v["n"], Value::Number(Number::BigInt(_))));
+            assert_eq!(crate::serde::to_string(&v).unwrap(), s);
+            assert_eq!(to_value(&v).unwrap(), v);
+            assert_eq!(
+                crate::from_value::<Number>(v["n"].clone()).unwrap(),
+                v["n"].as_number().unwrap()
+            );
+            assert!(crate::serde::from_str::<u128>(&s[2..s.len() - 1]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_values() {
+        let v = Value::vector([Value::map_empty(), Value::from("x")]);
+        assert_eq!(to_value(&v).unwrap(), v);
+        assert!(crate::serde::to_string(&v).is_err());
+
+        assert_eq!(to_value(&Shape::Empty).unwrap(), "Empty");
+        assert_eq!(
+            to_value(&Shape::Circle(1.5)).unwrap(),
+            Value::map_from([("Circle", 1.5)])
+        );
+        assert_eq!(
+            to_value(&Shape::Rect(2, 3)).unwrap(),
+            Value::map_from([("Rect", Value::vector_from([2, 3]))])
+        );
+        assert_eq!(
+            to_value(&Shape::Polygon { sides: 6 }).unwrap(),
+            Value::map_from([("Polygon", Value::map_from([("sides", 6)]))])
+        );
+        assert_eq!(
+            to_value(&BTreeMap::from([(-1, 'a')])).unwrap(),
+            Value::map_from([("-1", "a")])
+        );
+        assert_eq!(
+            to_value(&BTreeMap::from([(true, 1)])).unwrap_err().kind(),
+            &SerializeErrorKind::KeyMustBeString
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use alloc::vec::Vec;
+use core::hash::Hasher;
+
+/// A hasher recording everything written to it.
+#[derive(Default)]
+pub(crate) struct Recorder(Vec<u8>);
+
+impl Hasher for Recorder {
+    fn finish(&self) -> u64 {
+        0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+}
+
+/// Returns the bytes written to a hasher by `hash`, so that tests can check
+/// what values feed to a hasher without depending on a hash function.
+pub(crate) fn hashed(hash: impl FnOnce(&mut Recorder)) -> Vec<u8> {
+    let mut h = Recorder::default();
+    hash(&mut h);
+    h.0
+}